@@ -0,0 +1,69 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::fmt;
+
+use hyper::Method;
+
+/// Lifecycle operations on a single container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerAction {
+    Start,
+    Stop,
+    Restart,
+    Kill,
+    Pause,
+    Unpause,
+    Remove,
+}
+
+impl ContainerAction {
+    /// Method and path, relative to the API version prefix. Removal is
+    /// forced, killing a running container first; it is confirmed anyway.
+    #[must_use]
+    pub fn request(self, container_id: &str) -> (Method, String) {
+        let verb = match self {
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+            Self::Kill => "kill",
+            Self::Pause => "pause",
+            Self::Unpause => "unpause",
+            Self::Remove => return (Method::DELETE, format!("/containers/{container_id}?force=1")),
+        };
+        (Method::POST, format!("/containers/{container_id}/{verb}"))
+    }
+
+    /// Whether the action loses state and should be confirmed first.
+    #[must_use]
+    pub fn is_destructive(self) -> bool {
+        matches!(self, Self::Stop | Self::Restart | Self::Kill | Self::Remove)
+    }
+
+    /// Past tense, for result messages.
+    #[must_use]
+    pub fn done(self) -> &'static str {
+        match self {
+            Self::Start => "started",
+            Self::Stop => "stopped",
+            Self::Restart => "restarted",
+            Self::Kill => "killed",
+            Self::Pause => "paused",
+            Self::Unpause => "unpaused",
+            Self::Remove => "removed",
+        }
+    }
+}
+
+impl fmt::Display for ContainerAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+            Self::Kill => "kill",
+            Self::Pause => "pause",
+            Self::Unpause => "unpause",
+            Self::Remove => "remove",
+        })
+    }
+}
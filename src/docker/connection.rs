@@ -2,117 +2,725 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use http_body_util::BodyExt;
-use hyper::Request;
+use http_body_util::{BodyExt, Collected, Full, combinators::UnsyncBoxBody};
+use hyper::http::uri::InvalidUri;
+use hyper::body::Incoming;
+use hyper::upgrade::Upgraded;
+use hyper::{Method, Request, Response, StatusCode, Uri};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::connect::{Connect, HttpConnector};
 use hyper_util::client::legacy::Client as HyperClient;
-use hyper_util::rt::TokioExecutor;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+#[cfg(target_os = "linux")]
 use hyperlocal::UnixConnector;
-use log::{debug, error, trace};
+use log::{debug, error, info, trace, warn};
+use serde::Deserialize;
+use serde_json::json;
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
+use std::sync::Arc;
 
-use super::models::{Container, ContainerStats};
+use super::actions::ContainerAction;
+use super::endpoint::DockerEndpoint;
+use super::logs::{LogOptions, LogReader};
+use super::models::{
+    Container, ContainerDetails, ContainerStats, ContainerTop, DiskUsage, DockerEvent, ExecCreated, ExecDetails,
+    FilesystemChange, ImageHistory, ImageSummary, Network, Pod, PruneReport, SystemInfo, VersionInfo, Volume,
+    VolumeList,
+};
+use super::ssh::{SshConnection, SshConnector};
+use super::stream::JsonLines;
+use super::tls::{self, TlsOptions};
+use super::version::{ApiVersion, MAX_API_VERSION, VersionState, negotiate};
 use crate::error::WhaleError;
 
+/// A response body that is read as it arrives, for streaming endpoints.
+pub type ResponseBody = UnsyncBoxBody<Bytes, WhaleError>;
+
+/// A connection the daemon has switched to a raw byte stream.
+pub type Hijacked = TokioIo<Upgraded>;
+
+/// A Docker Engine API client. Transports only provide [`Connection::request`],
+/// plus [`Connection::request_with_body`] and [`Connection::hijack`] for execs;
+/// the typed endpoints are built on top of them, so tests can swap in a mock.
 #[async_trait]
-#[allow(dead_code)]  // Used through trait objects
 pub trait Connection: Send + Sync {
-    async fn list_containers(&self) -> Result<Vec<Container>, WhaleError>;
-    async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStats, WhaleError>;
+    /// The endpoint this connection talks to.
+    fn endpoint(&self) -> &DockerEndpoint;
+
+    /// The negotiated API version and daemon details.
+    fn version(&self) -> &VersionState;
+
+    /// Issue a request against the Engine API and return the body of a
+    /// successful response. Non-2xx responses become [`WhaleError::Api`].
+    async fn request(&self, method: Method, path: &str) -> Result<Bytes, WhaleError>;
+
+    /// Like [`Connection::request`], but hand back the body unread so
+    /// long-lived responses such as `/events` can be consumed incrementally.
+    /// The default buffers the whole response, which is enough for mocks.
+    async fn request_stream(&self, method: Method, path: &str) -> Result<ResponseBody, WhaleError> {
+        let body = self.request(method, path).await?;
+        Ok(Full::new(body).map_err(|never| match never {}).boxed_unsync())
+    }
+
+    /// Like [`Connection::request`], with a JSON request body. The default
+    /// refuses, for mocks that never send one.
+    async fn request_with_body(&self, method: Method, path: &str, body: Bytes) -> Result<Bytes, WhaleError> {
+        let _ = body;
+        Err(WhaleError::Connection(format!("{method} {path}: request bodies are not supported")))
+    }
+
+    /// POST `body` to `path` and take over the connection once the daemon
+    /// upgrades it, as `/exec/{id}/start` does for interactive sessions.
+    async fn hijack(&self, path: &str, body: Bytes) -> Result<Hijacked, WhaleError> {
+        let _ = body;
+        Err(WhaleError::Connection(format!("POST {path}: upgrades are not supported")))
+    }
+
+    /// Prefix `path` with the negotiated API version.
+    fn api_path(&self, path: &str) -> String {
+        format!("/v{}{}", self.version().api(), path)
+    }
+
+    /// Check the daemon is reachable, then settle on the highest API version
+    /// both sides support. Until this runs, requests use [`MAX_API_VERSION`].
+    async fn negotiate_version(&self) -> Result<VersionInfo, WhaleError> {
+        self.request(Method::GET, "/_ping").await?;
+        let body = self.request(Method::GET, "/version").await?;
+        let info: VersionInfo = serde_json::from_slice(&body)?;
+        let api = negotiate(&info)?;
+        if api < MAX_API_VERSION {
+            info!("Daemon supports API {}, downgrading from {}", info.api_version, MAX_API_VERSION);
+        }
+        if info.is_podman() {
+            info!("Detected Podman {}", info.version);
+        }
+        self.version().set(api, info.clone());
+        Ok(info)
+    }
+
+    /// Details of the daemon's host, such as its hostname, CPUs and memory.
+    async fn info(&self) -> Result<SystemInfo, WhaleError> {
+        debug!("Requesting daemon info");
+        let body = self.request(Method::GET, &self.api_path("/info")).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    async fn list_containers(&self) -> Result<Vec<Container>, WhaleError> {
+        self.list_containers_with(&[]).await
+    }
+
+    /// List containers matching the given `filters`, e.g. `[("id", &[id])]`.
+    async fn list_containers_with(&self, filters: &[(&str, &[&str])]) -> Result<Vec<Container>, WhaleError> {
+        debug!("Listing containers");
+        let mut path = "/containers/json?all=true&health=true".to_string();
+        if !filters.is_empty() {
+            path.push_str("&filters=");
+            path.push_str(&encode_filters(filters));
+        }
+        let body = self.request(Method::GET, &self.api_path(&path)).await?;
+        let mut containers = parse_containers(&body)?;
+        for container in &mut containers {
+            container.fill_missing_state();
+        }
+        if self.is_podman() {
+            // The compat endpoint doesn't report pods, so join them in
+            match self.list_pods().await {
+                Ok(pods) => assign_pods(&mut containers, &pods),
+                Err(e) => warn!("Failed to list Podman pods: {e}"),
+            }
+        }
+        Ok(containers)
+    }
+
+    /// The list entry for a single container, or `None` once it is gone.
+    async fn get_container(&self, container_id: &str) -> Result<Option<Container>, WhaleError> {
+        // The id filter matches prefixes, so pick the exact match
+        let containers = self.list_containers_with(&[("id", &[container_id])]).await?;
+        Ok(containers.into_iter().find(|c| c.id == container_id))
+    }
+
+    async fn inspect_container(&self, container_id: &str) -> Result<ContainerDetails, WhaleError> {
+        let path = self.api_path(&format!("/containers/{container_id}/json"));
+        debug!("Inspecting container: {}", container_id);
+        let body = self.request(Method::GET, &path).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// The whole inspect document, for showing fields the models leave out.
+    async fn inspect_container_json(&self, container_id: &str) -> Result<serde_json::Value, WhaleError> {
+        let path = self.api_path(&format!("/containers/{container_id}/json"));
+        debug!("Inspecting container: {}", container_id);
+        let body = self.request(Method::GET, &path).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Processes running in a container, as listed by `ps` with `ps_args`.
+    async fn top(&self, container_id: &str, ps_args: &str) -> Result<ContainerTop, WhaleError> {
+        let path = self.api_path(&format!("/containers/{container_id}/top?ps_args={}", encode_query_value(ps_args)));
+        trace!("Listing processes of container: {}", container_id);
+        let body = self.request(Method::GET, &path).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Paths changed in a container's writable layer.
+    async fn container_changes(&self, container_id: &str) -> Result<Vec<FilesystemChange>, WhaleError> {
+        let path = self.api_path(&format!("/containers/{container_id}/changes"));
+        debug!("Listing filesystem changes of container: {}", container_id);
+        let body = self.request(Method::GET, &path).await?;
+        // An unchanged container's list is `null`
+        let changes: Option<Vec<FilesystemChange>> = serde_json::from_slice(&body)?;
+        Ok(changes.unwrap_or_default())
+    }
+
+    /// Local images, without the intermediate layers.
+    async fn list_images(&self) -> Result<Vec<ImageSummary>, WhaleError> {
+        debug!("Listing images");
+        let body = self.request(Method::GET, &self.api_path("/images/json")).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// The layers of an image and the instructions that created them.
+    async fn image_history(&self, image_id: &str) -> Result<Vec<ImageHistory>, WhaleError> {
+        let path = self.api_path(&format!("/images/{image_id}/history"));
+        debug!("Requesting history of image: {}", image_id);
+        let body = self.request(Method::GET, &path).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Remove an image and untag it everywhere. Fails while a container
+    /// uses it.
+    async fn remove_image(&self, image_id: &str) -> Result<(), WhaleError> {
+        info!("Removing image {}", image_id);
+        self.request(Method::DELETE, &self.api_path(&format!("/images/{image_id}"))).await?;
+        Ok(())
+    }
+
+    /// Remove every dangling image no container uses, or with `all` every
+    /// image no container uses.
+    async fn prune_images(&self, all: bool) -> Result<PruneReport, WhaleError> {
+        info!("Pruning {} images", if all { "unused" } else { "dangling" });
+        let dangling = if all { "false" } else { "true" };
+        let path = format!("/images/prune?filters={}", encode_filters(&[("dangling", &[dangling])]));
+        let body = self.request(Method::POST, &self.api_path(&path)).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    async fn list_volumes(&self) -> Result<Vec<Volume>, WhaleError> {
+        debug!("Listing volumes");
+        let body = self.request(Method::GET, &self.api_path("/volumes")).await?;
+        let list: VolumeList = serde_json::from_slice(&body)?;
+        Ok(list.volumes)
+    }
+
+    /// Remove a volume. Fails while a container mounts it.
+    async fn remove_volume(&self, name: &str) -> Result<(), WhaleError> {
+        info!("Removing volume {}", name);
+        self.request(Method::DELETE, &self.api_path(&format!("/volumes/{name}"))).await?;
+        Ok(())
+    }
+
+    /// Remove every volume no container mounts.
+    async fn prune_volumes(&self) -> Result<PruneReport, WhaleError> {
+        info!("Pruning unused volumes");
+        // Since API 1.42 only anonymous volumes are pruned unless asked for all
+        let path = if self.version().api() >= ApiVersion::new(1, 42) {
+            format!("/volumes/prune?filters={}", encode_filters(&[("all", &["true"])]))
+        } else {
+            "/volumes/prune".to_string()
+        };
+        let body = self.request(Method::POST, &self.api_path(&path)).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Networks, without their attached containers.
+    async fn list_networks(&self) -> Result<Vec<Network>, WhaleError> {
+        debug!("Listing networks");
+        let body = self.request(Method::GET, &self.api_path("/networks")).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// A network with the containers attached to it.
+    async fn inspect_network(&self, network_id: &str) -> Result<Network, WhaleError> {
+        let path = self.api_path(&format!("/networks/{network_id}"));
+        debug!("Inspecting network: {}", network_id);
+        let body = self.request(Method::GET, &path).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Attach a container to a network; it gets an address right away if
+    /// it is running.
+    async fn connect_network(&self, network_id: &str, container_id: &str) -> Result<(), WhaleError> {
+        info!("Connecting container {} to network {}", container_id, network_id);
+        let path = self.api_path(&format!("/networks/{network_id}/connect"));
+        let body = json!({ "Container": container_id });
+        self.request_with_body(Method::POST, &path, Bytes::from(body.to_string())).await?;
+        Ok(())
+    }
+
+    async fn disconnect_network(&self, network_id: &str, container_id: &str) -> Result<(), WhaleError> {
+        info!("Disconnecting container {} from network {}", container_id, network_id);
+        let path = self.api_path(&format!("/networks/{network_id}/disconnect"));
+        let body = json!({ "Container": container_id });
+        self.request_with_body(Method::POST, &path, Bytes::from(body.to_string())).await?;
+        Ok(())
+    }
+
+    /// Remove all build cache not used by a running build.
+    async fn prune_build_cache(&self) -> Result<PruneReport, WhaleError> {
+        info!("Pruning build cache");
+        let body = self.request(Method::POST, &self.api_path("/build/prune?all=true")).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Space used by images, containers, volumes and the build cache. The
+    /// daemon walks every volume for this, so it can take a while.
+    async fn disk_usage(&self) -> Result<DiskUsage, WhaleError> {
+        debug!("Requesting disk usage");
+        let body = self.request(Method::GET, &self.api_path("/system/df")).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Follow a container's stdout and stderr. The stream stays open while
+    /// the container runs; TTY containers are read without demultiplexing.
+    async fn logs(&self, container_id: &str, options: &LogOptions) -> Result<LogReader, WhaleError> {
+        let tty = self.inspect_container(container_id).await?.config.tty;
+        let path = self.api_path(&format!("/containers/{container_id}/logs?{}", options.query()));
+        debug!("Following logs of container: {}", container_id);
+        let body = self.request_stream(Method::GET, &path).await?;
+        Ok(LogReader::new(body, tty))
+    }
+
+    /// Create a TTY exec of `cmd` in a running container, returning its ID.
+    async fn create_exec(&self, container_id: &str, cmd: &[&str], env: &[String]) -> Result<String, WhaleError> {
+        let config = json!({
+            "AttachStdin": true,
+            "AttachStdout": true,
+            "AttachStderr": true,
+            "Tty": true,
+            "Cmd": cmd,
+            "Env": env,
+        });
+        let path = self.api_path(&format!("/containers/{container_id}/exec"));
+        debug!("Creating exec in container: {}", container_id);
+        let body = self.request_with_body(Method::POST, &path, Bytes::from(config.to_string())).await?;
+        let created: ExecCreated = serde_json::from_slice(&body)?;
+        Ok(created.id)
+    }
+
+    /// Start an exec attached, returning its terminal as a raw stream.
+    async fn start_exec(&self, exec_id: &str) -> Result<Hijacked, WhaleError> {
+        let path = self.api_path(&format!("/exec/{exec_id}/start"));
+        debug!("Starting exec: {}", exec_id);
+        self.hijack(&path, Bytes::from(json!({ "Detach": false, "Tty": true }).to_string()))
+            .await
+    }
+
+    /// Resize an exec's TTY; only works once it has started.
+    async fn resize_exec(&self, exec_id: &str, width: u16, height: u16) -> Result<(), WhaleError> {
+        let path = self.api_path(&format!("/exec/{exec_id}/resize?h={height}&w={width}"));
+        self.request(Method::POST, &path).await?;
+        Ok(())
+    }
+
+    async fn inspect_exec(&self, exec_id: &str) -> Result<ExecDetails, WhaleError> {
+        let body = self.request(Method::GET, &self.api_path(&format!("/exec/{exec_id}/json"))).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Run a lifecycle action on a container. Starting a running container
+    /// or stopping a stopped one is not an error.
+    async fn container_action(&self, container_id: &str, action: ContainerAction) -> Result<(), WhaleError> {
+        let (method, path) = action.request(container_id);
+        info!("Requesting {} of container {}", action, container_id);
+        match self.request(method, &self.api_path(&path)).await {
+            Ok(_) | Err(WhaleError::Api { status: 304, .. }) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Remove every stopped container.
+    async fn prune_containers(&self) -> Result<PruneReport, WhaleError> {
+        info!("Pruning stopped containers");
+        let body = self.request(Method::POST, &self.api_path("/containers/prune")).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Subscribe to a container's stats. The daemon sends a sample about
+    /// once a second, with `precpu_stats` filled from the previous one.
+    async fn stats_stream(&self, container_id: &str) -> Result<JsonLines<ContainerStats>, WhaleError> {
+        let path = self.api_path(&format!("/containers/{container_id}/stats?stream=true"));
+        debug!("Subscribing to stats for container: {}", container_id);
+        let body = self.request_stream(Method::GET, &path).await?;
+        Ok(JsonLines::new(body))
+    }
+
+    /// Subscribe to container events. The stream stays open until the
+    /// daemon goes away or the returned reader is dropped.
+    async fn events(&self) -> Result<JsonLines<DockerEvent>, WhaleError> {
+        debug!("Subscribing to container events");
+        let path = format!("/events?filters={}", encode_filters(&[("type", &["container"])]));
+        let body = self.request_stream(Method::GET, &self.api_path(&path)).await?;
+        Ok(JsonLines::new(body))
+    }
+
+    /// Whether the daemon identified itself as Podman during negotiation.
+    fn is_podman(&self) -> bool {
+        self.version().daemon().is_some_and(|daemon| daemon.is_podman())
+    }
+
+    /// Podman's pods, from its native (non-Docker) API.
+    async fn list_pods(&self) -> Result<Vec<Pod>, WhaleError> {
+        let body = self.request(Method::GET, "/libpod/pods/json").await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
 }
 
-#[allow(dead_code)]  // Used as the factory for creating connections
-pub struct ConnectionFactory;
+/// Encode Engine API filters as the URL-encoded JSON map the daemon expects.
+#[must_use]
+pub fn encode_filters(filters: &[(&str, &[&str])]) -> String {
+    let map: serde_json::Map<String, serde_json::Value> = filters
+        .iter()
+        .map(|(key, values)| ((*key).to_string(), serde_json::json!(values)))
+        .collect();
+    encode_query_value(&serde_json::Value::Object(map).to_string())
+}
 
-impl ConnectionFactory {
-    #[allow(dead_code)]  // Used to create connections
-    pub fn create() -> impl Connection {
-        #[cfg(target_os = "linux")]
-        {
-            UnixSocketConnection::new()
+/// Percent-encode everything but the unreserved characters of RFC 3986.
+#[must_use]
+pub fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn assign_pods(containers: &mut [Container], pods: &[Pod]) {
+    for pod in pods {
+        for member in &pod.containers {
+            if let Some(container) = containers.iter_mut().find(|c| c.id == member.id) {
+                container.pod_name = Some(pod.name.clone());
+            }
         }
+    }
+}
+
+pub struct ConnectionFactory;
 
-        #[cfg(target_os = "windows")]
-        {
-            HttpConnection::new()
+impl ConnectionFactory {
+    /// Create the transport matching the endpoint's scheme. TCP endpoints use
+    /// TLS when `DOCKER_TLS_VERIFY` or `DOCKER_TLS` is set; SSH endpoints
+    /// tunnel through the `ssh` binary on `PATH`.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the TLS material can't be loaded, or on platforms without
+    /// Unix sockets.
+    pub fn create(endpoint: &DockerEndpoint) -> Result<Arc<dyn Connection>, WhaleError> {
+        match endpoint {
+            #[cfg(target_os = "linux")]
+            DockerEndpoint::Unix(socket) => Ok(Arc::new(UnixSocketConnection::new(socket.clone()))),
+            #[cfg(not(target_os = "linux"))]
+            DockerEndpoint::Unix(_) => Err(WhaleError::Connection(
+                "Unix sockets are not supported on this platform".to_string(),
+            )),
+            DockerEndpoint::Tcp(address) => Ok(Arc::new(HttpConnection::new(
+                address,
+                TlsOptions::from_env().as_ref(),
+            )?)),
+            DockerEndpoint::Ssh { user, host, port } => Ok(Arc::new(SshConnection::new(
+                endpoint.clone(),
+                SshConnector::new("ssh", user.as_deref(), host, *port),
+            ))),
         }
     }
 }
 
 #[cfg(target_os = "linux")]
 pub struct UnixSocketConnection {
-    client: HyperClient<UnixConnector, http_body_util::Empty<Bytes>>,
+    client: HyperClient<UnixConnector, Full<Bytes>>,
+    endpoint: DockerEndpoint,
+    version: VersionState,
+    socket: PathBuf,
 }
 
 #[cfg(target_os = "linux")]
 impl UnixSocketConnection {
-    pub fn new() -> Self {
-        debug!("Creating new UnixSocketConnection");
+    #[must_use]
+    pub fn new(socket: PathBuf) -> Self {
+        debug!("Creating new UnixSocketConnection to {}", socket.display());
         Self {
             client: HyperClient::builder(TokioExecutor::new()).build(UnixConnector),
+            endpoint: DockerEndpoint::Unix(socket.clone()),
+            version: VersionState::default(),
+            socket,
         }
     }
+}
 
-    async fn make_request(&self, path: &str) -> Result<Bytes, WhaleError> {
-        let uri = hyperlocal::Uri::new("/var/run/docker.sock", path);
-        debug!("Making request to {}", path);
+#[cfg(target_os = "linux")]
+#[async_trait]
+impl Connection for UnixSocketConnection {
+    fn endpoint(&self) -> &DockerEndpoint {
+        &self.endpoint
+    }
 
-        let req = Request::builder()
-            .uri(uri)
-            .header("Host", "")
-            .body(http_body_util::Empty::new())
-            .map_err(|e| WhaleError::Connection(e.to_string()))?;
+    fn version(&self) -> &VersionState {
+        &self.version
+    }
 
-        let response = self
-            .client
-            .request(req)
-            .await
-            .map_err(|e| WhaleError::Connection(e.to_string()))?;
+    async fn request(&self, method: Method, path: &str) -> Result<Bytes, WhaleError> {
+        let uri = hyperlocal::Uri::new(&self.socket, path);
+        debug!("Making {} request to {}", method, path);
+        fetch(&self.client, method, uri.into(), None).await
+    }
 
-        trace!("Response status: {}", response.status());
-        
-        response
-            .collect()
-            .await
-            .map_err(|e| WhaleError::Connection(e.to_string()))
-            .map(|body| body.to_bytes())
+    async fn request_with_body(&self, method: Method, path: &str, body: Bytes) -> Result<Bytes, WhaleError> {
+        let uri = hyperlocal::Uri::new(&self.socket, path);
+        debug!("Making {} request to {}", method, path);
+        fetch(&self.client, method, uri.into(), Some(body)).await
+    }
+
+    async fn hijack(&self, path: &str, body: Bytes) -> Result<Hijacked, WhaleError> {
+        let uri = hyperlocal::Uri::new(&self.socket, path);
+        debug!("Upgrading connection for {}", path);
+        upgrade(&self.client, uri.into(), body).await
+    }
+
+    async fn request_stream(&self, method: Method, path: &str) -> Result<ResponseBody, WhaleError> {
+        let uri = hyperlocal::Uri::new(&self.socket, path);
+        debug!("Opening {} stream to {}", method, path);
+        open(&self.client, method, uri.into()).await
+    }
+}
+
+/// Plain HTTP or HTTPS connection to a daemon listening on `tcp://host:port`.
+pub struct HttpConnection {
+    client: HyperClient<HttpsConnector<HttpConnector>, Full<Bytes>>,
+    endpoint: DockerEndpoint,
+    version: VersionState,
+    base_url: String,
+}
+
+impl HttpConnection {
+    /// Connect to `host:port`, over TLS when `tls` is given.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the certificates or key in `tls` can't be loaded.
+    pub fn new(address: &str, tls: Option<&TlsOptions>) -> Result<Self, WhaleError> {
+        let (scheme, config) = match tls {
+            Some(options) => ("https", options.client_config()?),
+            None => ("http", tls::plain_client_config()),
+        };
+        debug!("Creating new HttpConnection to {scheme}://{address}");
+
+        let connector = HttpsConnectorBuilder::new()
+            .with_tls_config(config)
+            .https_or_http()
+            .enable_http1()
+            .build();
+
+        Ok(Self {
+            client: HyperClient::builder(TokioExecutor::new()).build(connector),
+            endpoint: DockerEndpoint::Tcp(address.to_string()),
+            version: VersionState::default(),
+            base_url: format!("{scheme}://{address}"),
+        })
+    }
+
+    fn uri(&self, path: &str) -> Result<Uri, WhaleError> {
+        format!("{}{}", self.base_url, path)
+            .parse()
+            .map_err(|e: InvalidUri| WhaleError::Connection(e.to_string()))
     }
 }
 
-#[cfg(target_os = "linux")]
 #[async_trait]
-impl Connection for UnixSocketConnection {
-    async fn list_containers(&self) -> Result<Vec<Container>, WhaleError> {
-        let body = self.make_request("/v1.43/containers/json?all=true&health=true").await?;
-        trace!("List containers response: {}", String::from_utf8_lossy(&body));
-        match serde_json::from_slice(&body) {
-            Ok(containers) => {
-                debug!("Successfully parsed container list");
-                Ok(containers)
-            }
-            Err(e) => {
-                error!("Failed to parse container list: {}", e);
-                error!("Error location: line {}, column {}", e.line(), e.column());
-                error!("Raw JSON response: {}", String::from_utf8_lossy(&body));
-                Err(WhaleError::Serialization(e.to_string()))
-            }
+impl Connection for HttpConnection {
+    fn endpoint(&self) -> &DockerEndpoint {
+        &self.endpoint
+    }
+
+    fn version(&self) -> &VersionState {
+        &self.version
+    }
+
+    async fn request(&self, method: Method, path: &str) -> Result<Bytes, WhaleError> {
+        let uri = self.uri(path)?;
+        debug!("Making {} request to {}", method, uri);
+        fetch(&self.client, method, uri, None).await
+    }
+
+    async fn request_with_body(&self, method: Method, path: &str, body: Bytes) -> Result<Bytes, WhaleError> {
+        let uri = self.uri(path)?;
+        debug!("Making {} request to {}", method, uri);
+        fetch(&self.client, method, uri, Some(body)).await
+    }
+
+    async fn hijack(&self, path: &str, body: Bytes) -> Result<Hijacked, WhaleError> {
+        let uri = self.uri(path)?;
+        debug!("Upgrading connection for {}", uri);
+        upgrade(&self.client, uri, body).await
+    }
+
+    async fn request_stream(&self, method: Method, path: &str) -> Result<ResponseBody, WhaleError> {
+        let uri = self.uri(path)?;
+        debug!("Opening {} stream to {}", method, uri);
+        open(&self.client, method, uri).await
+    }
+}
+
+/// Send a request, with a JSON `body` if given, and collect the response.
+pub(super) async fn fetch<C>(
+    client: &HyperClient<C, Full<Bytes>>,
+    method: Method,
+    uri: Uri,
+    body: Option<Bytes>,
+) -> Result<Bytes, WhaleError>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    let response = send(client, method, uri, body).await?;
+    response
+        .into_body()
+        .collect()
+        .await
+        .map(Collected::to_bytes)
+        .map_err(|e| WhaleError::Connection(e.to_string()))
+}
+
+/// Send a request and return its body without reading it.
+pub(super) async fn open<C>(
+    client: &HyperClient<C, Full<Bytes>>,
+    method: Method,
+    uri: Uri,
+) -> Result<ResponseBody, WhaleError>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    let response = send(client, method, uri, None).await?;
+    Ok(response
+        .into_body()
+        .map_err(|e| WhaleError::Connection(e.to_string()))
+        .boxed_unsync())
+}
+
+/// POST `body` asking for an upgrade to a raw stream, and take over the
+/// connection once the daemon switches protocols.
+pub(super) async fn upgrade<C>(client: &HyperClient<C, Full<Bytes>>, uri: Uri, body: Bytes) -> Result<Hijacked, WhaleError>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("Host", "")
+        .header("Content-Type", "application/json")
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "tcp")
+        .body(Full::new(body))
+        .map_err(|e| WhaleError::Connection(e.to_string()))?;
+    let response = client
+        .request(req)
+        .await
+        .map_err(|e| WhaleError::Connection(e.to_string()))?;
+
+    let status = response.status();
+    if status != StatusCode::SWITCHING_PROTOCOLS {
+        let body = response
+            .collect()
+            .await
+            .map_err(|e| WhaleError::Connection(e.to_string()))?
+            .to_bytes();
+        if status.is_success() {
+            return Err(WhaleError::Connection("the daemon did not upgrade the connection".to_string()));
         }
+        return Err(api_error(status, &body));
     }
+    let upgraded = hyper::upgrade::on(response)
+        .await
+        .map_err(|e| WhaleError::Connection(e.to_string()))?;
+    Ok(TokioIo::new(upgraded))
+}
 
-    async fn get_container_stats(&self, container_id: &str) -> Result<ContainerStats, WhaleError> {
-        let path = format!("/v1.43/containers/{container_id}/stats?stream=false");
-        debug!("Requesting stats for container: {}", container_id);
-        let body = self.make_request(&path).await?;
-        trace!("Stats response for {}: {}", container_id, String::from_utf8_lossy(&body));
-        match serde_json::from_slice::<ContainerStats>(&body) {
-            Ok(stats) => {
-                debug!("Successfully parsed stats for container {}", container_id);
-                Ok(stats)
-            }
-            Err(e) => {
-                error!("Failed to parse stats for container {}: {}", container_id, e);
-                error!("Error location: line {}, column {}", e.line(), e.column());
-                error!("Raw JSON response: {}", String::from_utf8_lossy(&body));
-                Err(WhaleError::Serialization(e.to_string()))
-            }
+/// Send a request, turning non-2xx responses into [`WhaleError::Api`].
+async fn send<C>(
+    client: &HyperClient<C, Full<Bytes>>,
+    method: Method,
+    uri: Uri,
+    body: Option<Bytes>,
+) -> Result<Response<Incoming>, WhaleError>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    let mut req = Request::builder().method(method).uri(uri).header("Host", "");
+    if body.is_some() {
+        req = req.header("Content-Type", "application/json");
+    }
+    let req = req
+        .body(Full::new(body.unwrap_or_default()))
+        .map_err(|e| WhaleError::Connection(e.to_string()))?;
+
+    let response = client
+        .request(req)
+        .await
+        .map_err(|e| WhaleError::Connection(e.to_string()))?;
+
+    let status = response.status();
+    trace!("Response status: {status}");
+
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response
+        .collect()
+        .await
+        .map_err(|e| WhaleError::Connection(e.to_string()))?
+        .to_bytes();
+    Err(api_error(status, &body))
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
+/// The daemon reports failures as `{"message": "..."}`; fall back to the raw
+/// body for proxies and other non-JSON responses.
+pub(super) fn api_error(status: StatusCode, body: &[u8]) -> WhaleError {
+    let message = serde_json::from_slice::<ErrorBody>(body).map_or_else(
+        |_| String::from_utf8_lossy(body).trim().to_string(),
+        |error| error.message,
+    );
+    // 304 is the daemon saying there was nothing to do, e.g. starting a
+    // running container
+    if status == StatusCode::NOT_MODIFIED {
+        debug!("Docker API returned {status}: {message}");
+    } else {
+        error!("Docker API returned {status}: {message}");
+    }
+    WhaleError::Api {
+        status: status.as_u16(),
+        message,
+    }
+}
+
+fn parse_containers(body: &[u8]) -> Result<Vec<Container>, WhaleError> {
+    trace!("List containers response: {}", String::from_utf8_lossy(body));
+    match serde_json::from_slice(body) {
+        Ok(containers) => {
+            debug!("Successfully parsed container list");
+            Ok(containers)
+        }
+        Err(e) => {
+            error!("Failed to parse container list: {e}");
+            error!("Error location: line {}, column {}", e.line(), e.column());
+            error!("Raw JSON response: {}", String::from_utf8_lossy(body));
+            Err(WhaleError::Serialization(e.to_string()))
         }
     }
 }
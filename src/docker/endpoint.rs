@@ -0,0 +1,297 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use log::{debug, warn};
+use serde::Deserialize;
+
+use crate::error::WhaleError;
+
+pub const DEFAULT_SOCKET: &str = "/var/run/docker.sock";
+pub const DEFAULT_TCP_PORT: u16 = 2375;
+/// Podman's Docker-compatible socket, relative to `/run` or `$XDG_RUNTIME_DIR`.
+pub const PODMAN_SOCKET: &str = "podman/podman.sock";
+
+/// Where the Docker Engine API can be reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DockerEndpoint {
+    Unix(PathBuf),
+    /// `host:port` of a daemon listening on TCP.
+    Tcp(String),
+    /// A remote host running `docker system dial-stdio` over SSH.
+    Ssh {
+        user: Option<String>,
+        host: String,
+        port: Option<u16>,
+    },
+}
+
+/// Which configuration source an endpoint was resolved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointSource {
+    Flag,
+    Environment,
+    Context,
+    Default,
+}
+
+impl Default for DockerEndpoint {
+    fn default() -> Self {
+        Self::Unix(PathBuf::from(DEFAULT_SOCKET))
+    }
+}
+
+impl fmt::Display for DockerEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+            Self::Tcp(address) => write!(f, "tcp://{address}"),
+            Self::Ssh { user, host, port } => {
+                f.write_str("ssh://")?;
+                if let Some(user) = user {
+                    write!(f, "{user}@")?;
+                }
+                f.write_str(host)?;
+                if let Some(port) = port {
+                    write!(f, ":{port}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for EndpointSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Flag => "--host",
+            Self::Environment => "DOCKER_HOST",
+            Self::Context => "docker context",
+            Self::Default => "default",
+        })
+    }
+}
+
+impl DockerEndpoint {
+    /// Parse a Docker host string such as `unix:///var/run/docker.sock`,
+    /// `tcp://build-host:2376` or `ssh://deploy@staging`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on an unknown scheme or a malformed host.
+    pub fn parse(host: &str) -> Result<Self, WhaleError> {
+        let host = host.trim();
+        if let Some(path) = host.strip_prefix("unix://") {
+            if path.is_empty() {
+                return Err(WhaleError::InvalidEndpoint(format!("{host}: missing socket path")));
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        if let Some(address) = host.strip_prefix("tcp://") {
+            let address = address.trim_end_matches('/');
+            if address.is_empty() {
+                return Err(WhaleError::InvalidEndpoint(format!("{host}: missing address")));
+            }
+            return Ok(Self::Tcp(with_default_port(address)?));
+        }
+        if let Some(target) = host.strip_prefix("ssh://") {
+            return parse_ssh(target.trim_end_matches('/'))
+                .ok_or_else(|| WhaleError::InvalidEndpoint(format!("{host}: expected ssh://[user@]host[:port]")));
+        }
+        if host.starts_with('/') {
+            return Ok(Self::Unix(PathBuf::from(host)));
+        }
+        match host.split_once("://") {
+            Some((scheme, _)) => Err(WhaleError::InvalidEndpoint(format!(
+                "{host}: unsupported scheme '{scheme}'"
+            ))),
+            None => Err(WhaleError::InvalidEndpoint(format!("{host}: missing scheme"))),
+        }
+    }
+
+    /// Resolve the endpoint from, in order: the `--host` flag, `DOCKER_HOST`,
+    /// the current Docker context, and finally the default socket.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the chosen host can't be parsed or the current context
+    /// doesn't exist.
+    pub fn resolve(flag: Option<&str>) -> Result<(Self, EndpointSource), WhaleError> {
+        let docker_host = env::var("DOCKER_HOST").ok();
+        let context = env::var("DOCKER_CONTEXT").ok();
+        resolve_with(flag, docker_host.as_deref(), context.as_deref(), &docker_config_dir())
+    }
+}
+
+/// Resolution with every input made explicit, so it can be exercised without
+/// touching the process environment.
+///
+/// # Errors
+///
+/// As for [`DockerEndpoint::resolve`].
+pub fn resolve_with(
+    flag: Option<&str>,
+    docker_host: Option<&str>,
+    context: Option<&str>,
+    config_dir: &Path,
+) -> Result<(DockerEndpoint, EndpointSource), WhaleError> {
+    if let Some(host) = flag.filter(|h| !h.is_empty()) {
+        return Ok((DockerEndpoint::parse(host)?, EndpointSource::Flag));
+    }
+    if let Some(host) = docker_host.filter(|h| !h.is_empty()) {
+        return Ok((DockerEndpoint::parse(host)?, EndpointSource::Environment));
+    }
+    if let Some(host) = context_host(context, config_dir)? {
+        return Ok((DockerEndpoint::parse(&host)?, EndpointSource::Context));
+    }
+    Ok((default_endpoint(), EndpointSource::Default))
+}
+
+/// The first socket that exists out of the system Docker socket, the
+/// rootless Docker socket and the Podman sockets, in that order. Falls back
+/// to the system Docker socket when none exist.
+fn default_endpoint() -> DockerEndpoint {
+    let runtime_dir = env::var("XDG_RUNTIME_DIR").ok().map(PathBuf::from).or_else(|| {
+        user_id().map(|uid| PathBuf::from(format!("/run/user/{uid}")))
+    });
+    match default_socket_candidates(runtime_dir.as_deref())
+        .into_iter()
+        .find(|candidate| candidate.exists())
+    {
+        Some(socket) => {
+            debug!("Using socket at {}", socket.display());
+            DockerEndpoint::Unix(socket)
+        }
+        None => DockerEndpoint::default(),
+    }
+}
+
+#[must_use]
+pub fn default_socket_candidates(runtime_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(DEFAULT_SOCKET)];
+    if let Some(runtime_dir) = runtime_dir {
+        candidates.push(runtime_dir.join("docker.sock"));
+        candidates.push(runtime_dir.join(PODMAN_SOCKET));
+    }
+    candidates.push(Path::new("/run").join(PODMAN_SOCKET));
+    candidates
+}
+
+#[cfg(unix)]
+fn user_id() -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata("/proc/self").ok().map(|m| m.uid())
+}
+
+#[cfg(not(unix))]
+fn user_id() -> Option<u32> {
+    None
+}
+
+/// Append the default port unless the address already has one. IPv6
+/// literals must be bracketed, as in `tcp://[::1]:2375`.
+fn with_default_port(address: &str) -> Result<String, WhaleError> {
+    let port = match address.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && !port.contains(']') => Some(port),
+        _ => None,
+    };
+    match port {
+        Some(port) => port
+            .parse::<u16>()
+            .map(|_| address.to_string())
+            .map_err(|_| WhaleError::InvalidEndpoint(format!("{address}: invalid port '{port}'"))),
+        None => Ok(format!("{address}:{DEFAULT_TCP_PORT}")),
+    }
+}
+
+fn parse_ssh(target: &str) -> Option<DockerEndpoint> {
+    let (user, rest) = match target.split_once('@') {
+        Some((user, rest)) if !user.is_empty() => (Some(user.to_string()), rest),
+        Some(_) => return None,
+        None => (None, target),
+    };
+    let (host, port) = match rest.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port.parse::<u16>().ok()?)),
+        None => (rest, None),
+    };
+    if host.is_empty() || host.contains('/') {
+        return None;
+    }
+    Some(DockerEndpoint::Ssh {
+        user,
+        host: host.to_string(),
+        port,
+    })
+}
+
+pub(crate) fn docker_config_dir() -> PathBuf {
+    if let Ok(dir) = env::var("DOCKER_CONFIG") {
+        return PathBuf::from(dir);
+    }
+    env::var("HOME").map_or_else(|_| PathBuf::from(".docker"), |home| Path::new(&home).join(".docker"))
+}
+
+#[derive(Deserialize)]
+struct DockerConfig {
+    #[serde(rename = "currentContext", default)]
+    current_context: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ContextMeta {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Endpoints", default)]
+    endpoints: HashMap<String, ContextEndpoint>,
+}
+
+#[derive(Deserialize)]
+struct ContextEndpoint {
+    #[serde(rename = "Host", default)]
+    host: Option<String>,
+}
+
+/// Look up the Docker host of the active context, if one other than
+/// `default` is selected.
+fn context_host(context: Option<&str>, config_dir: &Path) -> Result<Option<String>, WhaleError> {
+    let name = match context.filter(|c| !c.is_empty()) {
+        Some(name) => name.to_string(),
+        None => match fs::read(config_dir.join("config.json")) {
+            Ok(bytes) => match serde_json::from_slice::<DockerConfig>(&bytes) {
+                Ok(config) => match config.current_context {
+                    Some(name) => name,
+                    None => return Ok(None),
+                },
+                Err(e) => {
+                    warn!("Ignoring unreadable Docker config: {e}");
+                    return Ok(None);
+                }
+            },
+            Err(_) => return Ok(None),
+        },
+    };
+    if name == "default" {
+        return Ok(None);
+    }
+
+    let Ok(entries) = fs::read_dir(config_dir.join("contexts").join("meta")) else {
+        return Err(WhaleError::InvalidEndpoint(format!("context '{name}' not found")));
+    };
+    for entry in entries.flatten() {
+        let Ok(bytes) = fs::read(entry.path().join("meta.json")) else {
+            continue;
+        };
+        let Ok(meta) = serde_json::from_slice::<ContextMeta>(&bytes) else {
+            continue;
+        };
+        if meta.name == name {
+            debug!("Using Docker context '{name}'");
+            return Ok(meta.endpoints.get("docker").and_then(|e| e.host.clone()));
+        }
+    }
+    Err(WhaleError::InvalidEndpoint(format!("context '{name}' not found")))
+}
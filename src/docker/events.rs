@@ -0,0 +1,161 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{ops::ControlFlow, time::Duration};
+
+use log::{debug, warn};
+use tokio::time::Instant;
+
+use super::connection::Connection;
+use super::models::{Container, DockerEvent, Health};
+use crate::error::WhaleError;
+
+/// How long to wait before resubscribing after the event stream drops.
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// What an event means for a container list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventEffect {
+    /// The event doesn't change anything we display.
+    Ignored,
+    /// The list was updated in place from the event alone.
+    Applied,
+    /// The container's list entry has to be fetched again.
+    Refetch(String),
+}
+
+/// Update `containers` from a single event, without talking to the daemon.
+pub fn apply_event(containers: &mut Vec<Container>, event: &DockerEvent) -> EventEffect {
+    if event.event_type != "container" {
+        return EventEffect::Ignored;
+    }
+    let id = &event.actor.id;
+    let position = containers.iter().position(|c| &c.id == id);
+
+    if event.action == "destroy" {
+        return match position {
+            Some(index) => {
+                containers.remove(index);
+                EventEffect::Applied
+            }
+            None => EventEffect::Ignored,
+        };
+    }
+    if matches!(event.action.as_str(), "create" | "start" | "restart" | "rename" | "update") {
+        return EventEffect::Refetch(id.clone());
+    }
+
+    let Some(container) = position.map(|index| &mut containers[index]) else {
+        // Something we haven't listed yet changed; pick it up
+        return match event.action.as_str() {
+            "die" | "pause" | "unpause" => EventEffect::Refetch(id.clone()),
+            action if action.starts_with("health_status") => EventEffect::Refetch(id.clone()),
+            _ => EventEffect::Ignored,
+        };
+    };
+    match event.action.as_str() {
+        "die" => {
+            let code = event.actor.attributes.get("exitCode").map_or("0", String::as_str);
+            container.state = "exited".to_string();
+            container.status = format!("Exited ({code})");
+            container.health = None;
+        }
+        "pause" => {
+            container.state = "paused".to_string();
+            container.status = format!("{} (Paused)", container.status);
+        }
+        "unpause" => {
+            container.state = "running".to_string();
+            container.status = container.status.trim_end_matches(" (Paused)").to_string();
+        }
+        action => match action.strip_prefix("health_status:") {
+            Some(status) => {
+                container.health = Some(Health {
+                    status: status.trim().to_string(),
+                });
+            }
+            None => return EventEffect::Ignored,
+        },
+    }
+    EventEffect::Applied
+}
+
+/// Keep a container list in sync with the daemon: list once, then follow
+/// `/events`, re-listing after a reconnect and every `resync()` as a safety
+/// net. `publish` sees the list after every change and stops the watch by
+/// returning `Break`.
+pub async fn watch_containers<F, R>(client: &dyn Connection, resync: R, mut publish: F)
+where
+    F: FnMut(&[Container]) -> ControlFlow<()>,
+    R: Fn() -> Duration,
+{
+    loop {
+        match follow(client, &resync, &mut publish).await {
+            Ok(ControlFlow::Break(())) => return,
+            Ok(ControlFlow::Continue(())) => debug!("Event stream closed, reconnecting"),
+            Err(e) => warn!("Event stream failed, reconnecting: {e}"),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn follow<F, R>(client: &dyn Connection, resync: &R, publish: &mut F) -> Result<ControlFlow<()>, WhaleError>
+where
+    F: FnMut(&[Container]) -> ControlFlow<()>,
+    R: Fn() -> Duration,
+{
+    // Subscribe before listing so nothing slips through in between
+    let mut events = client.events().await?;
+    let mut containers = client.list_containers().await?;
+    let mut listed_at = Instant::now();
+    if publish(&containers).is_break() {
+        return Ok(ControlFlow::Break(()));
+    }
+
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else {
+                    return Ok(ControlFlow::Continue(()));
+                };
+                if !handle_event(client, &mut containers, &event?).await? {
+                    continue;
+                }
+            }
+            () = tokio::time::sleep_until(listed_at + resync()) => {
+                debug!("Re-listing containers");
+                containers = client.list_containers().await?;
+                listed_at = Instant::now();
+            }
+        }
+        if publish(&containers).is_break() {
+            return Ok(ControlFlow::Break(()));
+        }
+    }
+}
+
+/// Apply `event`, refetching the container when needed. Returns whether the
+/// list changed.
+async fn handle_event(
+    client: &dyn Connection,
+    containers: &mut Vec<Container>,
+    event: &DockerEvent,
+) -> Result<bool, WhaleError> {
+    match apply_event(containers, event) {
+        EventEffect::Ignored => return Ok(false),
+        EventEffect::Applied => {}
+        EventEffect::Refetch(id) => {
+            let fetched = client.get_container(&id).await?;
+            let position = containers.iter().position(|c| c.id == id);
+            match (fetched, position) {
+                (Some(container), Some(index)) => containers[index] = container,
+                (Some(container), None) => containers.push(container),
+                (None, Some(index)) => {
+                    containers.remove(index);
+                }
+                (None, None) => return Ok(false),
+            }
+        }
+    }
+    debug!("Container {} changed: {}", event.actor.id, event.action);
+    Ok(true)
+}
@@ -0,0 +1,381 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, VecDeque},
+    fmt::Write,
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::{Buf, BytesMut};
+use http_body_util::BodyExt;
+use tokio::{
+    sync::mpsc,
+    task::JoinSet,
+    time::{Instant, sleep_until},
+};
+
+use super::connection::{Connection, ResponseBody};
+use crate::error::WhaleError;
+
+/// How long lines from several containers are held back, so that a line
+/// from a slower stream can still be put before them.
+pub const MERGE_WINDOW: Duration = Duration::from_millis(200);
+
+/// Lines read ahead of the merge; a stream waits for room once it's full.
+const MERGE_QUEUE_LINES: usize = 1024;
+
+/// Docker puts `[stream, 0, 0, 0, size (u32, big endian)]` in front of each
+/// chunk of output from containers without a TTY.
+const FRAME_HEADER_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// One line of container output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub stream: LogStream,
+    /// RFC 3339 timestamp the daemon prefixes with `timestamps=1`.
+    pub timestamp: Option<String>,
+    /// The timestamp as seconds and nanoseconds since the Unix epoch.
+    pub time: Option<(i64, u32)>,
+    pub text: String,
+}
+
+/// Which part of the existing output to start from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogOptions {
+    /// Only output after this Unix timestamp.
+    pub since: Option<i64>,
+    /// Only the last `tail` lines of existing output; all of it when `None`.
+    pub tail: Option<usize>,
+}
+
+impl LogOptions {
+    /// Query string for a followed, timestamped request of both streams.
+    #[must_use]
+    pub fn query(&self) -> String {
+        let mut query = "follow=1&stdout=1&stderr=1&timestamps=1".to_string();
+        if let Some(since) = self.since {
+            let _ = write!(query, "&since={since}");
+        }
+        match self.tail {
+            Some(tail) => {
+                let _ = write!(query, "&tail={tail}");
+            }
+            None => query.push_str("&tail=all"),
+        }
+        query
+    }
+}
+
+/// Reads lines from a `/containers/{id}/logs` response, removing the stream
+/// multiplexing headers unless the container has a TTY.
+pub struct LogReader {
+    body: ResponseBody,
+    /// TTY output is sent raw, and all of it on stdout.
+    tty: bool,
+    buf: BytesMut,
+    /// Output of each stream not yet terminated by a newline.
+    stdout: BytesMut,
+    stderr: BytesMut,
+    lines: VecDeque<LogLine>,
+    done: bool,
+}
+
+impl LogReader {
+    #[must_use]
+    pub fn new(body: ResponseBody, tty: bool) -> Self {
+        Self {
+            body,
+            tty,
+            buf: BytesMut::new(),
+            stdout: BytesMut::new(),
+            stderr: BytesMut::new(),
+            lines: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// The next line, or `None` once the daemon closes the stream.
+    pub async fn next(&mut self) -> Option<Result<LogLine, WhaleError>> {
+        loop {
+            if let Some(line) = self.lines.pop_front() {
+                return Some(Ok(line));
+            }
+            if self.done {
+                return None;
+            }
+            match self.body.frame().await {
+                Some(Ok(frame)) => {
+                    if let Ok(data) = frame.into_data() {
+                        self.push(&data);
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.done = true;
+                    self.finish();
+                }
+            }
+        }
+    }
+
+    /// Queue every line completed by `data`. Frames and lines can both be
+    /// split across reads.
+    fn push(&mut self, data: &[u8]) {
+        if self.tty {
+            self.stdout.extend_from_slice(data);
+            split_lines(&mut self.stdout, LogStream::Stdout, &mut self.lines);
+            return;
+        }
+        self.buf.extend_from_slice(data);
+        while self.buf.len() >= FRAME_HEADER_LEN {
+            let size = u32::from_be_bytes([self.buf[4], self.buf[5], self.buf[6], self.buf[7]]);
+            let size = usize::try_from(size).unwrap_or(usize::MAX);
+            if self.buf.len() - FRAME_HEADER_LEN < size {
+                break;
+            }
+            let (partial, stream) = match self.buf[0] {
+                2 => (&mut self.stderr, LogStream::Stderr),
+                _ => (&mut self.stdout, LogStream::Stdout),
+            };
+            self.buf.advance(FRAME_HEADER_LEN);
+            partial.extend_from_slice(&self.buf.split_to(size));
+            split_lines(partial, stream, &mut self.lines);
+        }
+    }
+
+    /// Keep output the stream ended without a trailing newline.
+    fn finish(&mut self) {
+        for (partial, stream) in [(&mut self.stdout, LogStream::Stdout), (&mut self.stderr, LogStream::Stderr)] {
+            if !partial.is_empty() {
+                self.lines.push_back(parse_line(&partial.split(), stream));
+            }
+        }
+    }
+}
+
+fn split_lines(partial: &mut BytesMut, stream: LogStream, lines: &mut VecDeque<LogLine>) {
+    while let Some(end) = partial.iter().position(|b| *b == b'\n') {
+        let line = partial.split_to(end + 1);
+        lines.push_back(parse_line(&line, stream));
+    }
+}
+
+/// Split off the timestamp and the line ending, which is `\r\n` on a TTY.
+fn parse_line(raw: &[u8], stream: LogStream) -> LogLine {
+    let line = String::from_utf8_lossy(raw);
+    let line = line.trim_end_matches(['\n', '\r']);
+    let parsed = line
+        .split_once(' ')
+        .and_then(|(timestamp, text)| Some((timestamp, parse_timestamp(timestamp)?, text)));
+    let (timestamp, time, text) = match parsed {
+        Some((timestamp, time, text)) => (Some(timestamp.to_string()), Some(time), text),
+        None => (None, None, line),
+    };
+    LogLine {
+        stream,
+        timestamp,
+        time,
+        text: text.to_string(),
+    }
+}
+
+/// Seconds and nanoseconds since the Unix epoch of an RFC 3339 timestamp
+/// like `2024-01-01T00:00:00.5Z` or `2024-01-01T02:00:00+02:00`. The
+/// fraction can have any number of digits up to nine.
+pub fn parse_timestamp(s: &str) -> Option<(i64, u32)> {
+    let bytes = s.as_bytes();
+    let separators = [(4, b'-'), (7, b'-'), (10, b'T'), (13, b':'), (16, b':')];
+    if bytes.len() < 20 || separators.iter().any(|&(at, separator)| bytes[at] != separator) {
+        return None;
+    }
+    let (year, month, day) = (digits(s.get(0..4))?, digits(s.get(5..7))?, digits(s.get(8..10))?);
+    let (hour, minute, second) = (digits(s.get(11..13))?, digits(s.get(14..16))?, digits(s.get(17..19))?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut rest = s.get(19..)?;
+    let mut nanos = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 || len > 9 {
+            return None;
+        }
+        nanos = fraction[..len].parse::<u32>().ok()? * 10u32.pow(9 - u32::try_from(len).ok()?);
+        rest = &fraction[len..];
+    }
+    let offset = if rest.eq_ignore_ascii_case("z") {
+        0
+    } else {
+        let sign = match rest.as_bytes().first()? {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+        if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+            return None;
+        }
+        sign * (digits(rest.get(1..3))? * 3600 + digits(rest.get(4..6))? * 60)
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset;
+    Some((seconds, nanos))
+}
+
+fn digits(s: Option<&str>) -> Option<i64> {
+    let s = s?;
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Days between 1970-01-01 and a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Output of [`merge_logs`], tagged with the index of its container.
+#[derive(Debug)]
+pub enum MergedLog {
+    Line(usize, LogLine),
+    /// A container's stream is over, with the error that ended it if any.
+    Ended(usize, Option<WhaleError>),
+}
+
+/// A line waiting in the merge window, ordered by time and then by arrival.
+/// Lines without a timestamp take the latest time seen so far, so they stay
+/// after everything that arrived before them.
+struct Held {
+    time: (i64, u32),
+    arrival: u64,
+    arrived_at: Instant,
+    index: usize,
+    line: LogLine,
+}
+
+impl Held {
+    fn key(&self) -> ((i64, u32), u64) {
+        (self.time, self.arrival)
+    }
+}
+
+impl PartialEq for Held {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Held {}
+
+impl PartialOrd for Held {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Held {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Follow the logs of several containers at once, interleaved by timestamp
+/// like `docker compose logs -f`. Each line is held for [`MERGE_WINDOW`]
+/// first; a single container's lines are passed on as they arrive. Returns
+/// once every stream has ended.
+pub async fn merge_logs<F>(client: Arc<dyn Connection>, ids: Vec<String>, options: LogOptions, mut emit: F)
+where
+    F: FnMut(MergedLog),
+{
+    let window = if ids.len() > 1 { MERGE_WINDOW } else { Duration::ZERO };
+    let (tx, mut rx) = mpsc::channel(MERGE_QUEUE_LINES);
+    // Dropping the set aborts every stream, so cancelling this future is enough
+    let mut tasks = JoinSet::new();
+    for (index, id) in ids.into_iter().enumerate() {
+        let client = Arc::clone(&client);
+        let options = options.clone();
+        let tx = tx.clone();
+        tasks.spawn(async move {
+            let error = match client.logs(&id, &options).await {
+                Ok(mut reader) => loop {
+                    match reader.next().await {
+                        Some(Ok(line)) => {
+                            if tx.send(MergedLog::Line(index, line)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Some(Err(e)) => break Some(e),
+                        None => break None,
+                    }
+                },
+                Err(e) => Some(e),
+            };
+            let _ = tx.send(MergedLog::Ended(index, error)).await;
+        });
+    }
+    drop(tx);
+
+    let mut held: BinaryHeap<Reverse<Held>> = BinaryHeap::new();
+    let mut ended: VecDeque<(Instant, usize, Option<WhaleError>)> = VecDeque::new();
+    let mut arrivals = 0;
+    let mut latest = (i64::MIN, 0);
+    loop {
+        let next_due = match held.peek() {
+            Some(Reverse(line)) => Some(line.arrived_at + window),
+            None => ended.front().map(|(at, _, _)| *at + window),
+        };
+        tokio::select! {
+            message = rx.recv() => match message {
+                Some(MergedLog::Line(index, line)) => {
+                    arrivals += 1;
+                    latest = line.time.map_or(latest, |time| time.max(latest));
+                    held.push(Reverse(Held {
+                        time: line.time.unwrap_or(latest),
+                        arrival: arrivals,
+                        arrived_at: Instant::now(),
+                        index,
+                        line,
+                    }));
+                }
+                Some(MergedLog::Ended(index, error)) => ended.push_back((Instant::now(), index, error)),
+                None => break,
+            },
+            () = sleep_until(next_due.unwrap_or_else(Instant::now)), if next_due.is_some() => {}
+        }
+
+        let now = Instant::now();
+        while held.peek().is_some_and(|Reverse(line)| line.arrived_at + window <= now) {
+            if let Some(Reverse(line)) = held.pop() {
+                emit(MergedLog::Line(line.index, line.line));
+            }
+        }
+        // A stream's end goes after the last of its lines
+        while ended.front().is_some_and(|(at, index, _)| {
+            *at + window <= now && !held.iter().any(|Reverse(line)| line.index == *index)
+        }) {
+            if let Some((_, index, error)) = ended.pop_front() {
+                emit(MergedLog::Ended(index, error));
+            }
+        }
+    }
+    while let Some(Reverse(line)) = held.pop() {
+        emit(MergedLog::Line(line.index, line.line));
+    }
+    for (_, index, error) in ended {
+        emit(MergedLog::Ended(index, error));
+    }
+}
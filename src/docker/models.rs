@@ -1,27 +1,71 @@
 #![warn(clippy::all, clippy::pedantic)]
 
-use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Treat `null` like a missing field; daemons send either for empty
+/// collections.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Container {
     #[serde(alias = "Id", alias = "ID")]
     pub id: String,
-    #[serde(default)]
+    #[serde(alias = "Names", default)]
     pub names: Vec<String>,
     #[serde(alias = "Image")]
     pub image: String,
-    #[serde(alias = "Command")]
+    /// ID of the image the container was created from.
+    #[serde(alias = "ImageID", default)]
+    pub image_id: String,
+    #[serde(alias = "Command", default)]
     pub command: String,
     #[serde(alias = "Created")]
     pub created: i64,
-    #[serde(alias = "State")]
+    // Older API versions only report `Status`
+    #[serde(alias = "State", default)]
     pub state: String,
-    #[serde(alias = "Status")]
+    #[serde(alias = "Status", default)]
     pub status: String,
-    #[serde(default)]
+    #[serde(alias = "Ports", default)]
     pub ports: Vec<Port>,
     #[serde(alias = "Health")]
     pub health: Option<Health>,
+    /// Pod the container belongs to; only set when talking to Podman.
+    #[serde(alias = "PodName", default)]
+    pub pod_name: Option<String>,
+    #[serde(alias = "NetworkSettings", default, deserialize_with = "null_as_default")]
+    pub network_settings: NetworkSettings,
+    #[serde(alias = "Mounts", default, deserialize_with = "null_as_default")]
+    pub mounts: Vec<Mount>,
+}
+
+impl Container {
+    /// Derive `state` from the human-readable status on API versions that
+    /// predate the `State` field.
+    pub(crate) fn fill_missing_state(&mut self) {
+        if !self.state.is_empty() {
+            return;
+        }
+        let status = self.status.to_lowercase();
+        self.state = if status.starts_with("up") {
+            if status.contains("(paused)") { "paused" } else { "running" }
+        } else if status.starts_with("created") {
+            "created"
+        } else if status.starts_with("restarting") {
+            "restarting"
+        } else {
+            "exited"
+        }
+        .to_string();
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -68,7 +112,54 @@ pub struct ContainerStats {
     pub storage_stats: serde_json::Value,
 }
 
+impl ContainerStats {
+    /// CPU usage since the previous sample, as a percentage of one CPU
+    /// (so a container saturating two cores reports 200%).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn cpu_percent(&self) -> Option<f64> {
+        let system_cpu = self.cpu_stats.system_cpu_usage?;
+        // `online_cpus` appeared in API 1.27; older daemons only list per-CPU usage
+        let online_cpus = match self.cpu_stats.online_cpus {
+            Some(cpus) if cpus > 0 => cpus,
+            _ => u32::try_from(self.cpu_stats.cpu_usage.percpu_usage.as_ref()?.len()).ok()?,
+        };
+        if online_cpus == 0 {
+            return None;
+        }
+        let cpu_delta = self.cpu_stats.cpu_usage.total_usage as f64
+            - self.precpu_stats.cpu_usage.total_usage as f64;
+        let system_delta = system_cpu as f64 - self.precpu_stats.system_cpu_usage.unwrap_or(0) as f64;
+        if system_delta > 0.0 {
+            Some((cpu_delta / system_delta) * 100.0 * f64::from(online_cpus))
+        } else {
+            None
+        }
+    }
+
+    /// Podman's one-shot stats leave `precpu_stats` empty; fall back to the
+    /// previous sample we collected ourselves.
+    pub fn fill_missing_precpu(&mut self, previous: Option<&CpuStats>) {
+        if self.precpu_stats.system_cpu_usage.unwrap_or(0) == 0 {
+            if let Some(previous) = previous {
+                self.precpu_stats = previous.clone();
+            }
+        }
+    }
+
+    /// Memory usage as a percentage of the container's limit.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn memory_percent(&self) -> Option<f64> {
+        match (self.memory_stats.usage, self.memory_stats.limit) {
+            (Some(usage), Some(limit)) if limit > 0 => Some((usage as f64 / limit as f64) * 100.0),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
+#[allow(dead_code)]
 pub struct CpuStats {
     #[serde(default)]
     pub cpu_usage: CpuUsage,
@@ -81,6 +172,7 @@ pub struct CpuStats {
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
+#[allow(dead_code)]
 pub struct CpuUsage {
     #[serde(default)]
     pub total_usage: u64,
@@ -88,9 +180,12 @@ pub struct CpuUsage {
     pub usage_in_kernelmode: u64,
     #[serde(default)]
     pub usage_in_usermode: u64,
+    #[serde(default)]
+    pub percpu_usage: Option<Vec<u64>>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
+#[allow(dead_code)]
 pub struct ThrottlingData {
     #[serde(default)]
     pub periods: u64,
@@ -122,3 +217,491 @@ pub struct GpuDevice {
     #[serde(default)]
     pub utilization: u32,
 }
+
+/// The parts of `/containers/{id}/json` that aren't in the list entry.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ContainerDetails {
+    #[serde(rename = "Config", default)]
+    pub config: ContainerConfig,
+    #[serde(rename = "HostConfig", default)]
+    pub host_config: HostConfig,
+    #[serde(rename = "Mounts", default, deserialize_with = "null_as_default")]
+    pub mounts: Vec<Mount>,
+    #[serde(rename = "NetworkSettings", default)]
+    pub network_settings: NetworkSettings,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ContainerConfig {
+    /// Output of TTY containers isn't split into stdout and stderr.
+    #[serde(rename = "Tty", default)]
+    pub tty: bool,
+    #[serde(rename = "Image", default)]
+    pub image: String,
+    #[serde(rename = "Entrypoint", default, deserialize_with = "null_as_default")]
+    pub entrypoint: Vec<String>,
+    #[serde(rename = "Cmd", default, deserialize_with = "null_as_default")]
+    pub cmd: Vec<String>,
+    #[serde(rename = "Env", default, deserialize_with = "null_as_default")]
+    pub env: Vec<String>,
+    #[serde(rename = "Labels", default, deserialize_with = "null_as_default")]
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct HostConfig {
+    #[serde(rename = "RestartPolicy", default)]
+    pub restart_policy: RestartPolicy,
+    /// Memory limit in bytes; 0 is unlimited.
+    #[serde(rename = "Memory", default)]
+    pub memory: i64,
+    /// CPU limit in billionths of a CPU; 0 is unlimited.
+    #[serde(rename = "NanoCpus", default)]
+    pub nano_cpus: i64,
+    /// Relative CPU weight; 0 is the default of 1024.
+    #[serde(rename = "CpuShares", default)]
+    pub cpu_shares: i64,
+    /// Unlimited when unset, 0 or -1.
+    #[serde(rename = "PidsLimit", default)]
+    pub pids_limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RestartPolicy {
+    /// `no`, `always`, `unless-stopped` or `on-failure`; empty means `no`.
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    #[serde(rename = "MaximumRetryCount", default)]
+    pub maximum_retry_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct Mount {
+    /// `bind`, `volume`, `tmpfs`, ...
+    #[serde(rename = "Type", default)]
+    pub kind: String,
+    /// Volume name; unset for bind mounts.
+    #[serde(rename = "Name", default)]
+    pub name: Option<String>,
+    #[serde(rename = "Source", default)]
+    pub source: String,
+    #[serde(rename = "Destination", default)]
+    pub destination: String,
+    #[serde(rename = "RW", default)]
+    pub rw: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct NetworkSettings {
+    #[serde(rename = "Networks", default, deserialize_with = "null_as_default")]
+    pub networks: BTreeMap<String, EndpointSettings>,
+}
+
+/// A container's attachment to one network.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct EndpointSettings {
+    #[serde(rename = "IPAddress", default)]
+    pub ip_address: String,
+    #[serde(rename = "Gateway", default)]
+    pub gateway: String,
+    #[serde(rename = "MacAddress", default)]
+    pub mac_address: String,
+}
+
+/// Response of `/containers/{id}/top`. The columns depend on the `ps`
+/// arguments and the platform.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ContainerTop {
+    #[serde(rename = "Titles", default, deserialize_with = "null_as_default")]
+    pub titles: Vec<String>,
+    #[serde(rename = "Processes", default, deserialize_with = "null_as_default")]
+    pub processes: Vec<Vec<String>>,
+}
+
+/// A row of [`ContainerTop`], picked out by column title.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Process {
+    pub pid: u32,
+    pub user: String,
+    /// Not every `ps` output has the usage columns.
+    pub cpu_percent: Option<f64>,
+    pub memory_percent: Option<f64>,
+    pub command: String,
+}
+
+impl ContainerTop {
+    /// The processes, reading `ps aux` columns or the `-ef` ones Docker uses
+    /// without arguments.
+    #[must_use]
+    pub fn processes(&self) -> Vec<Process> {
+        let column = |names: &[&str]| self.titles.iter().position(|title| names.contains(&title.as_str()));
+        let pid = column(&["PID"]);
+        let user = column(&["USER", "UID"]);
+        let cpu = column(&["%CPU", "C"]);
+        let memory = column(&["%MEM"]);
+        let command = column(&["COMMAND", "CMD"]);
+        self.processes
+            .iter()
+            .map(|row| {
+                let get = |index: Option<usize>| index.and_then(|i| row.get(i)).map_or("", String::as_str);
+                Process {
+                    pid: get(pid).parse().unwrap_or_default(),
+                    user: get(user).to_string(),
+                    cpu_percent: get(cpu).parse().ok(),
+                    memory_percent: get(memory).parse().ok(),
+                    command: get(command).to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// An entry of `/containers/{id}/changes`: a path that differs between the
+/// container's writable layer and its image.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct FilesystemChange {
+    #[serde(rename = "Path")]
+    pub path: String,
+    #[serde(rename = "Kind")]
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8")]
+pub enum ChangeKind {
+    Modified,
+    Added,
+    Deleted,
+}
+
+impl From<u8> for ChangeKind {
+    fn from(kind: u8) -> Self {
+        match kind {
+            1 => Self::Added,
+            2 => Self::Deleted,
+            _ => Self::Modified,
+        }
+    }
+}
+
+impl ChangeKind {
+    /// The marker `docker diff` prints.
+    #[must_use]
+    pub fn marker(self) -> char {
+        match self {
+            Self::Modified => 'C',
+            Self::Added => 'A',
+            Self::Deleted => 'D',
+        }
+    }
+}
+
+/// Entry of `/images/json`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ImageSummary {
+    #[serde(rename = "Id", default)]
+    pub id: String,
+    /// `repo:tag` names; dangling images have none, or `<none>:<none>`.
+    #[serde(rename = "RepoTags", default, deserialize_with = "null_as_default")]
+    pub repo_tags: Vec<String>,
+    #[serde(rename = "Created", default)]
+    pub created: i64,
+    /// Size in bytes, including the layers shared with other images.
+    #[serde(rename = "Size", default)]
+    pub size: u64,
+    /// Bytes in layers shared with other images; only computed by
+    /// `/system/df`, -1 elsewhere.
+    #[serde(rename = "SharedSize", default)]
+    pub shared_size: i64,
+    /// Containers using the image; only counted by `/system/df`, -1 elsewhere.
+    #[serde(rename = "Containers", default)]
+    pub containers: i64,
+}
+
+impl ImageSummary {
+    /// Untagged images, usually left behind when a tag moves to a newer build.
+    #[must_use]
+    pub fn is_dangling(&self) -> bool {
+        self.repo_tags.iter().all(|tag| tag == "<none>:<none>")
+    }
+
+    /// The first 12 hex digits of the ID, as `docker images` shows it.
+    #[must_use]
+    pub fn short_id(&self) -> &str {
+        let id = self.id.strip_prefix("sha256:").unwrap_or(&self.id);
+        &id[..id.len().min(12)]
+    }
+}
+
+/// Entry of `/images/{id}/history`: one layer, newest first.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ImageHistory {
+    #[serde(rename = "Created", default)]
+    pub created: i64,
+    #[serde(rename = "CreatedBy", default)]
+    pub created_by: String,
+    #[serde(rename = "Size", default)]
+    pub size: u64,
+}
+
+/// Response of the prune endpoints.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PruneReport {
+    /// What was removed; the entries' shape depends on the kind of object.
+    #[serde(
+        rename = "ImagesDeleted",
+        alias = "ContainersDeleted",
+        alias = "VolumesDeleted",
+        alias = "CachesDeleted",
+        default,
+        deserialize_with = "null_as_default"
+    )]
+    pub deleted: Vec<serde_json::Value>,
+    #[serde(rename = "SpaceReclaimed", default)]
+    pub space_reclaimed: u64,
+}
+
+/// Response of `/volumes`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct VolumeList {
+    #[serde(rename = "Volumes", default, deserialize_with = "null_as_default")]
+    pub volumes: Vec<Volume>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Volume {
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    #[serde(rename = "Driver", default)]
+    pub driver: String,
+    #[serde(rename = "Mountpoint", default)]
+    pub mountpoint: String,
+    /// Only filled in by `/system/df`.
+    #[serde(rename = "UsageData", default)]
+    pub usage_data: Option<VolumeUsage>,
+}
+
+/// Disk usage of a volume; either field is -1 when the driver can't tell.
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
+pub struct VolumeUsage {
+    #[serde(rename = "Size", default)]
+    pub size: i64,
+    #[serde(rename = "RefCount", default)]
+    pub ref_count: i64,
+}
+
+impl VolumeUsage {
+    #[must_use]
+    pub fn size(self) -> Option<u64> {
+        u64::try_from(self.size).ok()
+    }
+
+    #[must_use]
+    pub fn ref_count(self) -> Option<u64> {
+        u64::try_from(self.ref_count).ok()
+    }
+}
+
+/// Response of `/system/df`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DiskUsage {
+    /// Bytes used by all image layers, counting shared layers once.
+    #[serde(rename = "LayersSize", default)]
+    pub layers_size: u64,
+    #[serde(rename = "Images", default, deserialize_with = "null_as_default")]
+    pub images: Vec<ImageSummary>,
+    #[serde(rename = "Containers", default, deserialize_with = "null_as_default")]
+    pub containers: Vec<ContainerUsage>,
+    #[serde(rename = "Volumes", default, deserialize_with = "null_as_default")]
+    pub volumes: Vec<Volume>,
+    #[serde(rename = "BuildCache", default, deserialize_with = "null_as_default")]
+    pub build_cache: Vec<BuildCache>,
+}
+
+/// Container entry of `/system/df`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ContainerUsage {
+    #[serde(rename = "Id", default)]
+    pub id: String,
+    #[serde(rename = "Names", default, deserialize_with = "null_as_default")]
+    pub names: Vec<String>,
+    #[serde(rename = "State", default)]
+    pub state: String,
+    /// Bytes written to the container's writable layer.
+    #[serde(rename = "SizeRw", default)]
+    pub size_rw: u64,
+}
+
+/// Build cache record of `/system/df`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct BuildCache {
+    #[serde(rename = "ID", default)]
+    pub id: String,
+    /// `regular`, `source.local`, `exec.cachemount`, ...
+    #[serde(rename = "Type", default)]
+    pub kind: String,
+    /// The step that produced it, e.g. `mount / from exec /bin/sh -c make`.
+    #[serde(rename = "Description", default)]
+    pub description: String,
+    #[serde(rename = "InUse", default)]
+    pub in_use: bool,
+    /// Also part of an image, so pruning it frees nothing.
+    #[serde(rename = "Shared", default)]
+    pub shared: bool,
+    #[serde(rename = "Size", default)]
+    pub size: u64,
+}
+
+/// Entry of `/networks`, or the response of `/networks/{id}`. Only the
+/// latter lists the attached containers.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Network {
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    #[serde(rename = "Id", default)]
+    pub id: String,
+    /// `bridge`, `host`, `overlay`, `macvlan`, `null`, ...
+    #[serde(rename = "Driver", default)]
+    pub driver: String,
+    #[serde(rename = "Scope", default)]
+    pub scope: String,
+    #[serde(rename = "Internal", default)]
+    pub internal: bool,
+    #[serde(rename = "IPAM", default)]
+    pub ipam: Ipam,
+    /// Attached containers, by ID.
+    #[serde(rename = "Containers", default, deserialize_with = "null_as_default")]
+    pub containers: BTreeMap<String, NetworkContainer>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Ipam {
+    #[serde(rename = "Config", default, deserialize_with = "null_as_default")]
+    pub config: Vec<IpamConfig>,
+}
+
+/// One address pool; IPv4 and IPv6 get one each.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct IpamConfig {
+    #[serde(rename = "Subnet", default)]
+    pub subnet: String,
+    #[serde(rename = "Gateway", default)]
+    pub gateway: String,
+}
+
+/// A container's endpoint as its network reports it.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct NetworkContainer {
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    /// With the prefix length, e.g. `172.17.0.2/16`.
+    #[serde(rename = "IPv4Address", default)]
+    pub ipv4_address: String,
+    #[serde(rename = "IPv6Address", default)]
+    pub ipv6_address: String,
+    #[serde(rename = "MacAddress", default)]
+    pub mac_address: String,
+}
+
+/// Response of `/containers/{id}/exec`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ExecCreated {
+    #[serde(rename = "Id", default)]
+    pub id: String,
+}
+
+/// Response of `/exec/{id}/json`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ExecDetails {
+    #[serde(rename = "ExitCode", default)]
+    pub exit_code: Option<i64>,
+}
+
+/// Response of `/version`, used for API version negotiation.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct VersionInfo {
+    #[serde(rename = "Version", default)]
+    pub version: String,
+    #[serde(rename = "ApiVersion", default)]
+    pub api_version: String,
+    #[serde(rename = "MinAPIVersion", default)]
+    pub min_api_version: Option<String>,
+    #[serde(rename = "Os", default)]
+    pub os: String,
+    #[serde(rename = "KernelVersion", default)]
+    pub kernel_version: String,
+    #[serde(rename = "Components", default)]
+    pub components: Vec<VersionComponent>,
+}
+
+impl VersionInfo {
+    /// Podman reports itself as a "Podman Engine" component.
+    #[must_use]
+    pub fn is_podman(&self) -> bool {
+        self.components
+            .iter()
+            .any(|c| c.name.to_lowercase().contains("podman"))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct VersionComponent {
+    #[serde(rename = "Name", default)]
+    pub name: String,
+}
+
+/// Response of `/info`: the daemon's host and configuration.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SystemInfo {
+    /// Hostname of the daemon's host.
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    /// Distribution name, e.g. `Ubuntu 22.04.3 LTS` or `Docker Desktop`.
+    #[serde(rename = "OperatingSystem", default)]
+    pub operating_system: String,
+    #[serde(rename = "KernelVersion", default)]
+    pub kernel_version: String,
+    #[serde(rename = "NCPU", default)]
+    pub ncpu: u32,
+    /// Bytes of memory on the host.
+    #[serde(rename = "MemTotal", default)]
+    pub mem_total: u64,
+    /// Storage driver, e.g. `overlay2`.
+    #[serde(rename = "Driver", default)]
+    pub driver: String,
+}
+
+/// Entry of Podman's `/libpod/pods/json`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Pod {
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    #[serde(rename = "Containers", default)]
+    pub containers: Vec<PodContainer>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PodContainer {
+    #[serde(rename = "Id", default)]
+    pub id: String,
+}
+
+/// A message from the `/events` stream.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DockerEvent {
+    #[serde(rename = "Type", default)]
+    pub event_type: String,
+    /// e.g. `start`, `die` or `health_status: healthy`.
+    #[serde(rename = "Action", default)]
+    pub action: String,
+    #[serde(rename = "Actor", default)]
+    pub actor: EventActor,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EventActor {
+    #[serde(rename = "ID", default)]
+    pub id: String,
+    #[serde(rename = "Attributes", default)]
+    pub attributes: HashMap<String, String>,
+}
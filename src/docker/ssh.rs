@@ -0,0 +1,191 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    ffi::OsString,
+    future::Future,
+    io,
+    pin::Pin,
+    process::Stdio,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{Method, Uri};
+use hyper_util::client::legacy::Client as HyperClient;
+use hyper_util::client::legacy::connect::{Connected, Connection as HyperConnection};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use log::debug;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tower_service::Service;
+
+use super::connection::{Connection, Hijacked, ResponseBody, fetch, open, upgrade};
+use super::endpoint::DockerEndpoint;
+use super::version::VersionState;
+use crate::error::WhaleError;
+
+/// Spawns `ssh ... docker system dial-stdio` for every new connection, the
+/// same way `docker -H ssh://` reaches a remote daemon.
+#[derive(Debug, Clone)]
+pub struct SshConnector {
+    program: OsString,
+    args: Vec<String>,
+}
+
+impl SshConnector {
+    pub fn new(program: impl Into<OsString>, user: Option<&str>, host: &str, port: Option<u16>) -> Self {
+        Self {
+            program: program.into(),
+            args: ssh_args(user, host, port),
+        }
+    }
+
+    fn spawn(&self) -> io::Result<SshStream> {
+        debug!("Spawning {} {}", self.program.to_string_lossy(), self.args.join(" "));
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| io::Error::other("ssh stdin is not piped"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::other("ssh stdout is not piped"))?;
+        Ok(SshStream {
+            _child: child,
+            stdin,
+            stdout,
+        })
+    }
+}
+
+/// Arguments passed to `ssh`. `BatchMode` keeps password prompts from
+/// drawing over the TUI; key-based auth or an agent is expected.
+pub fn ssh_args(user: Option<&str>, host: &str, port: Option<u16>) -> Vec<String> {
+    let mut args = vec!["-o".to_string(), "BatchMode=yes".to_string(), "-T".to_string()];
+    if let Some(user) = user {
+        args.extend(["-l".to_string(), user.to_string()]);
+    }
+    if let Some(port) = port {
+        args.extend(["-p".to_string(), port.to_string()]);
+    }
+    args.extend(
+        ["--", host, "docker", "system", "dial-stdio"]
+            .into_iter()
+            .map(str::to_string),
+    );
+    args
+}
+
+impl Service<Uri> for SshConnector {
+    type Response = TokioIo<SshStream>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let connector = self.clone();
+        Box::pin(async move { connector.spawn().map(TokioIo::new) })
+    }
+}
+
+/// The stdin/stdout pipes of an `ssh` child process as one byte stream. The
+/// process is killed when the stream is dropped.
+pub struct SshStream {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+}
+
+impl AsyncRead for SshStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stdout).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SshStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stdin).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stdin).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stdin).poll_shutdown(cx)
+    }
+}
+
+impl HyperConnection for SshStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+pub struct SshConnection {
+    client: HyperClient<SshConnector, Full<Bytes>>,
+    endpoint: DockerEndpoint,
+    version: VersionState,
+}
+
+impl SshConnection {
+    #[must_use]
+    pub fn new(endpoint: DockerEndpoint, connector: SshConnector) -> Self {
+        debug!("Creating new SshConnection to {endpoint}");
+        Self {
+            client: HyperClient::builder(TokioExecutor::new()).build(connector),
+            endpoint,
+            version: VersionState::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Connection for SshConnection {
+    fn endpoint(&self) -> &DockerEndpoint {
+        &self.endpoint
+    }
+
+    fn version(&self) -> &VersionState {
+        &self.version
+    }
+
+    async fn request(&self, method: Method, path: &str) -> Result<Bytes, WhaleError> {
+        debug!("Making {} request to {}", method, path);
+        fetch(&self.client, method, uri(path)?, None).await
+    }
+
+    async fn request_with_body(&self, method: Method, path: &str, body: Bytes) -> Result<Bytes, WhaleError> {
+        debug!("Making {} request to {}", method, path);
+        fetch(&self.client, method, uri(path)?, Some(body)).await
+    }
+
+    async fn hijack(&self, path: &str, body: Bytes) -> Result<Hijacked, WhaleError> {
+        debug!("Upgrading connection for {}", path);
+        upgrade(&self.client, uri(path)?, body).await
+    }
+
+    async fn request_stream(&self, method: Method, path: &str) -> Result<ResponseBody, WhaleError> {
+        debug!("Opening {} stream to {}", method, path);
+        open(&self.client, method, uri(path)?).await
+    }
+}
+
+/// The authority is never resolved; every connection is an ssh pipe.
+fn uri(path: &str) -> Result<Uri, WhaleError> {
+    format!("http://docker{path}")
+        .parse()
+        .map_err(|e: hyper::http::uri::InvalidUri| WhaleError::Connection(e.to_string()))
+}
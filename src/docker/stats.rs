@@ -0,0 +1,99 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use log::{debug, warn};
+use tokio::{
+    sync::{mpsc, watch},
+    task::{AbortHandle, JoinSet},
+};
+
+use super::connection::Connection;
+use super::models::ContainerStats;
+
+/// How long to wait before resubscribing after a stats stream ends.
+pub const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Samples buffered between the per-container streams and `record`.
+const SAMPLE_BUFFER: usize = 256;
+
+/// Follow `stats?stream=true` for every container id in `running`, starting
+/// and stopping subscriptions as the set changes. Samples from all streams
+/// are funnelled to `record`; returns once the sender of `running` is gone.
+pub async fn stream_stats<F>(client: Arc<dyn Connection>, mut running: watch::Receiver<Vec<String>>, mut record: F)
+where
+    F: FnMut(&str, ContainerStats),
+{
+    let (tx, mut rx) = mpsc::channel(SAMPLE_BUFFER);
+    // Dropping the set aborts every stream, so cancelling this future is enough
+    let mut tasks = JoinSet::new();
+    let mut streams = HashMap::new();
+    let ids = running.borrow_and_update().clone();
+    sync_streams(&client, &ids, &mut tasks, &mut streams, &tx);
+
+    loop {
+        tokio::select! {
+            changed = running.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let ids = running.borrow_and_update().clone();
+                sync_streams(&client, &ids, &mut tasks, &mut streams, &tx);
+            }
+            Some((id, stats)) = rx.recv() => record(&id, stats),
+        }
+    }
+}
+
+/// Start a stream for each new id and cancel the ones no longer running.
+fn sync_streams(
+    client: &Arc<dyn Connection>,
+    ids: &[String],
+    tasks: &mut JoinSet<()>,
+    streams: &mut HashMap<String, AbortHandle>,
+    tx: &mpsc::Sender<(String, ContainerStats)>,
+) {
+    streams.retain(|id, stream| {
+        let keep = ids.contains(id);
+        if !keep {
+            debug!("Stopping stats stream for {id}");
+            stream.abort();
+        }
+        keep
+    });
+    for id in ids {
+        if !streams.contains_key(id) {
+            debug!("Starting stats stream for {id}");
+            let stream = tasks.spawn(follow_stats(Arc::clone(client), id.clone(), tx.clone()));
+            streams.insert(id.clone(), stream);
+        }
+    }
+    // Reap streams that have finished aborting
+    while tasks.try_join_next().is_some() {}
+}
+
+async fn follow_stats(client: Arc<dyn Connection>, id: String, tx: mpsc::Sender<(String, ContainerStats)>) {
+    loop {
+        match client.stats_stream(&id).await {
+            Ok(mut samples) => {
+                while let Some(sample) = samples.next().await {
+                    match sample {
+                        Ok(stats) => {
+                            if tx.send((id.clone(), stats)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Stats stream for {id} failed: {e}");
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to subscribe to stats for {id}: {e}"),
+        }
+        // The stream ends when the container stops; we're aborted if it
+        // really is gone, so keep trying until then
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
@@ -0,0 +1,58 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::marker::PhantomData;
+
+use bytes::BytesMut;
+use http_body_util::BodyExt;
+use serde::de::DeserializeOwned;
+
+use super::connection::ResponseBody;
+use crate::error::WhaleError;
+
+/// Reads newline-delimited JSON values from a streaming response, as sent by
+/// `/events` and `/containers/{id}/stats?stream=true`.
+pub struct JsonLines<T> {
+    body: ResponseBody,
+    buf: BytesMut,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> JsonLines<T> {
+    #[must_use]
+    pub fn new(body: ResponseBody) -> Self {
+        Self {
+            body,
+            buf: BytesMut::new(),
+            _item: PhantomData,
+        }
+    }
+
+    /// The next value, or `None` once the daemon closes the stream.
+    pub async fn next(&mut self) -> Option<Result<T, WhaleError>> {
+        loop {
+            if let Some(end) = self.buf.iter().position(|b| *b == b'\n') {
+                let line = self.buf.split_to(end + 1);
+                if line.trim_ascii().is_empty() {
+                    continue;
+                }
+                return Some(serde_json::from_slice(&line).map_err(WhaleError::from));
+            }
+            match self.body.frame().await {
+                Some(Ok(frame)) => {
+                    if let Ok(data) = frame.into_data() {
+                        self.buf.extend_from_slice(&data);
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    // A final value without a trailing newline
+                    let rest = self.buf.split();
+                    if rest.trim_ascii().is_empty() {
+                        return None;
+                    }
+                    return Some(serde_json::from_slice(&rest).map_err(WhaleError::from));
+                }
+            }
+        }
+    }
+}
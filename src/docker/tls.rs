@@ -0,0 +1,157 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    env,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use log::{debug, warn};
+use rustls::{
+    ClientConfig, DigitallySignedStruct, Error as TlsError, RootCertStore, SignatureScheme,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{self, WebPkiSupportedAlgorithms},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+};
+
+use super::endpoint::docker_config_dir;
+use crate::error::WhaleError;
+
+/// TLS settings for a `tcp://` endpoint, following the Docker CLI's
+/// `DOCKER_CERT_PATH` / `DOCKER_TLS_VERIFY` conventions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// Directory holding `ca.pem`, `cert.pem` and `key.pem`.
+    pub cert_path: PathBuf,
+    /// Verify the daemon's certificate against `ca.pem`.
+    pub verify: bool,
+}
+
+impl TlsOptions {
+    /// TLS is enabled when `DOCKER_TLS_VERIFY` or `DOCKER_TLS` is set to a
+    /// non-empty value; certificates default to `~/.docker`.
+    pub fn from_env() -> Option<Self> {
+        let set = |name: &str| env::var(name).is_ok_and(|v| !v.is_empty());
+        let verify = set("DOCKER_TLS_VERIFY");
+        if !verify && !set("DOCKER_TLS") {
+            return None;
+        }
+        let cert_path = env::var("DOCKER_CERT_PATH")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map_or_else(docker_config_dir, PathBuf::from);
+        Some(Self { cert_path, verify })
+    }
+
+    /// Client config trusting `ca.pem` when verifying, and presenting
+    /// `cert.pem`/`key.pem` when both exist.
+    ///
+    /// # Errors
+    ///
+    /// Fails when any of the files can't be read or parsed.
+    pub fn client_config(&self) -> Result<ClientConfig, WhaleError> {
+        let builder = if self.verify {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(&self.cert_path.join("ca.pem"))? {
+                roots
+                    .add(cert)
+                    .map_err(|e| WhaleError::Tls(format!("invalid CA certificate: {e}")))?;
+            }
+            ClientConfig::builder().with_root_certificates(roots)
+        } else {
+            warn!("DOCKER_TLS_VERIFY is not set, the daemon's certificate will not be verified");
+            ClientConfig::builder()
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(NoVerification::new()))
+        };
+
+        let cert_file = self.cert_path.join("cert.pem");
+        let key_file = self.cert_path.join("key.pem");
+        if cert_file.exists() && key_file.exists() {
+            debug!("Using client certificate from {}", self.cert_path.display());
+            let certs = load_certs(&cert_file)?;
+            let key = load_key(&key_file)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| WhaleError::Tls(format!("invalid client certificate: {e}")))
+        } else {
+            Ok(builder.with_no_client_auth())
+        }
+    }
+}
+
+/// Config used for plain `http` connections, where no handshake ever happens.
+#[must_use]
+pub fn plain_client_config() -> ClientConfig {
+    ClientConfig::builder()
+        .with_root_certificates(RootCertStore::empty())
+        .with_no_client_auth()
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, WhaleError> {
+    let file = File::open(path)
+        .map_err(|e| WhaleError::Tls(format!("{}: {e}", path.display())))?;
+    rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| WhaleError::Tls(format!("{}: {e}", path.display())))
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, WhaleError> {
+    let file = File::open(path)
+        .map_err(|e| WhaleError::Tls(format!("{}: {e}", path.display())))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| WhaleError::Tls(format!("{}: {e}", path.display())))?
+        .ok_or_else(|| WhaleError::Tls(format!("{}: no private key found", path.display())))
+}
+
+/// Accepts any server certificate while still checking handshake signatures,
+/// matching `docker --tls` without `--tlsverify`.
+#[derive(Debug)]
+struct NoVerification {
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl NoVerification {
+    fn new() -> Self {
+        Self {
+            algorithms: crypto::ring::default_provider().signature_verification_algorithms,
+        }
+    }
+}
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
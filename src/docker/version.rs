@@ -0,0 +1,102 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{fmt, str::FromStr, sync::RwLock};
+
+use super::models::VersionInfo;
+use crate::error::WhaleError;
+
+/// Highest Engine API version this client speaks.
+pub const MAX_API_VERSION: ApiVersion = ApiVersion::new(1, 52);
+/// Oldest Engine API version with every endpoint the client relies on.
+pub const MIN_API_VERSION: ApiVersion = ApiVersion::new(1, 24);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl ApiVersion {
+    #[must_use]
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Default for ApiVersion {
+    fn default() -> Self {
+        MAX_API_VERSION
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = WhaleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || WhaleError::UnsupportedVersion(format!("invalid API version '{s}'"));
+        let (major, minor) = s.trim().split_once('.').ok_or_else(invalid)?;
+        Ok(Self::new(
+            major.parse().map_err(|_| invalid())?,
+            minor.parse().map_err(|_| invalid())?,
+        ))
+    }
+}
+
+/// Pick the highest API version both sides support, or fail when the daemon
+/// is too old or requires something newer than this client.
+///
+/// # Errors
+///
+/// [`WhaleError::UnsupportedVersion`] when no common version exists or the
+/// daemon reports a malformed one.
+pub fn negotiate(server: &VersionInfo) -> Result<ApiVersion, WhaleError> {
+    let server_max: ApiVersion = server.api_version.parse()?;
+    let version = server_max.min(MAX_API_VERSION);
+    if version < MIN_API_VERSION {
+        return Err(WhaleError::UnsupportedVersion(format!(
+            "daemon API {server_max} is older than the minimum supported {MIN_API_VERSION}"
+        )));
+    }
+    if let Some(server_min) = server.min_api_version.as_deref() {
+        let server_min: ApiVersion = server_min.parse()?;
+        if server_min > version {
+            return Err(WhaleError::UnsupportedVersion(format!(
+                "daemon requires API {server_min} or newer, client supports up to {MAX_API_VERSION}"
+            )));
+        }
+    }
+    Ok(version)
+}
+
+/// Negotiated API version and daemon details, shared by every request made
+/// through a connection.
+#[derive(Debug, Default)]
+pub struct VersionState {
+    api: RwLock<ApiVersion>,
+    daemon: RwLock<Option<VersionInfo>>,
+}
+
+impl VersionState {
+    pub fn api(&self) -> ApiVersion {
+        self.api.read().map(|v| *v).unwrap_or_default()
+    }
+
+    pub fn daemon(&self) -> Option<VersionInfo> {
+        self.daemon.read().ok().and_then(|d| d.clone())
+    }
+
+    pub fn set(&self, api: ApiVersion, daemon: VersionInfo) {
+        if let Ok(mut current) = self.api.write() {
+            *current = api;
+        }
+        if let Ok(mut current) = self.daemon.write() {
+            *current = Some(daemon);
+        }
+    }
+}
@@ -13,4 +13,12 @@ pub enum WhaleError {
     Json(#[from] serde_json::Error),
     #[error("Serialization error: {0}")]
     Serialization(String),
+    #[error("TLS error: {0}")]
+    Tls(String),
+    #[error("Invalid Docker endpoint: {0}")]
+    InvalidEndpoint(String),
+    #[error("Docker API error ({status}): {message}")]
+    Api { status: u16, message: String },
+    #[error("Unsupported Docker API version: {0}")]
+    UnsupportedVersion(String),
 }
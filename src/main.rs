@@ -1,6 +1,6 @@
 #![warn(clippy::all, clippy::pedantic)]
 
-use std::error::Error;
+use std::{error::Error, time::Duration};
 use clap::Parser;
 use log::{info, warn};
 
@@ -11,19 +11,47 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
-use crate::docker::DockerClient;
-use crate::tui::App;
+use crate::docker::{ConnectionFactory, DockerEndpoint, LogOptions};
+use crate::tui::{App, Intervals, sort_containers};
+use crate::utils::{parse_since, parse_tail};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Refresh rate in milliseconds
+    /// UI redraw interval in milliseconds
     #[arg(short, long, default_value_t = 250)]
     refresh_rate: u64,
 
+    /// Interval between full container re-lists in milliseconds; the list is
+    /// otherwise kept current from the Docker event stream
+    #[arg(long, default_value_t = 30_000)]
+    list_interval: u64,
+
+    /// Minimum interval between recorded stats samples in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    stats_interval: u64,
+
+    /// Show log output since a Unix timestamp or a time ago (e.g. 10m, 2h)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Number of existing log lines to show, or "all"
+    #[arg(long, default_value = "500")]
+    tail: String,
+
+    /// Start in a merged log pane for these containers (comma-separated
+    /// names or ID prefixes)
+    #[arg(long, value_delimiter = ',')]
+    logs: Vec<String>,
+
     /// Log level (error, warn, info, debug, trace)
     #[arg(short, long, default_value = "info")]
     log_level: String,
+
+    /// Docker daemon socket to connect to (e.g. <unix:///run/user/1000/docker.sock>).
+    /// Overrides `DOCKER_HOST` and the current Docker context.
+    #[arg(short = 'H', long)]
+    host: Option<String>,
 }
 
 #[tokio::main]
@@ -35,27 +63,45 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .filter_level(args.log_level.parse().unwrap_or(log::LevelFilter::Info))
         .init();
 
+    let log_options = LogOptions {
+        since: args.since.as_deref().map(parse_since).transpose()?,
+        tail: parse_tail(&args.tail)?,
+    };
+
     info!("Starting cetacea with refresh rate: {}ms", args.refresh_rate);
     
-    let client = DockerClient::new();
+    let (endpoint, source) = DockerEndpoint::resolve(args.host.as_deref())?;
+    info!("Using Docker endpoint {endpoint} (from {source})");
+
+    let client = ConnectionFactory::create(&endpoint)?;
+    let daemon = client.negotiate_version().await?;
+    info!("Connected to Docker {} using API {}", daemon.version, client.version().api());
+
     let mut containers = client.list_containers().await?;
-    
-    // Sort containers: running first, then by name
-    containers.sort_by(|a, b| {
-        let state_order = b.state.cmp(&a.state);
-        if state_order == std::cmp::Ordering::Equal {
-            let a_name = a.names.first().map_or("", |s| s.as_str());
-            let b_name = b.names.first().map_or("", |s| s.as_str());
-            a_name.cmp(b_name)
-        } else {
-            state_order
-        }
-    });
+    sort_containers(&mut containers);
 
     info!("Found {} containers", containers.len());
     
-    let app = App::new(containers, client);
-    app.run_with_options(std::time::Duration::from_millis(args.refresh_rate))?;
+    let intervals = Intervals {
+        list: Duration::from_millis(args.list_interval),
+        stats: Duration::from_millis(args.stats_interval),
+        render: Duration::from_millis(args.refresh_rate),
+    };
+    let clamped = intervals.clamped();
+    if clamped != intervals {
+        warn!(
+            "Intervals out of range, using list {}ms, stats {}ms, refresh {}ms",
+            clamped.list.as_millis(),
+            clamped.stats.as_millis(),
+            clamped.render.as_millis()
+        );
+    }
+    let intervals = clamped;
+    let mut app = App::with_intervals(containers, client, intervals).with_log_options(log_options);
+    if !args.logs.is_empty() {
+        app.open_logs_of(&args.logs)?;
+    }
+    app.run()?;
 
     Ok(())
 }
@@ -1,24 +1,86 @@
 #[cfg(test)]
 mod tests {
-    use crate::docker::{Container, DockerClient};
-    use crate::tui::App;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use hyper::Method;
+
+    use std::time::{Duration, Instant};
+
+    use ratui_lib::{
+        Event, KeyCode, TerminalApp,
+        ratatui::{
+            Terminal,
+            backend::TestBackend,
+            crossterm::event::{KeyEvent, KeyModifiers},
+        },
+    };
+
+    use crate::docker::models::NetworkSettings;
+    use crate::docker::version::VersionState;
+    use crate::docker::{Connection, Container, DockerEndpoint};
+    use crate::error::WhaleError;
+    use crate::tests::mock_daemon::{Fixtures, MockDaemon, TEST_IMAGE_ID, stats_sample};
+    use crate::tui::logs::LOG_BUFFER_LINES;
+    use crate::tui::{App, ContainerSummary, IntervalKind, Intervals, ResourceHistory};
+
+    const WEB_ID: &str = "0123456789abcdef0123456789abcdef";
+
+    /// Offline stand-in for the daemon; the background threads get an empty
+    /// container list and no stats.
+    struct MockConnection {
+        endpoint: DockerEndpoint,
+        version: VersionState,
+    }
+
+    #[async_trait]
+    impl Connection for MockConnection {
+        fn endpoint(&self) -> &DockerEndpoint {
+            &self.endpoint
+        }
+
+        fn version(&self) -> &VersionState {
+            &self.version
+        }
+
+        async fn request(&self, _method: Method, path: &str) -> Result<Bytes, WhaleError> {
+            if path.contains("/containers/json") {
+                Ok(Bytes::from_static(b"[]"))
+            } else {
+                Err(WhaleError::Connection("offline".to_string()))
+            }
+        }
+    }
+
+    fn mock_client() -> Arc<dyn Connection> {
+        Arc::new(MockConnection {
+            endpoint: DockerEndpoint::default(),
+            version: VersionState::default(),
+        })
+    }
 
     fn create_test_container(name: &str, state: &str) -> Container {
         Container {
             id: "test_id".to_string(),
             names: vec![name.to_string()],
             image: "test_image".to_string(),
+            image_id: "sha256:test_image".to_string(),
             command: "test_command".to_string(),
             created: 0,
             state: state.to_string(),
             status: "test_status".to_string(),
             ports: vec![],
             health: None,
+            pod_name: None,
+            network_settings: NetworkSettings::default(),
+            mounts: vec![],
         }
     }
 
-    #[test]
-    fn test_container_sorting() {
+    #[tokio::test]
+    async fn test_container_sorting() {
         let containers = vec![
             create_test_container("c", "stopped"),
             create_test_container("a", "running"),
@@ -26,7 +88,7 @@ mod tests {
             create_test_container("d", "stopped"),
         ];
 
-        let client = DockerClient::new();
+        let client = mock_client();
         let app = App::new(containers, client);
 
         // Verify containers are sorted: running first (alphabetically), then stopped (alphabetically)
@@ -36,8 +98,8 @@ mod tests {
         assert_eq!(app.containers[3].names[0], "d");
     }
 
-    #[test]
-    fn test_container_health_status() {
+    #[tokio::test]
+    async fn test_container_health_status() {
         use crate::docker::models::Health;
 
         let mut container = create_test_container("test", "running");
@@ -45,9 +107,810 @@ mod tests {
             status: "healthy".to_string(),
         });
 
-        let client = DockerClient::new();
+        let client = mock_client();
         let app = App::new(vec![container], client);
 
         assert_eq!(app.containers[0].health.as_ref().unwrap().status, "healthy");
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_stats_task_records_history() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .stats(WEB_ID, vec![stats_sample((3_000, 1_000), (20_000, 10_000), 4, (300, 1_200))]),
+        );
+        let client = daemon.client();
+        let containers = client.list_containers().await.unwrap();
+        let app = App::new(containers, client);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let history = loop {
+            let history = app.history(WEB_ID).unwrap();
+            if !history.cpu_usage.is_empty() || Instant::now() > deadline {
+                break history;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        };
+
+        assert_eq!(history.cpu_usage.last().map(|p| p.1), Some(80.0));
+        assert_eq!(history.mem_usage.last().map(|p| p.1), Some(25.0));
+    }
+
+    #[tokio::test]
+    async fn test_ui_renders_container_cards() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .container("fedcba9876543210fedcba9876543210", "worker", "exited"),
+        );
+        let client = daemon.client();
+        client.negotiate_version().await.unwrap();
+        let containers = client.list_containers().await.unwrap();
+        let app = App::new(containers, client);
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| app.ui(f)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let screen = (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        // The temporary socket path is too long to fit beside the tabs
+        assert!(screen.starts_with("cetacea · unix://"));
+        assert!(screen.contains("… · Docker 24.0.7 (API 1.43)"));
+        assert!(screen.contains("/web (0123456789ab)"));
+        assert!(screen.contains("/worker (fedcba987654)"));
+        assert!(screen.contains("list 30s"));
+        assert!(screen.contains("stats 1s"));
+        assert!(screen.contains("render 250ms"));
+    }
+
+    #[tokio::test]
+    async fn test_containers_grouped_by_pod() {
+        let mut db = create_test_container("db", "running");
+        db.pod_name = Some("shop".to_string());
+        let mut cart = create_test_container("cart", "exited");
+        cart.pod_name = Some("shop".to_string());
+        let containers = vec![cart, create_test_container("zeta", "exited"), db, create_test_container("solo", "running")];
+
+        let app = App::new(containers, mock_client());
+        let names: Vec<&str> = app.containers.iter().map(|c| c.names[0].as_str()).collect();
+        assert_eq!(names, vec!["solo", "zeta", "db", "cart"]);
+    }
+
+    #[tokio::test]
+    async fn test_dropping_app_cancels_tasks() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running"));
+        let client = daemon.client();
+        let app = App::new(client.list_containers().await.unwrap(), Arc::clone(&client));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(Arc::strong_count(&client) > 2);
+
+        drop(app);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Arc::strong_count(&client) > 1 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(Arc::strong_count(&client), 1);
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[tokio::test]
+    async fn test_interval_keys() {
+        let mut app = App::new(vec![], mock_client());
+
+        app.handle_event(key('+')).unwrap();
+        assert_eq!(app.intervals().render, Duration::from_millis(500));
+
+        app.handle_event(key('i')).unwrap();
+        app.handle_event(key('-')).unwrap();
+        assert_eq!(app.intervals().list, Duration::from_secs(15));
+        assert_eq!(app.intervals().render, Duration::from_millis(500));
+
+        app.handle_event(key('i')).unwrap();
+        app.handle_event(key('+')).unwrap();
+        assert_eq!(app.intervals().stats, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_interval_bounds() {
+        let mut intervals = Intervals::default();
+        for _ in 0..10 {
+            intervals.adjust(IntervalKind::Render, false);
+            intervals.adjust(IntervalKind::Stats, false);
+            intervals.adjust(IntervalKind::List, true);
+        }
+        assert_eq!(intervals.render, Duration::from_millis(50));
+        assert_eq!(intervals.stats, Duration::from_secs(1));
+        assert_eq!(intervals.list, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_intervals_clamped() {
+        let intervals = Intervals {
+            list: Duration::ZERO,
+            stats: Duration::from_secs(3600),
+            render: Duration::ZERO,
+        }
+        .clamped();
+        assert_eq!(intervals.list, Duration::from_secs(5));
+        assert_eq!(intervals.stats, Duration::from_secs(60));
+        assert_eq!(intervals.render, Duration::from_millis(50));
+        assert_eq!(Intervals::default().clamped(), Intervals::default());
+    }
+
+    #[test]
+    fn test_stats_due() {
+        // At the daemon's cadence, early samples are kept too
+        let intervals = Intervals::default();
+        assert!(intervals.stats_due(Duration::from_millis(990)));
+        assert!(intervals.stats_due(Duration::ZERO));
+
+        let intervals = Intervals {
+            stats: Duration::from_secs(5),
+            ..Intervals::default()
+        };
+        assert!(!intervals.stats_due(Duration::from_secs(1)));
+        assert!(intervals.stats_due(Duration::from_millis(4_980)));
+        assert!(intervals.stats_due(Duration::from_secs(6)));
+    }
+
+    fn numbered_containers(count: usize) -> Vec<Container> {
+        (0..count)
+            .map(|i| Container {
+                id: format!("{i:x}{}", "0".repeat(31)),
+                ..create_test_container(&format!("/c{i}"), "running")
+            })
+            .collect()
+    }
+
+    fn render(app: &App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| app.ui(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[tokio::test]
+    async fn test_short_container_id_is_shown_whole() {
+        let unnamed = Container {
+            id: "abc123".to_string(),
+            names: vec![],
+            ..create_test_container("", "exited")
+        };
+        let mut app = App::new(vec![create_test_container("web", "running"), unnamed], mock_client());
+        assert!(render(&app, 120, 40).contains("web (test_id)"));
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE))).unwrap();
+        app.handle_event(key('e')).unwrap();
+        assert!(render(&app, 120, 40).contains("Cannot open a shell: abc123 is not running"));
+    }
+
+    fn selected_name(app: &App) -> &str {
+        app.selected().map_or("", |c| c.names[0].as_str())
+    }
+
+    #[tokio::test]
+    async fn test_selection_moves_and_clamps() {
+        let mut app = App::new(numbered_containers(5), mock_client());
+        assert_eq!(selected_name(&app), "/c0");
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE))).unwrap();
+        assert_eq!(selected_name(&app), "/c0");
+        app.handle_event(key('l')).unwrap();
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE))).unwrap();
+        assert_eq!(selected_name(&app), "/c2");
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE))).unwrap();
+        assert_eq!(selected_name(&app), "/c4");
+        app.handle_event(key('l')).unwrap();
+        assert_eq!(selected_name(&app), "/c4");
+        app.handle_event(key('h')).unwrap();
+        assert_eq!(selected_name(&app), "/c3");
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE))).unwrap();
+        assert_eq!(selected_name(&app), "/c0");
+    }
+
+    #[tokio::test]
+    async fn test_grid_scrolls_to_selection() {
+        let mut app = App::new(numbered_containers(10), mock_client());
+
+        let screen = render(&app, 60, 30);
+        assert!(screen.contains("▶ /c0"));
+        assert!(!screen.contains("/c9"));
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE))).unwrap();
+        let screen = render(&app, 60, 30);
+        assert!(screen.contains("▶ /c9"));
+        assert!(!screen.contains("/c0"));
+
+        // Up moves a whole grid row, which is one card at this width
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE))).unwrap();
+        assert_eq!(selected_name(&app), "/c8");
+    }
+
+    #[tokio::test]
+    async fn test_selection_kept_across_refresh() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111aaa111aaa1", "a", "running")
+                .container("bbb222bbb222bbb2", "b", "running")
+                .container("ccc333ccc333ccc3", "c", "running"),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+        app.handle_event(key('l')).unwrap();
+        assert_eq!(selected_name(&app), "/b");
+
+        // The list is re-read after the mock's event stream closes
+        daemon.update(|f| {
+            f.containers.remove(0);
+        });
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.containers.len() == 3 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            app.handle_event(Event::FocusGained).unwrap();
+        }
+        assert_eq!(app.containers.len(), 2);
+        assert_eq!(selected_name(&app), "/b");
+
+        // Once it's gone, the card that took its place is focused
+        daemon.update(|f| {
+            f.containers.remove(0);
+        });
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.containers.len() == 2 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            app.handle_event(Event::FocusGained).unwrap();
+        }
+        assert_eq!(selected_name(&app), "/c");
+    }
+
+    /// Process background results until `screen` shows `text`.
+    async fn wait_for_screen(app: &mut App, text: &str) -> String {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            app.handle_event(Event::FocusGained).unwrap();
+            let screen = render(app, 120, 40);
+            if screen.contains(text) || Instant::now() > deadline {
+                return screen;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_destructive_action_needs_confirmation() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running"));
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('s')).unwrap();
+        assert!(render(&app, 120, 40).contains("Stop web?"));
+        app.handle_event(key('n')).unwrap();
+        assert!(!render(&app, 120, 40).contains("Stop web?"));
+
+        app.handle_event(key('s')).unwrap();
+        app.handle_event(key('y')).unwrap();
+        let screen = wait_for_screen(&mut app, "web stopped").await;
+        assert!(screen.contains("web stopped"));
+        let stops = daemon.requests().iter().filter(|r| r.ends_with("/stop")).count();
+        assert_eq!(stops, 1);
+    }
+
+    #[tokio::test]
+    async fn test_action_error_shows_in_toast() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running"));
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        // Removal is forced, so a running container goes, and removing it
+        // again fails
+        app.handle_event(key('r')).unwrap();
+        assert!(render(&app, 120, 40).contains("Restart web?"));
+        app.handle_event(key('y')).unwrap();
+        wait_for_screen(&mut app, "web restarted").await;
+        app.handle_event(key('D')).unwrap();
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        wait_for_screen(&mut app, "web removed").await;
+        app.handle_event(key('D')).unwrap();
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        let screen = wait_for_screen(&mut app, "Failed to remove web").await;
+        assert!(screen.contains("Failed to remove web: Docker API error (404)"));
+    }
+
+    #[tokio::test]
+    async fn test_log_pane_follows_output() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .logs(WEB_ID, &[(1, "2024-01-01T12:34:56.000000000Z listening on :80"), (2, "warning: no TLS")]),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        let screen = wait_for_screen(&mut app, "log stream ended").await;
+        assert!(screen.contains("Logs: web"));
+        assert!(screen.contains("12:34:56 listening on :80"));
+        assert!(screen.contains("warning: no TLS"));
+        assert!(screen.contains("following"));
+
+        app.handle_event(key('f')).unwrap();
+        assert!(render(&app, 120, 40).contains(" paused 1-2 of 2"));
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(!screen.contains("Logs: web"));
+        assert!(screen.contains("/web"));
+    }
+
+    #[tokio::test]
+    async fn test_log_search_and_filter() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running").logs(
+            WEB_ID,
+            &[
+                (1, "GET /health 200"),
+                (1, r#"{"level":"error","msg":"upstream timeout"}"#),
+                (1, "GET /index 200"),
+                (2, "ERROR upstream refused"),
+            ],
+        ));
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        let screen = wait_for_screen(&mut app, "log stream ended").await;
+        assert!(screen.contains("level=error msg=\"upstream timeout\""));
+
+        for c in "/upstream".chars() {
+            app.handle_event(key(c)).unwrap();
+        }
+        assert!(render(&app, 120, 40).contains("/upstream▏ 1/2"));
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        app.handle_event(key('n')).unwrap();
+        assert!(render(&app, 120, 40).contains("/upstream 2/2"));
+
+        app.handle_event(key('F')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("filtered"));
+        assert!(screen.contains("ERROR upstream refused"));
+        assert!(!screen.contains("GET /health"));
+
+        // Esc clears the search before it closes the pane
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("GET /health"));
+        assert!(screen.contains("Logs: web"));
+    }
+
+    #[tokio::test]
+    async fn test_merged_log_pane() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111aaa111aaa111", "web", "running")
+                .container("bbb222bbb222bbb222", "worker-1", "running")
+                .logs("aaa111aaa111aaa111", &[(1, "2024-01-01T00:00:01.000000000Z from web")])
+                .logs("bbb222bbb222bbb222", &[(1, "2024-01-01T00:00:02.000000000Z from worker")]),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        // Mark both cards, then open one pane for them
+        app.handle_event(key(' ')).unwrap();
+        app.handle_event(key('l')).unwrap();
+        app.handle_event(key(' ')).unwrap();
+        assert!(render(&app, 120, 40).contains("● /worker-1"));
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+
+        let screen = wait_for_screen(&mut app, "log streams ended").await;
+        assert!(screen.contains("Logs: web, worker-1"));
+        let web = screen.find("web      | 00:00:01 from web").unwrap();
+        let worker = screen.find("worker-1 | 00:00:02 from worker").unwrap();
+        assert!(web < worker);
+    }
+
+    #[tokio::test]
+    async fn test_merged_log_pane_bounds_each_container() {
+        let noise: Vec<String> = (0..=LOG_BUFFER_LINES).map(|i| format!("2024-01-01T00:00:02.000000000Z noise {i}")).collect();
+        let noise: Vec<(u8, &str)> = noise.iter().map(|line| (1, line.as_str())).collect();
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111aaa111aaa111", "web", "running")
+                .container("bbb222bbb222bbb222", "worker", "running")
+                .logs("aaa111aaa111aaa111", &[(1, "2024-01-01T00:00:01.000000000Z from web")])
+                .logs("bbb222bbb222bbb222", &noise),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+        app.open_logs_of(&["web".to_string(), "worker".to_string()]).unwrap();
+        wait_for_screen(&mut app, "log streams ended").await;
+
+        // The noisy container only drops its own oldest line
+        app.handle_event(key('g')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains(&format!(" paused 1-35 of {} ", LOG_BUFFER_LINES + 1)));
+        assert!(screen.contains("web    | 00:00:01 from web"));
+        assert!(screen.contains("worker | 00:00:02 noise 1"));
+        assert!(!screen.contains("noise 0"));
+    }
+
+    #[tokio::test]
+    async fn test_open_logs_by_name() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111aaa111aaa111", "web", "running")
+                .logs("aaa111aaa111aaa111", &[(1, "hello")]),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        assert_eq!(app.open_logs_of(&["db".to_string()]), Err("No such container: db".to_string()));
+        app.open_logs_of(&["aaa111".to_string()]).unwrap();
+        let screen = wait_for_screen(&mut app, "hello").await;
+        assert!(screen.contains("Logs: web"));
+    }
+
+    #[tokio::test]
+    async fn test_inspect_view() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running"));
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('d')).unwrap();
+        let screen = wait_for_screen(&mut app, "Environment (1)").await;
+        assert!(screen.contains("Inspect: web"));
+        assert!(screen.contains("test/image:latest"));
+        assert!(screen.contains("PATH=/usr/bin"));
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("▸ Config: {3}"));
+        assert!(!screen.contains("Image:"));
+
+        for c in "/image".chars() {
+            app.handle_event(key(c)).unwrap();
+        }
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("▾ Config: {3}"));
+        assert!(screen.contains("Image: \"test/image:latest\""));
+        assert!(screen.contains("1/1"));
+
+        // Esc clears the search first, then goes back to the grid
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+        assert!(render(&app, 120, 40).contains("Inspect: web"));
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+        assert!(!render(&app, 120, 40).contains("Inspect: web"));
+    }
+
+    #[tokio::test]
+    async fn test_process_table() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running").top(
+            WEB_ID,
+            &[
+                (1, "root", 0.1, 0.5, "nginx: master process"),
+                (29, "nginx", 12.5, 2.0, "nginx: worker process"),
+                (30, "www", 3.0, 4.5, "php-fpm: pool www"),
+            ],
+        ));
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('t')).unwrap();
+        let screen = wait_for_screen(&mut app, "nginx: master process").await;
+        assert!(screen.contains("Processes: web"));
+        assert!(screen.contains("3 processes"));
+        assert!(screen.contains("%CPU ▼"));
+        let position = |screen: &str, text: &str| screen.find(text).unwrap();
+        // Busiest first
+        assert!(position(&screen, "nginx: worker") < position(&screen, "php-fpm"));
+        assert!(position(&screen, "php-fpm") < position(&screen, "nginx: master"));
+
+        app.handle_event(key('m')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("%MEM ▼"));
+        assert!(position(&screen, "php-fpm") < position(&screen, "nginx: worker"));
+
+        app.handle_event(key('p')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(position(&screen, "nginx: master") < position(&screen, "nginx: worker"));
+        app.handle_event(key('p')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("PID ▼"));
+        assert!(position(&screen, "php-fpm") < position(&screen, "nginx: master"));
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+        assert!(!render(&app, 120, 40).contains("Processes: web"));
+    }
+
+    #[tokio::test]
+    async fn test_changes_view() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .changes(WEB_ID, &[("/var", 0), ("/var/log", 1), ("/var/log/app.log", 1), ("/etc/passwd", 2)]),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('c')).unwrap();
+        // The title shows while loading; the summary only once the changes arrive
+        let screen = wait_for_screen(&mut app, "2 A · 1 C · 1 D").await;
+        assert!(screen.contains("Changes: web"));
+        assert!(screen.contains("▸   etc/  1 D"));
+        assert!(screen.contains("▸ C var/  2 A"));
+        assert!(!screen.contains("app.log"));
+
+        app.handle_event(key('E')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("D passwd"));
+        assert!(screen.contains("▾ A log/  1 A"));
+        assert!(screen.contains("A app.log"));
+
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+        assert!(!render(&app, 120, 40).contains("Changes: web"));
+    }
+
+    #[tokio::test]
+    async fn test_images_screen() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .image(TEST_IMAGE_ID, &["test/image:latest"], 50 * 1024 * 1024, 1_700_000_000)
+                .image("sha256:0ld0ld0ld0ld0ld0ld", &["test/image:old"], 80 * 1024 * 1024, 1_600_000_000)
+                .image("sha256:d4ngl1ngd4ngl1ng", &[], 1024, 1_650_000_000)
+                .history(TEST_IMAGE_ID, &[("/bin/sh -c #(nop)  CMD [\"serve\"]", 0), ("/bin/sh -c make install", 4096)]),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('2')).unwrap();
+        let screen = wait_for_screen(&mut app, "test/image:old").await;
+        assert!(screen.contains("3 images"));
+        assert!(screen.contains("1 dangling"));
+        assert!(screen.contains("<none> (dangling)"));
+        assert!(screen.contains("7e5f0a3c9b1d"));
+        let position = |screen: &str, text: &str| screen.find(text).unwrap();
+        // Newest first
+        assert!(position(&screen, "test/image:latest") < position(&screen, "<none>"));
+        assert!(position(&screen, "<none>") < position(&screen, "test/image:old"));
+
+        app.handle_event(key('s')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("SIZE ▼"));
+        assert!(position(&screen, "test/image:old") < position(&screen, "test/image:latest"));
+
+        // The selection stays on the same image when the order changes
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))).unwrap();
+        let screen = wait_for_screen(&mut app, "RUN make install").await;
+        assert!(screen.contains("History: test/image:latest"));
+        assert!(screen.contains("CMD [\"serve\"]"));
+        app.handle_event(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))).unwrap();
+
+        // web uses the image, so removing it fails
+        app.handle_event(key('D')).unwrap();
+        assert!(render(&app, 120, 40).contains("1 containers use it"));
+        app.handle_event(key('y')).unwrap();
+        wait_for_screen(&mut app, "Failed to remove test/image:latest").await;
+
+        app.handle_event(key('P')).unwrap();
+        assert!(render(&app, 120, 40).contains("Prune 1 dangling images (1.0 KiB)?"));
+        app.handle_event(key('y')).unwrap();
+        wait_for_screen(&mut app, "Pruned 1 images, reclaimed 1.0 KiB").await;
+        let screen = wait_for_screen(&mut app, "2 images").await;
+        assert!(!screen.contains("dangling)"));
+
+        app.handle_event(key('1')).unwrap();
+        assert!(render(&app, 120, 40).contains("/web (0123456789ab)"));
+    }
+
+    #[tokio::test]
+    async fn test_volumes_screen() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .volume("pgdata", 3 * 1024 * 1024)
+                .volume("leftover", 2048)
+                .mount(WEB_ID, "pgdata", "/var/lib/postgresql/data"),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('3')).unwrap();
+        let screen = wait_for_screen(&mut app, "pgdata").await;
+        assert!(screen.contains("2 volumes"));
+        assert!(screen.contains("1 orphaned"));
+        assert!(screen.contains("3.0 MiB"));
+        assert!(screen.contains("web"));
+        let position = |screen: &str, text: &str| screen.find(text).unwrap();
+        assert!(position(&screen, "leftover") < position(&screen, "pgdata"));
+
+        app.handle_event(key('s')).unwrap();
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("SIZE ▼"));
+        assert!(position(&screen, "pgdata") < position(&screen, "leftover"));
+
+        // Mounted volumes aren't offered for removal
+        app.handle_event(key('k')).unwrap();
+        app.handle_event(key('D')).unwrap();
+        assert!(render(&app, 120, 40).contains("pgdata is mounted by web"));
+        // Mounts come from the container list, without inspecting each one
+        assert!(!daemon.requests().iter().any(|r| r.ends_with(&format!("/containers/{WEB_ID}/json"))));
+
+        app.handle_event(key('j')).unwrap();
+        app.handle_event(key('D')).unwrap();
+        assert!(render(&app, 120, 40).contains("Remove volume leftover?"));
+        app.handle_event(key('y')).unwrap();
+        wait_for_screen(&mut app, "leftover removed").await;
+        let screen = wait_for_screen(&mut app, "1 volumes").await;
+        assert!(screen.contains("0 orphaned"));
+        assert!(daemon.requests().contains(&"DELETE /v1.52/volumes/leftover".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_networks_screen() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .network("backend", "172.18.0.0/16", "172.18.0.1")
+                .network("frontend", "172.19.0.0/16", "172.19.0.1")
+                .attach(WEB_ID, "backend", "172.18.0.2"),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+        assert!(render(&app, 120, 40).contains("Networks: backend 172.18.0.2"));
+
+        app.handle_event(key('4')).unwrap();
+        let screen = wait_for_screen(&mut app, "172.19.0.0/16").await;
+        assert!(screen.contains("2 networks"));
+        assert!(screen.contains("Attached to backend"));
+        assert!(screen.contains("172.18.0.2/16"));
+
+        // Already attached to backend, so only disconnecting is offered
+        app.handle_event(key('c')).unwrap();
+        assert!(render(&app, 120, 40).contains("web is already connected to backend"));
+        app.handle_event(key('d')).unwrap();
+        assert!(render(&app, 120, 40).contains("Disconnect web from backend?"));
+        app.handle_event(key('y')).unwrap();
+        wait_for_screen(&mut app, "web disconnected from backend").await;
+        wait_for_screen(&mut app, "No containers").await;
+
+        app.handle_event(key('j')).unwrap();
+        app.handle_event(key('d')).unwrap();
+        assert!(render(&app, 120, 40).contains("web is not connected to frontend"));
+        app.handle_event(key('c')).unwrap();
+        wait_for_screen(&mut app, "web connected to frontend").await;
+        wait_for_screen(&mut app, "172.19.0.2/16").await;
+        assert!(daemon.requests().iter().any(|r| r.starts_with("POST") && r.ends_with("/connect")));
+    }
+
+    #[tokio::test]
+    async fn test_disk_screen() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .image(TEST_IMAGE_ID, &["test/image:latest"], 5 * 1024 * 1024, 1_700_000_000)
+                .build_cache("c1", "RUN cargo build", 2 * 1024 * 1024, false)
+                .build_cache("c2", "COPY . .", 1024 * 1024, true),
+        );
+        let client = daemon.client();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        app.handle_event(key('5')).unwrap();
+        let screen = wait_for_screen(&mut app, "Build cache").await;
+        assert!(screen.contains("8.0 MiB · 2.0 MiB reclaimable"));
+        assert!(screen.contains("test/image:latest"));
+
+        // The breakdown follows the selected category
+        for _ in 0..3 {
+            app.handle_event(key('j')).unwrap();
+        }
+        let screen = render(&app, 120, 40);
+        assert!(screen.contains("RUN cargo build"));
+        assert!(screen.contains("2.0 MiB (66%)"));
+
+        app.handle_event(key('P')).unwrap();
+        assert!(render(&app, 120, 40).contains("Remove all build cache not in use? Frees up to 2.0 MiB."));
+        app.handle_event(key('y')).unwrap();
+        wait_for_screen(&mut app, "Pruned 1 build cache records, reclaimed 2.0 MiB").await;
+        let screen = wait_for_screen(&mut app, "6.0 MiB · 0 B reclaimable").await;
+        assert!(!screen.contains("RUN cargo build"));
+        assert!(daemon.requests().iter().any(|r| r.starts_with("POST /v1.52/build/prune")));
+    }
+
+    #[test]
+    fn test_container_summary() {
+        use crate::docker::models::Health;
+
+        let mut web = create_test_container("/web", "running");
+        web.id = "web".to_string();
+        let mut api = create_test_container("/api", "running");
+        api.id = "api".to_string();
+        api.health = Some(Health {
+            status: "unhealthy".to_string(),
+        });
+        let containers = vec![
+            web,
+            api,
+            create_test_container("/worker", "paused"),
+            create_test_container("/job", "exited"),
+            create_test_container("/new", "created"),
+        ];
+        let history = |cpu: f64, memory: u64| ResourceHistory {
+            cpu_usage: vec![(0.0, 99.0), (1.0, cpu)],
+            memory: Some(memory),
+            ..ResourceHistory::default()
+        };
+        // Only the latest sample of each running container counts
+        let histories = HashMap::from([("web".to_string(), history(12.5, 1024)), ("api".to_string(), history(50.0, 2048))]);
+
+        let summary = ContainerSummary::new(&containers, &histories);
+        assert_eq!((summary.running, summary.paused, summary.exited, summary.unhealthy), (2, 1, 2, 1));
+        assert!((summary.cpu_percent - 62.5).abs() < f64::EPSILON);
+        assert_eq!(summary.memory, 3072);
+    }
+
+    #[tokio::test]
+    async fn test_header_shows_daemon_and_totals() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container(WEB_ID, "web", "running")
+                .container("bbb222bbb222bbb2", "worker", "paused")
+                .container("ccc333ccc333ccc3", "job", "exited")
+                .stats(
+                    WEB_ID,
+                    vec![stats_sample((2_000, 1_000), (20_000, 10_000), 2, (256 * 1024 * 1024, 1024 * 1024 * 1024))],
+                ),
+        );
+        let client = daemon.client();
+        client.negotiate_version().await.unwrap();
+        let mut app = App::new(client.list_containers().await.unwrap(), client);
+
+        wait_for_screen(&mut app, "mock-host").await;
+        // Wide enough for the host details and the totals side by side
+        let screen = render(&app, 200, 40);
+        assert!(screen.contains("Docker 24.0.7 (API 1.43)"));
+        assert!(screen.contains("mock-host · Ubuntu 22.04.3 LTS · 6.5.0-14-generic · 8 CPUs · 16.0 GiB · overlay2"));
+        assert!(screen.contains("1 running"));
+        assert!(screen.contains("1 paused"));
+        assert!(screen.contains("1 exited"));
+        assert!(screen.contains("0 unhealthy"));
+
+        let screen = wait_for_screen(&mut app, "CPU 20.0%").await;
+        assert!(screen.contains("Mem 256.0 MiB"));
+    }
+
+    #[tokio::test]
+    async fn test_header_truncates_endpoint_beside_tabs() {
+        let daemon = MockDaemon::start(Fixtures::default().container(WEB_ID, "web", "running"));
+        let client = daemon.client();
+        client.negotiate_version().await.unwrap();
+        let app = App::new(client.list_containers().await.unwrap(), client);
+
+        let endpoint = daemon.endpoint().to_string();
+        let screen = render(&app, 120, 40);
+        let header = screen.lines().next().unwrap();
+        assert!(header.starts_with("cetacea · unix://"));
+        assert!(!header.contains(&endpoint));
+        assert!(header.contains("… · Docker 24.0.7 (API 1.43)"));
+        assert!(header.contains(" 1 Containers   2 Images   3 Volumes   4 Networks   5 Disk usage "));
+
+        let screen = render(&app, 300, 40);
+        let header = screen.lines().next().unwrap();
+        assert!(header.contains(&format!("cetacea · {endpoint} · Docker 24.0.7 (API 1.43)")));
+
+        // Too narrow for the endpoint: the tabs still get their full width
+        let screen = render(&app, 80, 40);
+        let header = screen.lines().next().unwrap();
+        assert!(header.starts_with("cetacea · "));
+        assert!(!header.contains("unix://"));
+        assert!(header.ends_with(" 1 Containers   2 Images   3 Volumes   4 Networks   5 Disk usage  "));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use crate::docker::models::{ChangeKind, FilesystemChange};
+    use crate::tui::changes::{ChangeTree, Counts, export_text};
+
+    fn changes() -> Vec<FilesystemChange> {
+        [
+            ("/var", ChangeKind::Modified),
+            ("/var/log", ChangeKind::Modified),
+            ("/var/log/app.log", ChangeKind::Added),
+            ("/var/log/old.log", ChangeKind::Deleted),
+            ("/tmp/cache/x", ChangeKind::Added),
+            ("/etc/hosts", ChangeKind::Modified),
+        ]
+        .into_iter()
+        .map(|(path, kind)| FilesystemChange {
+            path: path.to_string(),
+            kind,
+        })
+        .collect()
+    }
+
+    fn names(tree: &ChangeTree) -> Vec<String> {
+        tree.rows()
+            .iter()
+            .map(|row| format!("{}{}", "  ".repeat(row.depth), row.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_change_tree() {
+        let mut tree = ChangeTree::from_changes(&changes());
+        assert_eq!(names(&tree), vec!["etc", "tmp", "var"]);
+        assert_eq!(
+            tree.counts(),
+            Counts {
+                added: 2,
+                changed: 3,
+                deleted: 1
+            }
+        );
+        // Directories only implied by a deeper path have no change of their own
+        assert_eq!(tree.rows()[1].kind, None);
+        assert_eq!(tree.rows()[2].kind, Some(ChangeKind::Modified));
+        assert_eq!(
+            tree.rows()[2].counts,
+            Counts {
+                added: 1,
+                changed: 1,
+                deleted: 1
+            }
+        );
+
+        tree.move_to(2);
+        tree.expand();
+        tree.move_by(1);
+        tree.expand();
+        assert_eq!(names(&tree), vec!["etc", "tmp", "var", "  log", "    app.log", "    old.log"]);
+
+        // Files can't be expanded; collapsing one goes to its directory
+        tree.move_by(2);
+        tree.expand();
+        assert_eq!(tree.rows().len(), 6);
+        tree.collapse();
+        assert_eq!(tree.rows()[tree.selected()].path, "/var/log");
+
+        tree.collapse_all();
+        assert_eq!(names(&tree), vec!["etc", "tmp", "var"]);
+        assert_eq!(tree.rows()[tree.selected()].path, "/var");
+
+        tree.expand_all();
+        assert_eq!(tree.rows().len(), 9);
+    }
+
+    #[test]
+    fn test_export_text() {
+        assert_eq!(
+            export_text(&changes()),
+            "C /etc/hosts\nA /tmp/cache/x\nC /var\nC /var/log\nA /var/log/app.log\nD /var/log/old.log\n"
+        );
+        assert_eq!(export_text(&[]), "");
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http_body_util::Full;
+    use hyper::{Request, Response, StatusCode, body::Incoming, server::conn::http1, service::service_fn};
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpListener;
+
+    use crate::docker::connection::{Connection, HttpConnection};
+    use crate::docker::ssh::{SshConnection, SshConnector, ssh_args};
+    use crate::docker::tls::TlsOptions;
+    use crate::docker::{ConnectionFactory, DockerEndpoint};
+    use crate::error::WhaleError;
+
+    const CONTAINERS_JSON: &str = r#"[{
+        "Id": "4f66ad9a0b2e1c3d5e7f9a1b3c5d7e9f0a2b4c6d8e0f1a3b5c7d9e1f3a5b7c9d",
+        "Names": ["/web"],
+        "Image": "nginx:latest",
+        "Command": "nginx -g 'daemon off;'",
+        "Created": 1700000000,
+        "State": "running",
+        "Status": "Up 2 hours",
+        "Ports": [{"IP": "0.0.0.0", "PrivatePort": 80, "PublicPort": 8080, "Type": "tcp"}]
+    }]"#;
+
+    // One sample per line, as the daemon streams them
+    const STATS_JSON: &str = concat!(
+        r#"{"name": "/web", "#,
+        r#""cpu_stats": {"cpu_usage": {"total_usage": 2000}, "system_cpu_usage": 20000, "online_cpus": 2}, "#,
+        r#""precpu_stats": {"cpu_usage": {"total_usage": 1000}, "system_cpu_usage": 10000}, "#,
+        r#""memory_stats": {"usage": 512, "limit": 1024}}"#,
+        "\n",
+    );
+
+    /// Serve canned JSON bodies keyed by path suffix on a local TCP port.
+    async fn serve_canned(routes: Vec<(&'static str, u16, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let routes = routes.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |req: Request<Incoming>| {
+                        let (status, body) = routes
+                            .iter()
+                            .find(|(suffix, _, _)| req.uri().path().ends_with(suffix))
+                            .map_or((404, r#"{"message":"page not found"}"#), |(_, status, body)| (*status, *body));
+                        async move {
+                            let mut response = Response::new(Full::new(Bytes::from(body)));
+                            *response.status_mut() = StatusCode::from_u16(status).unwrap();
+                            Ok::<_, Infallible>(response)
+                        }
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_http_list_containers() {
+        let address = serve_canned(vec![("/containers/json", 200, CONTAINERS_JSON)]).await;
+        let connection = HttpConnection::new(&address, None).unwrap();
+
+        let containers = connection.list_containers().await.unwrap();
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].names, vec!["/web".to_string()]);
+        assert_eq!(containers[0].ports[0].external, Some(8080));
+    }
+
+    #[tokio::test]
+    async fn test_http_container_stats() {
+        let address = serve_canned(vec![("/stats", 200, STATS_JSON)]).await;
+        let connection = HttpConnection::new(&address, None).unwrap();
+
+        let mut samples = connection.stats_stream("web").await.unwrap();
+        let stats = samples.next().await.unwrap().unwrap();
+        assert_eq!(stats.cpu_stats.cpu_usage.total_usage, 2000);
+        assert_eq!(stats.memory_stats.limit, Some(1024));
+    }
+
+    #[tokio::test]
+    async fn test_factory_creates_tcp_client() {
+        let address = serve_canned(vec![("/containers/json", 200, CONTAINERS_JSON)]).await;
+        let client = ConnectionFactory::create(&DockerEndpoint::parse(&format!("tcp://{address}")).unwrap()).unwrap();
+
+        assert_eq!(client.endpoint().to_string(), format!("tcp://{address}"));
+        assert_eq!(client.list_containers().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_api_error_carries_status_and_message() {
+        let address = serve_canned(vec![(
+            "/stats",
+            404,
+            r#"{"message":"No such container: missing"}"#,
+        )])
+        .await;
+        let connection = HttpConnection::new(&address, None).unwrap();
+
+        match connection.stats_stream("missing").await.map(|_| ()) {
+            Err(WhaleError::Api { status, message }) => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "No such container: missing");
+            }
+            other => panic!("expected an API error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_tcp_endpoint() {
+        assert_eq!(
+            DockerEndpoint::parse("tcp://build-host").unwrap(),
+            DockerEndpoint::Tcp("build-host:2375".to_string())
+        );
+        assert_eq!(
+            DockerEndpoint::parse("tcp://10.0.0.5:2376/").unwrap(),
+            DockerEndpoint::Tcp("10.0.0.5:2376".to_string())
+        );
+        assert_eq!(
+            DockerEndpoint::parse("tcp://[::1]").unwrap(),
+            DockerEndpoint::Tcp("[::1]:2375".to_string())
+        );
+        assert!(DockerEndpoint::parse("tcp://host:notaport").is_err());
+    }
+
+    #[test]
+    fn test_tls_missing_ca_is_an_error() {
+        let options = TlsOptions {
+            cert_path: std::env::temp_dir().join("cetacea-no-such-certs"),
+            verify: true,
+        };
+        assert!(HttpConnection::new("127.0.0.1:2376", Some(&options)).is_err());
+    }
+
+    /// Write a fake `ssh` that records its arguments, waits for the request
+    /// head on stdin and answers with a canned HTTP response.
+    #[cfg(unix)]
+    fn fake_ssh(name: &str, body: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("cetacea-ssh-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let response = dir.join("response");
+        std::fs::write(
+            &response,
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+        .unwrap();
+        let args = dir.join("args");
+        let script = dir.join("ssh");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho \"$@\" > '{}'\nwhile IFS= read -r line; do [ \"$line\" = \"$(printf '\\r')\" ] && break; done\ncat '{}'\n",
+                args.display(),
+                response.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        (script, args)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ssh_list_containers() {
+        let (script, args) = fake_ssh("list", CONTAINERS_JSON);
+        let endpoint = DockerEndpoint::parse("ssh://deploy@staging:2222").unwrap();
+        let connection = SshConnection::new(endpoint, SshConnector::new(&script, Some("deploy"), "staging", Some(2222)));
+
+        let containers = connection.list_containers().await.unwrap();
+        assert_eq!(containers.len(), 1);
+        assert_eq!(containers[0].image, "nginx:latest");
+        assert_eq!(
+            std::fs::read_to_string(args).unwrap().trim(),
+            "-o BatchMode=yes -T -l deploy -p 2222 -- staging docker system dial-stdio"
+        );
+    }
+
+    #[test]
+    fn test_parse_ssh_endpoint() {
+        assert_eq!(
+            DockerEndpoint::parse("ssh://deploy@staging:2222").unwrap(),
+            DockerEndpoint::Ssh {
+                user: Some("deploy".to_string()),
+                host: "staging".to_string(),
+                port: Some(2222),
+            }
+        );
+        let endpoint = DockerEndpoint::parse("ssh://staging").unwrap();
+        assert_eq!(endpoint.to_string(), "ssh://staging");
+        assert!(DockerEndpoint::parse("ssh://@staging").is_err());
+        assert!(DockerEndpoint::parse("ssh://staging:port").is_err());
+        assert_eq!(
+            ssh_args(None, "staging", None),
+            vec!["-o", "BatchMode=yes", "-T", "--", "staging", "docker", "system", "dial-stdio"]
+        );
+    }
+}
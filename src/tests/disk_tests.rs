@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::docker::models::{BuildCache, ContainerUsage, DiskUsage, ImageSummary, Volume, VolumeUsage};
+    use crate::tui::disk::{Category, format_reclaimable, summarize};
+
+    fn image(tag: &str, size: u64, shared_size: i64, containers: i64) -> ImageSummary {
+        ImageSummary {
+            id: format!("sha256:{tag}"),
+            repo_tags: vec![format!("{tag}:latest")],
+            size,
+            shared_size,
+            containers,
+            ..ImageSummary::default()
+        }
+    }
+
+    fn container(name: &str, state: &str, size_rw: u64) -> ContainerUsage {
+        ContainerUsage {
+            names: vec![format!("/{name}")],
+            state: state.to_string(),
+            size_rw,
+            ..ContainerUsage::default()
+        }
+    }
+
+    fn cache(description: &str, size: u64, in_use: bool, shared: bool) -> BuildCache {
+        BuildCache {
+            description: description.to_string(),
+            kind: "regular".to_string(),
+            size,
+            in_use,
+            shared,
+            ..BuildCache::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let usage = DiskUsage {
+            // `app` and `base` share 100 bytes of layers
+            layers_size: 600,
+            images: vec![image("app", 300, 100, 1), image("base", 200, 100, 0), image("old", 200, 0, 0)],
+            containers: vec![
+                container("web", "running", 10),
+                container("job", "exited", 30),
+                container("db", "paused", 5),
+            ],
+            volumes: vec![
+                Volume {
+                    name: "data".to_string(),
+                    usage_data: Some(VolumeUsage { size: 1000, ref_count: 1 }),
+                    ..Volume::default()
+                },
+                Volume {
+                    name: "scratch".to_string(),
+                    usage_data: Some(VolumeUsage { size: 50, ref_count: 0 }),
+                    ..Volume::default()
+                },
+                // The driver couldn't tell
+                Volume {
+                    name: "nfs".to_string(),
+                    usage_data: Some(VolumeUsage { size: -1, ref_count: -1 }),
+                    ..Volume::default()
+                },
+            ],
+            build_cache: vec![
+                cache("RUN make", 700, false, false),
+                cache("COPY . .", 80, true, false),
+                cache("FROM base", 100, false, true),
+            ],
+        };
+        let summaries = summarize(&usage);
+        let categories: Vec<Category> = summaries.iter().map(|s| s.category).collect();
+        assert_eq!(
+            categories,
+            [Category::Images, Category::Containers, Category::Volumes, Category::BuildCache]
+        );
+        let totals: Vec<(usize, usize, u64, u64)> = summaries
+            .iter()
+            .map(|s| (s.items.len(), s.active, s.size, s.reclaimable))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![(3, 1, 600, 400), (3, 1, 45, 30), (3, 1, 1050, 50), (2, 1, 780, 700)]
+        );
+
+        // Biggest first, reclaimable ones flagged
+        let images: Vec<(&str, bool)> = summaries[0].items.iter().map(|i| (i.name.as_str(), i.reclaimable)).collect();
+        assert_eq!(images, vec![("app:latest", false), ("base:latest", true), ("old:latest", true)]);
+        let containers: Vec<(&str, bool)> = summaries[1].items.iter().map(|i| (i.name.as_str(), i.reclaimable)).collect();
+        assert_eq!(containers, vec![("job", true), ("web", false), ("db", false)]);
+        assert_eq!(summaries[2].items.last().map(|i| (i.name.as_str(), i.reclaimable, i.detail.as_str())), Some(("nfs", false, "-")));
+        assert_eq!(summaries[3].items[0].name, "RUN make");
+    }
+
+    #[test]
+    fn test_format_reclaimable() {
+        assert_eq!(format_reclaimable(0, 0), "0 B");
+        assert_eq!(format_reclaimable(512, 2048), "512 B (25%)");
+        assert_eq!(format_reclaimable(3 * 1024 * 1024, 3 * 1024 * 1024), "3.0 MiB (100%)");
+    }
+}
@@ -1,22 +1,388 @@
 #[cfg(test)]
 mod tests {
-    use crate::docker::DockerClient;
+    use hyper::Method;
+
+    use crate::docker::ContainerAction;
+    use crate::docker::models::ChangeKind;
+    use crate::error::WhaleError;
+    use crate::tests::mock_daemon::{Fixtures, MockDaemon, TEST_IMAGE_ID, event_json, multiplexed, stats_sample};
+
+    fn fixtures() -> Fixtures {
+        Fixtures::default()
+            .container("aaa111", "web", "running")
+            .container("bbb222", "worker", "exited")
+            .stats(
+                "aaa111",
+                vec![
+                    stats_sample((2_000, 1_000), (20_000, 10_000), 2, (256, 1_024)),
+                    stats_sample((4_000, 2_000), (30_000, 20_000), 2, (512, 1_024)),
+                ],
+            )
+            .logs("aaa111", &[(1, "listening on :80"), (2, "warning: no TLS")])
+            .event(event_json("start", "aaa111", "web"))
+    }
 
     #[tokio::test]
     async fn test_list_containers() {
-        let client = DockerClient::new();
-        let result = client.list_containers().await;
-        
-        // We can't guarantee containers will be present, but we can verify the call works
-        assert!(result.is_ok());
-    }
-
-    #[test]
-    fn test_list_containers_blocking() {
-        let client = DockerClient::new();
-        let result = client.list_containers_blocking();
-        
-        // We can't guarantee containers will be present, but we can verify the call works
-        assert!(result.is_ok());
-    }
-} 
\ No newline at end of file
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+
+        let containers = client.list_containers().await.unwrap();
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].names, vec!["/web".to_string()]);
+        assert_eq!(containers[1].state, "exited");
+        assert!(daemon.requests()[0].starts_with("GET /v1.52/containers/json?all=true"));
+    }
+
+    #[tokio::test]
+    async fn test_stats_stream_yields_each_sample() {
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+
+        let mut samples = client.stats_stream("aaa111").await.unwrap();
+        let first = samples.next().await.unwrap().unwrap();
+        assert_eq!(first.cpu_percent(), Some(20.0));
+        assert_eq!(first.memory_percent(), Some(25.0));
+        let second = samples.next().await.unwrap().unwrap();
+        assert_eq!(second.cpu_percent(), Some(40.0));
+        assert_eq!(second.memory_percent(), Some(50.0));
+        assert!(samples.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_container_is_api_error() {
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+
+        match client.stats_stream("nope").await.map(|_| ()) {
+            Err(WhaleError::Api { status, message }) => {
+                assert_eq!(status, 404);
+                assert_eq!(message, "No such container: nope");
+            }
+            other => panic!("expected an API error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inspect_logs_and_events_fixtures() {
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+
+        let inspect = client.request(Method::GET, "/v1.43/containers/aaa111/json").await.unwrap();
+        let inspect: serde_json::Value = serde_json::from_slice(&inspect).unwrap();
+        assert_eq!(inspect["Name"], "/web");
+
+        let logs = client
+            .request(Method::GET, "/v1.43/containers/aaa111/logs?stdout=1&stderr=1")
+            .await
+            .unwrap();
+        let expected = vec![(1, "listening on :80".to_string()), (2, "warning: no TLS".to_string())];
+        assert_eq!(logs, multiplexed(&expected));
+
+        let events = client.request(Method::GET, "/v1.43/events").await.unwrap();
+        let event: serde_json::Value = serde_json::from_slice(events.split(|b| *b == b'\n').next().unwrap()).unwrap();
+        assert_eq!(event["Action"], "start");
+    }
+
+    #[tokio::test]
+    async fn test_fixtures_can_change_while_running() {
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+
+        daemon.update(|f| f.containers.truncate(1));
+        assert_eq!(client.list_containers().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_negotiates_down_to_older_daemon() {
+        let daemon = MockDaemon::start(fixtures().version("20.10.2", "1.41", "1.12"));
+        let client = daemon.client();
+
+        let info = client.negotiate_version().await.unwrap();
+        assert_eq!(info.version, "20.10.2");
+        assert_eq!(client.version().api().to_string(), "1.41");
+        assert_eq!(client.version().daemon().unwrap().kernel_version, "6.1.0");
+
+        client.list_containers().await.unwrap();
+        let requests = daemon.requests();
+        assert_eq!(requests[0], "GET /_ping");
+        assert_eq!(requests[1], "GET /version");
+        assert!(requests[2].starts_with("GET /v1.41/containers/json"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_daemon_requiring_newer_api() {
+        let daemon = MockDaemon::start(fixtures().version("99.0.0", "1.60", "1.55"));
+        let client = daemon.client();
+
+        assert!(matches!(
+            client.negotiate_version().await,
+            Err(WhaleError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_daemon_too_old() {
+        let daemon = MockDaemon::start(fixtures().version("1.11.0", "1.23", "1.12"));
+        let client = daemon.client();
+
+        assert!(matches!(
+            client.negotiate_version().await,
+            Err(WhaleError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_old_api_payloads_degrade_gracefully() {
+        let daemon = MockDaemon::start(fixtures().version("1.13.1", "1.26", "1.12"));
+        daemon.update(|f| {
+            f.containers = vec![serde_json::json!({
+                "Id": "ccc333",
+                "Names": ["/legacy"],
+                "Image": "busybox",
+                "Created": 1_500_000_000,
+                "Status": "Up 3 days",
+            })];
+            f.stats.insert(
+                "ccc333".to_string(),
+                vec![serde_json::json!({
+                    "cpu_stats": {
+                        "cpu_usage": { "total_usage": 3_000, "percpu_usage": [1_500, 1_500] },
+                        "system_cpu_usage": 20_000,
+                    },
+                    "precpu_stats": { "cpu_usage": { "total_usage": 1_000 }, "system_cpu_usage": 10_000 },
+                    "memory_stats": {},
+                })],
+            );
+        });
+        let client = daemon.client();
+        client.negotiate_version().await.unwrap();
+
+        let containers = client.list_containers().await.unwrap();
+        assert_eq!(containers[0].state, "running");
+        assert_eq!(containers[0].command, "");
+
+        let stats = client.stats_stream("ccc333").await.unwrap().next().await.unwrap().unwrap();
+        assert_eq!(stats.cpu_percent(), Some(40.0));
+        assert_eq!(stats.memory_percent(), None);
+    }
+
+    #[tokio::test]
+    async fn test_podman_pods_are_joined() {
+        let daemon = MockDaemon::start(fixtures().podman(&[("shop", &["bbb222"])]));
+        let client = daemon.client();
+        let info = client.negotiate_version().await.unwrap();
+        assert!(info.is_podman());
+
+        let containers = client.list_containers().await.unwrap();
+        assert_eq!(containers[0].pod_name, None);
+        assert_eq!(containers[1].pod_name.as_deref(), Some("shop"));
+        assert!(daemon.requests().contains(&"GET /libpod/pods/json".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_docker_skips_pod_lookup() {
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+        assert!(!client.negotiate_version().await.unwrap().is_podman());
+
+        client.list_containers().await.unwrap();
+        assert!(!daemon.requests().iter().any(|r| r.contains("libpod")));
+    }
+
+    #[tokio::test]
+    async fn test_container_actions() {
+        let daemon = MockDaemon::start(fixtures());
+        let client = daemon.client();
+
+        client.container_action("aaa111", ContainerAction::Stop).await.unwrap();
+        // Already stopped: the daemon answers 304, which isn't a failure
+        client.container_action("aaa111", ContainerAction::Stop).await.unwrap();
+        client.container_action("bbb222", ContainerAction::Start).await.unwrap();
+        let containers = client.list_containers().await.unwrap();
+        assert_eq!(containers[0].state, "exited");
+        assert_eq!(containers[1].state, "running");
+
+        // Removal is forced, so a running container goes too
+        client.container_action("bbb222", ContainerAction::Remove).await.unwrap();
+        client.container_action("aaa111", ContainerAction::Remove).await.unwrap();
+        assert!(client.list_containers().await.unwrap().is_empty());
+        assert!(matches!(
+            client.container_action("aaa111", ContainerAction::Remove).await,
+            Err(WhaleError::Api { status: 404, .. })
+        ));
+
+        let requests = daemon.requests();
+        assert!(requests.contains(&"POST /v1.52/containers/aaa111/stop".to_string()));
+        assert!(requests.contains(&"DELETE /v1.52/containers/bbb222?force=1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_container_changes() {
+        let daemon = MockDaemon::start(fixtures().changes("aaa111", &[("/tmp", 0), ("/tmp/x", 1)]));
+        let client = daemon.client();
+
+        let changes = client.container_changes("aaa111").await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].path, "/tmp/x");
+        assert_eq!(changes[1].kind, ChangeKind::Added);
+        // An unchanged container answers `null`
+        assert!(client.container_changes("bbb222").await.unwrap().is_empty());
+        assert!(client.container_changes("ccc333").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_top_encodes_ps_args() {
+        let daemon = MockDaemon::start(fixtures().top("aaa111", &[(1, "root", 0.5, 1.0, "nginx")]));
+        let client = daemon.client();
+
+        let top = client.top("aaa111", "-eo pid,user&x=1").await.unwrap();
+        assert_eq!(top.processes().len(), 1);
+        assert!(daemon.requests().contains(&"GET /v1.52/containers/aaa111/top?ps_args=-eo%20pid%2Cuser%26x%3D1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_images() {
+        let daemon = MockDaemon::start(
+            fixtures()
+                .image(TEST_IMAGE_ID, &["test/image:latest"], 50_000_000, 1_700_000_000)
+                .image("sha256:dead", &[], 2_000, 1_600_000_000)
+                .history(TEST_IMAGE_ID, &[("/bin/sh -c #(nop)  CMD [\"run\"]", 0), ("/bin/sh -c #(nop) ADD file:abc in / ", 49_000_000)]),
+        );
+        let client = daemon.client();
+
+        let images = client.list_images().await.unwrap();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].repo_tags, vec!["test/image:latest".to_string()]);
+        assert!(!images[0].is_dangling());
+        assert!(images[1].is_dangling());
+
+        let history = client.image_history(TEST_IMAGE_ID).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].size, 49_000_000);
+
+        // The containers still use it
+        assert!(matches!(
+            client.remove_image(TEST_IMAGE_ID).await,
+            Err(WhaleError::Api { status: 409, .. })
+        ));
+        let report = client.prune_images(false).await.unwrap();
+        assert_eq!(report.deleted.len(), 1);
+        assert_eq!(report.space_reclaimed, 2_000);
+        assert_eq!(client.list_images().await.unwrap().len(), 1);
+
+        daemon.update(|f| f.containers.clear());
+        client.remove_image(TEST_IMAGE_ID).await.unwrap();
+        assert!(client.list_images().await.unwrap().is_empty());
+        assert!(daemon.requests().iter().any(|r| r.starts_with("POST /v1.52/images/prune?filters=")));
+    }
+
+    #[tokio::test]
+    async fn test_volumes() {
+        let daemon = MockDaemon::start(
+            fixtures()
+                .volume("data", 4096)
+                .volume("scratch", -1)
+                .mount("aaa111", "data", "/var/lib/data"),
+        );
+        let client = daemon.client();
+
+        let volumes = client.list_volumes().await.unwrap();
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes[0].driver, "local");
+        assert!(volumes[0].usage_data.is_none());
+
+        let usage = client.disk_usage().await.unwrap();
+        let data = usage.volumes[0].usage_data.unwrap();
+        assert_eq!((data.size(), data.ref_count()), (Some(4096), Some(1)));
+        assert_eq!(usage.volumes[1].usage_data.unwrap().size(), None);
+
+        let details = client.inspect_container("aaa111").await.unwrap();
+        assert_eq!(details.mounts[0].name.as_deref(), Some("data"));
+
+        assert!(matches!(
+            client.remove_volume("data").await,
+            Err(WhaleError::Api { status: 409, .. })
+        ));
+        client.remove_volume("scratch").await.unwrap();
+        assert_eq!(client.list_volumes().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_networks() {
+        let daemon = MockDaemon::start(
+            fixtures()
+                .network("backend", "172.18.0.0/16", "172.18.0.1")
+                .attach("aaa111", "backend", "172.18.0.2"),
+        );
+        let client = daemon.client();
+
+        let containers = client.list_containers().await.unwrap();
+        let web = containers.iter().find(|c| c.id == "aaa111").unwrap();
+        assert_eq!(web.network_settings.networks["backend"].ip_address, "172.18.0.2");
+
+        let networks = client.list_networks().await.unwrap();
+        assert_eq!(networks.len(), 1);
+        assert_eq!(networks[0].ipam.config[0].subnet, "172.18.0.0/16");
+        assert!(networks[0].containers.is_empty());
+
+        let backend = client.inspect_network(&networks[0].id).await.unwrap();
+        assert_eq!(backend.containers["aaa111"].name, "web");
+        assert_eq!(backend.containers["aaa111"].ipv4_address, "172.18.0.2/16");
+
+        // aaa111 still holds .2, so bbb222 gets the next address
+        client.connect_network(&backend.id, "bbb222").await.unwrap();
+        assert!(matches!(
+            client.connect_network(&backend.id, "bbb222").await,
+            Err(WhaleError::Api { status: 403, .. })
+        ));
+        client.disconnect_network(&backend.id, "aaa111").await.unwrap();
+        let backend = client.inspect_network("backend").await.unwrap();
+        assert_eq!(backend.containers.keys().collect::<Vec<_>>(), ["bbb222"]);
+        assert_eq!(backend.containers["bbb222"].ipv4_address, "172.18.0.3/16");
+
+        assert!(matches!(
+            client.inspect_network("frontend").await,
+            Err(WhaleError::Api { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disk_usage() {
+        let daemon = MockDaemon::start(
+            fixtures()
+                .size_rw("aaa111", 100)
+                .size_rw("bbb222", 4_000)
+                .image(TEST_IMAGE_ID, &["test/image:latest"], 50_000, 1_700_000_000)
+                .image("sha256:old", &["test/image:old"], 20_000, 1_600_000_000)
+                .volume("data", 4096)
+                .volume("scratch", 512)
+                .mount("aaa111", "data", "/var/lib/data")
+                .build_cache("c1", "RUN make", 7_000, false)
+                .build_cache("c2", "COPY . .", 300, true),
+        );
+        let client = daemon.client();
+
+        let usage = client.disk_usage().await.unwrap();
+        assert_eq!(usage.layers_size, 70_000);
+        assert_eq!((usage.images[0].containers, usage.images[1].containers), (2, 0));
+        assert_eq!(usage.containers[1].size_rw, 4_000);
+        assert_eq!(usage.build_cache.len(), 2);
+        assert!(usage.build_cache[1].in_use);
+
+        let report = client.prune_containers().await.unwrap();
+        assert_eq!((report.deleted.len(), report.space_reclaimed), (1, 4_000));
+        let report = client.prune_images(true).await.unwrap();
+        assert_eq!((report.deleted.len(), report.space_reclaimed), (1, 20_000));
+        let report = client.prune_volumes().await.unwrap();
+        assert_eq!((report.deleted.len(), report.space_reclaimed), (1, 512));
+        let report = client.prune_build_cache().await.unwrap();
+        assert_eq!((report.deleted.len(), report.space_reclaimed), (1, 7_000));
+
+        let requests = daemon.requests();
+        assert!(requests.iter().any(|r| r.starts_with("POST /v1.52/volumes/prune?filters=")));
+        assert!(requests.contains(&"POST /v1.52/build/prune?all=true".to_string()));
+        let usage = client.disk_usage().await.unwrap();
+        assert_eq!((usage.containers.len(), usage.images.len(), usage.volumes.len(), usage.build_cache.len()), (1, 1, 1, 1));
+    }
+}
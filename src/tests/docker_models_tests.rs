@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use crate::docker::Port;
-    use crate::tui::format_ports;
+    use crate::docker::models::{ContainerTop, EndpointSettings, NetworkSettings, Process};
+    use crate::docker::{ContainerStats, Port};
+    use crate::tui::{format_networks, format_ports};
 
     #[test]
     fn test_format_ports() {
@@ -44,4 +45,98 @@ mod tests {
         ];
         assert_eq!(format_ports(&multiple_ports), "8080:80/tcp, 8443:443/tcp");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_format_networks() {
+        let mut settings = NetworkSettings::default();
+        assert_eq!(format_networks(&settings), "None");
+
+        settings.networks.insert(
+            "bridge".to_string(),
+            EndpointSettings {
+                ip_address: "172.17.0.2".to_string(),
+                ..EndpointSettings::default()
+            },
+        );
+        // Stopped containers have no address
+        settings.networks.insert("backend".to_string(), EndpointSettings::default());
+        assert_eq!(format_networks(&settings), "backend, bridge 172.17.0.2");
+    }
+
+    #[test]
+    fn test_stats_percentages_need_deltas() {
+        let mut stats = ContainerStats::default();
+        assert_eq!(stats.cpu_percent(), None);
+        assert_eq!(stats.memory_percent(), None);
+
+        // No system time elapsed between samples
+        stats.cpu_stats.system_cpu_usage = Some(1_000);
+        stats.cpu_stats.online_cpus = Some(2);
+        stats.precpu_stats.system_cpu_usage = Some(1_000);
+        assert_eq!(stats.cpu_percent(), None);
+
+        stats.memory_stats.usage = Some(100);
+        stats.memory_stats.limit = Some(0);
+        assert_eq!(stats.memory_percent(), None);
+    }
+
+    #[test]
+    fn test_missing_precpu_uses_previous_sample() {
+        let mut previous = ContainerStats::default();
+        previous.cpu_stats.cpu_usage.total_usage = 1_000;
+        previous.cpu_stats.system_cpu_usage = Some(10_000);
+
+        let mut stats = ContainerStats::default();
+        stats.cpu_stats.cpu_usage.total_usage = 3_000;
+        stats.cpu_stats.system_cpu_usage = Some(20_000);
+        stats.cpu_stats.online_cpus = Some(2);
+
+        stats.fill_missing_precpu(Some(&previous.cpu_stats));
+        assert_eq!(stats.cpu_percent(), Some(40.0));
+
+        // A populated precpu_stats is left alone
+        let mut other = ContainerStats::default();
+        other.cpu_stats.system_cpu_usage = Some(5);
+        stats.fill_missing_precpu(Some(&other.cpu_stats));
+        assert_eq!(stats.cpu_percent(), Some(40.0));
+    }
+
+    #[test]
+    fn test_top_processes() {
+        let top: ContainerTop = serde_json::from_str(
+            r#"{
+                "Titles": ["USER", "PID", "%CPU", "%MEM", "VSZ", "RSS", "TTY", "STAT", "START", "TIME", "COMMAND"],
+                "Processes": [["root", "1", "0.5", "1.2", "1024", "512", "?", "Ss", "12:00", "0:00", "nginx: master process"]]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            top.processes(),
+            vec![Process {
+                pid: 1,
+                user: "root".to_string(),
+                cpu_percent: Some(0.5),
+                memory_percent: Some(1.2),
+                command: "nginx: master process".to_string(),
+            }]
+        );
+
+        // Docker's default `ps -ef` columns have no memory usage
+        let top: ContainerTop = serde_json::from_str(
+            r#"{
+                "Titles": ["UID", "PID", "PPID", "C", "STIME", "TTY", "TIME", "CMD"],
+                "Processes": [["999", "42", "1", "3", "12:00", "?", "00:00:01", "postgres"]]
+            }"#,
+        )
+        .unwrap();
+        let processes = top.processes();
+        assert_eq!(processes[0].pid, 42);
+        assert_eq!(processes[0].user, "999");
+        assert_eq!(processes[0].cpu_percent, Some(3.0));
+        assert_eq!(processes[0].memory_percent, None);
+        assert_eq!(processes[0].command, "postgres");
+
+        let empty: ContainerTop = serde_json::from_str(r#"{"Titles": null, "Processes": null}"#).unwrap();
+        assert!(empty.processes().is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use crate::docker::DockerEndpoint;
+    use crate::docker::endpoint::{EndpointSource, default_socket_candidates, resolve_with};
+
+    fn temp_config_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cetacea-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_context(config_dir: &Path, dir_name: &str, name: &str, host: &str) {
+        let meta_dir = config_dir.join("contexts").join("meta").join(dir_name);
+        fs::create_dir_all(&meta_dir).unwrap();
+        fs::write(
+            meta_dir.join("meta.json"),
+            format!(r#"{{"Name":"{name}","Metadata":{{}},"Endpoints":{{"docker":{{"Host":"{host}","SkipTLSVerify":false}}}}}}"#),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(
+            DockerEndpoint::parse("unix:///run/user/1000/docker.sock").unwrap(),
+            DockerEndpoint::Unix(PathBuf::from("/run/user/1000/docker.sock"))
+        );
+        assert_eq!(
+            DockerEndpoint::parse("/var/run/docker.sock").unwrap(),
+            DockerEndpoint::Unix(PathBuf::from("/var/run/docker.sock"))
+        );
+        assert!(DockerEndpoint::parse("unix://").is_err());
+        assert!(DockerEndpoint::parse("localhost:2375").is_err());
+        assert_eq!(
+            DockerEndpoint::Unix(PathBuf::from("/var/run/docker.sock")).to_string(),
+            "unix:///var/run/docker.sock"
+        );
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        let config_dir = temp_config_dir("order");
+        fs::write(config_dir.join("config.json"), r#"{"currentContext":"rootless"}"#).unwrap();
+        write_context(&config_dir, "abc123", "rootless", "unix:///run/user/1000/docker.sock");
+
+        let (endpoint, source) =
+            resolve_with(Some("unix:///flag.sock"), Some("unix:///env.sock"), None, &config_dir).unwrap();
+        assert_eq!(endpoint, DockerEndpoint::Unix(PathBuf::from("/flag.sock")));
+        assert_eq!(source, EndpointSource::Flag);
+
+        let (endpoint, source) = resolve_with(None, Some("unix:///env.sock"), None, &config_dir).unwrap();
+        assert_eq!(endpoint, DockerEndpoint::Unix(PathBuf::from("/env.sock")));
+        assert_eq!(source, EndpointSource::Environment);
+
+        let (endpoint, source) = resolve_with(None, None, None, &config_dir).unwrap();
+        assert_eq!(endpoint, DockerEndpoint::Unix(PathBuf::from("/run/user/1000/docker.sock")));
+        assert_eq!(source, EndpointSource::Context);
+
+        let _ = fs::remove_dir_all(&config_dir);
+    }
+
+    #[test]
+    fn test_context_override_and_default() {
+        let config_dir = temp_config_dir("context");
+        write_context(&config_dir, "def456", "remote", "unix:///remote.sock");
+
+        let (endpoint, source) = resolve_with(None, None, Some("remote"), &config_dir).unwrap();
+        assert_eq!(endpoint, DockerEndpoint::Unix(PathBuf::from("/remote.sock")));
+        assert_eq!(source, EndpointSource::Context);
+
+        let (_, source) = resolve_with(None, None, Some("default"), &config_dir).unwrap();
+        assert_eq!(source, EndpointSource::Default);
+
+        assert!(resolve_with(None, None, Some("missing"), &config_dir).is_err());
+
+        let _ = fs::remove_dir_all(&config_dir);
+    }
+
+    #[test]
+    fn test_default_socket_candidates() {
+        let candidates = default_socket_candidates(Some(Path::new("/run/user/1000")));
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/var/run/docker.sock"),
+                PathBuf::from("/run/user/1000/docker.sock"),
+                PathBuf::from("/run/user/1000/podman/podman.sock"),
+                PathBuf::from("/run/podman/podman.sock"),
+            ]
+        );
+
+        assert_eq!(
+            default_socket_candidates(None),
+            vec![PathBuf::from("/var/run/docker.sock"), PathBuf::from("/run/podman/podman.sock")]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use std::{ops::ControlFlow, time::Duration};
+
+    use serde_json::{Value, json};
+
+    use crate::docker::connection::encode_filters;
+    use crate::docker::events::{EventEffect, apply_event, watch_containers};
+    use crate::docker::Container;
+    use crate::docker::models::DockerEvent;
+    use crate::tests::mock_daemon::{Fixtures, MockDaemon, container_json, event_json};
+
+    fn container(id: &str, name: &str, state: &str) -> Container {
+        serde_json::from_value(container_json(id, name, state)).unwrap()
+    }
+
+    fn event(value: Value) -> DockerEvent {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn die(id: &str, name: &str, code: &str) -> Value {
+        let mut value = event_json("die", id, name);
+        value["Actor"]["Attributes"]["exitCode"] = json!(code);
+        value
+    }
+
+    #[test]
+    fn test_encode_filters() {
+        assert_eq!(
+            encode_filters(&[("type", &["container"])]),
+            "%7B%22type%22%3A%5B%22container%22%5D%7D"
+        );
+    }
+
+    #[test]
+    fn test_apply_event_updates_in_place() {
+        let mut containers = vec![container("aaa111", "web", "running"), container("bbb222", "worker", "running")];
+
+        let effect = apply_event(&mut containers, &event(die("aaa111", "web", "137")));
+        assert_eq!(effect, EventEffect::Applied);
+        assert_eq!(containers[0].state, "exited");
+        assert_eq!(containers[0].status, "Exited (137)");
+
+        let effect = apply_event(&mut containers, &event(event_json("health_status: unhealthy", "bbb222", "worker")));
+        assert_eq!(effect, EventEffect::Applied);
+        assert_eq!(containers[1].health.as_ref().unwrap().status, "unhealthy");
+
+        apply_event(&mut containers, &event(event_json("pause", "bbb222", "worker")));
+        assert_eq!(containers[1].state, "paused");
+        apply_event(&mut containers, &event(event_json("unpause", "bbb222", "worker")));
+        assert_eq!(containers[1].state, "running");
+        assert_eq!(containers[1].status, "Up 5 minutes");
+
+        let effect = apply_event(&mut containers, &event(event_json("destroy", "aaa111", "web")));
+        assert_eq!(effect, EventEffect::Applied);
+        assert_eq!(containers.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_event_refetches_or_ignores() {
+        let mut containers = vec![container("aaa111", "web", "running")];
+
+        let effect = apply_event(&mut containers, &event(event_json("start", "aaa111", "web")));
+        assert_eq!(effect, EventEffect::Refetch("aaa111".to_string()));
+        let effect = apply_event(&mut containers, &event(event_json("create", "ccc333", "new")));
+        assert_eq!(effect, EventEffect::Refetch("ccc333".to_string()));
+        let effect = apply_event(&mut containers, &event(event_json("die", "ccc333", "new")));
+        assert_eq!(effect, EventEffect::Refetch("ccc333".to_string()));
+
+        let effect = apply_event(&mut containers, &event(event_json("exec_start: sh", "aaa111", "web")));
+        assert_eq!(effect, EventEffect::Ignored);
+        let mut network = event_json("connect", "aaa111", "web");
+        network["Type"] = json!("network");
+        assert_eq!(apply_event(&mut containers, &event(network)), EventEffect::Ignored);
+        assert_eq!(containers[0].state, "running");
+    }
+
+    #[tokio::test]
+    async fn test_watch_follows_events() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111", "web", "running")
+                .container("bbb222", "worker", "exited")
+                .event(die("aaa111", "web", "1"))
+                .event(event_json("start", "bbb222", "worker"))
+                .event(event_json("destroy", "bbb222", "worker")),
+        );
+        let client = daemon.client();
+
+        let mut snapshots = Vec::new();
+        watch_containers(client.as_ref(), || Duration::from_secs(60), |containers| {
+            snapshots.push(
+                containers
+                    .iter()
+                    .map(|c| format!("{} {}", c.names[0], c.state))
+                    .collect::<Vec<_>>(),
+            );
+            if snapshots.len() == 4 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })
+        .await;
+
+        assert_eq!(snapshots[0], vec!["/web running", "/worker exited"]);
+        assert_eq!(snapshots[1], vec!["/web exited", "/worker exited"]);
+        assert_eq!(snapshots[2], vec!["/web exited", "/worker exited"]);
+        assert_eq!(snapshots[3], vec!["/web exited"]);
+
+        // One full listing; the start event only refetched its own container
+        let requests = daemon.requests();
+        let full = requests.iter().filter(|r| r.ends_with("/containers/json?all=true&health=true")).count();
+        assert_eq!(full, 1);
+        assert!(requests.iter().any(|r| r.contains("/containers/json?all=true&health=true&filters=%7B%22id%22")));
+        assert!(requests.iter().any(|r| r.contains("/events?filters=")));
+    }
+
+    #[tokio::test]
+    async fn test_watch_resyncs_periodically() {
+        let daemon = MockDaemon::start(Fixtures::default().container("aaa111", "web", "running").hold_events());
+        let client = daemon.client();
+        let mut listed = 0;
+        watch_containers(client.as_ref(), || Duration::from_millis(20), |_| {
+            listed += 1;
+            if listed == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })
+        .await;
+
+        let lists = daemon
+            .requests()
+            .iter()
+            .filter(|r| r.ends_with("/containers/json?all=true&health=true"))
+            .count();
+        assert_eq!(lists, 3);
+        // Re-listing didn't need a new subscription
+        assert_eq!(daemon.requests().iter().filter(|r| r.contains("/events")).count(), 1);
+    }
+}
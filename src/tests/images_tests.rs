@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::docker::models::{ImageSummary, NetworkSettings};
+    use crate::tui::images::{SortColumn, count_usage, instruction, sort_images};
+
+    fn image(id: &str, tags: &[&str], size: u64, created: i64) -> ImageSummary {
+        ImageSummary {
+            id: id.to_string(),
+            repo_tags: tags.iter().map(ToString::to_string).collect(),
+            size,
+            created,
+            ..ImageSummary::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_images() {
+        let mut images = vec![
+            image("sha256:a", &["app:1"], 300, 10),
+            image("sha256:b", &["app:2"], 100, 30),
+            image("sha256:c", &[], 200, 20),
+        ];
+        let ids = |images: &[ImageSummary]| images.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+
+        sort_images(&mut images, SortColumn::Created, true);
+        assert_eq!(ids(&images), vec!["sha256:b", "sha256:c", "sha256:a"]);
+        sort_images(&mut images, SortColumn::Size, true);
+        assert_eq!(ids(&images), vec!["sha256:a", "sha256:c", "sha256:b"]);
+        sort_images(&mut images, SortColumn::Size, false);
+        assert_eq!(ids(&images), vec!["sha256:b", "sha256:c", "sha256:a"]);
+    }
+
+    #[test]
+    fn test_dangling_and_short_id() {
+        let tagged = image("sha256:0123456789abcdef0123", &["nginx:latest"], 0, 0);
+        assert!(!tagged.is_dangling());
+        assert_eq!(tagged.short_id(), "0123456789ab");
+        // Older daemons list `<none>:<none>`, newer ones nothing at all
+        assert!(image("sha256:1", &["<none>:<none>"], 0, 0).is_dangling());
+        assert!(image("sha256:2", &[], 0, 0).is_dangling());
+        assert_eq!(image("abc", &[], 0, 0).short_id(), "abc");
+    }
+
+    #[test]
+    fn test_instruction() {
+        assert_eq!(instruction("/bin/sh -c #(nop)  CMD [\"nginx\"]"), "CMD [\"nginx\"]");
+        assert_eq!(instruction("/bin/sh -c apt-get update"), "RUN apt-get update");
+        assert_eq!(instruction("RUN /bin/sh -c make # buildkit"), "RUN /bin/sh -c make # buildkit");
+    }
+
+    #[test]
+    fn test_count_usage() {
+        let container = |image_id: &str| crate::docker::Container {
+            id: String::new(),
+            names: vec![],
+            image: String::new(),
+            image_id: image_id.to_string(),
+            command: String::new(),
+            created: 0,
+            state: "running".to_string(),
+            status: String::new(),
+            ports: vec![],
+            health: None,
+            pod_name: None,
+            network_settings: NetworkSettings::default(),
+            mounts: vec![],
+        };
+        let usage = count_usage(&[container("sha256:a"), container("sha256:b"), container("sha256:a")]);
+        assert_eq!(usage.get("sha256:a"), Some(&2));
+        assert_eq!(usage.get("sha256:b"), Some(&1));
+        assert_eq!(usage.get("sha256:c"), None);
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::docker::models::ContainerDetails;
+    use crate::tui::inspect::{JsonTree, summary_lines};
+
+    fn document() -> serde_json::Value {
+        json!({
+            "Id": "aaa111",
+            "Config": {
+                "Env": ["PATH=/usr/bin"],
+                "ExposedPorts": { "80/tcp": {} },
+                "Image": "nginx:latest",
+            },
+            "Mounts": [{ "Type": "volume", "Name": "data", "Destination": "/data" }],
+        })
+    }
+
+    fn keys(tree: &JsonTree) -> Vec<String> {
+        tree.rows()
+            .iter()
+            .map(|row| format!("{}{}", "  ".repeat(row.depth), row.key))
+            .collect()
+    }
+
+    fn selected_pointer(tree: &JsonTree) -> String {
+        tree.rows()[tree.selected()].pointer.clone()
+    }
+
+    #[test]
+    fn test_tree_expand_and_collapse() {
+        let mut tree = JsonTree::new(document());
+        assert_eq!(keys(&tree), vec!["Config", "Id", "Mounts"]);
+        assert_eq!(tree.rows()[0].children, Some(3));
+        assert_eq!(tree.rows()[1].children, None);
+
+        tree.expand();
+        assert_eq!(keys(&tree), vec!["Config", "  Env", "  ExposedPorts", "  Image", "Id", "Mounts"]);
+        assert!(tree.is_expanded("/Config"));
+
+        // Collapsing a closed node goes to its parent, then closes that
+        tree.move_by(3);
+        assert_eq!(selected_pointer(&tree), "/Config/Image");
+        tree.collapse();
+        assert_eq!(selected_pointer(&tree), "/Config");
+        tree.collapse();
+        assert_eq!(keys(&tree), vec!["Config", "Id", "Mounts"]);
+
+        tree.expand_all();
+        assert_eq!(tree.rows().len(), 12);
+        tree.move_to(usize::MAX);
+        assert_eq!(selected_pointer(&tree), "/Mounts/0/Type");
+        tree.collapse_all();
+        assert_eq!(keys(&tree), vec!["Config", "Id", "Mounts"]);
+        assert_eq!(selected_pointer(&tree), "/Mounts");
+
+        tree.toggle();
+        assert_eq!(keys(&tree), vec!["Config", "Id", "Mounts", "  [0]"]);
+    }
+
+    #[test]
+    fn test_tree_search_reveals_matches() {
+        let mut tree = JsonTree::new(document());
+        // Keys are escaped in pointers, and searching ignores case
+        let matches = tree.find("TCP");
+        assert_eq!(matches, vec!["/Config/ExposedPorts/80~1tcp"]);
+        tree.reveal(&matches[0]);
+        assert_eq!(selected_pointer(&tree), "/Config/ExposedPorts/80~1tcp");
+        assert!(tree.is_expanded("/Config") && tree.is_expanded("/Config/ExposedPorts"));
+        assert_eq!(tree.selected_text().unwrap(), "{}");
+
+        // Array indices aren't keys
+        assert_eq!(tree.find("0"), vec!["/Config/ExposedPorts/80~1tcp"]);
+        assert!(tree.find("nothing").is_empty());
+
+        tree.reveal("/Config/Image");
+        assert_eq!(tree.selected_text().unwrap(), "nginx:latest");
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let details: ContainerDetails = serde_json::from_value(json!({
+            "Config": {
+                "Image": "nginx:latest",
+                "Cmd": ["nginx", "-g", "daemon off;"],
+                "Env": ["PATH=/usr/bin", "MODE=prod"],
+                "Labels": null,
+            },
+            "HostConfig": {
+                "RestartPolicy": { "Name": "on-failure", "MaximumRetryCount": 3 },
+                "Memory": 536_870_912,
+                "NanoCpus": 1_500_000_000,
+                "PidsLimit": null,
+            },
+            "Mounts": [
+                { "Type": "bind", "Source": "/srv/www", "Destination": "/usr/share/nginx/html", "RW": false },
+                { "Type": "volume", "Name": "cache", "Source": "/var/lib/docker/volumes/cache/_data", "Destination": "/cache", "RW": true },
+            ],
+            "NetworkSettings": {
+                "Networks": { "bridge": { "IPAddress": "172.17.0.2", "Gateway": "172.17.0.1", "MacAddress": "" } },
+            },
+        }))
+        .unwrap();
+
+        let text: Vec<String> = summary_lines(&details)
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect();
+        let has = |expected: &str| text.iter().any(|line| line.trim_end() == expected);
+        assert!(has("  Command         nginx -g daemon off;"));
+        assert!(has("  Entrypoint      -"));
+        assert!(has("  Restart policy  on-failure (at most 3 retries)"));
+        assert!(has("  Memory          512.0 MiB"));
+        assert!(has("  CPUs            1.5"));
+        assert!(has("  PIDs            unlimited"));
+        assert!(has("Environment (2)"));
+        assert!(has("  MODE=prod"));
+        assert!(has("  bind /srv/www → /usr/share/nginx/html (ro)"));
+        assert!(has("  volume cache → /cache (rw)"));
+        assert!(has("  bridge  172.17.0.2  gateway 172.17.0.1"));
+        assert!(has("Labels (0)"));
+        assert!(has("  none"));
+    }
+}
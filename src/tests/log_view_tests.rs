@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use crate::docker::{LogLine, LogStream};
+    use crate::tui::logs::{LogBuffer, LogLevel, find_levels, pretty_json};
+
+    fn line(text: &str) -> LogLine {
+        LogLine {
+            stream: LogStream::Stdout,
+            timestamp: None,
+            time: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_log_buffer_drops_oldest() {
+        let mut buffer = LogBuffer::new(3);
+        for text in ["a", "b", "c"] {
+            assert!(buffer.push(line(text)).is_none());
+        }
+        assert_eq!(buffer.push(line("d")).unwrap().text, "a");
+        assert_eq!(buffer.push(line("e")).unwrap().text, "b");
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.get(0).unwrap().text, "c");
+        assert_eq!(buffer.get(2).unwrap().text, "e");
+        assert!(buffer.get(3).is_none());
+        assert_eq!(buffer.partition_point(|l| l.text.as_str() < "d"), 1);
+    }
+
+    #[test]
+    fn test_find_levels() {
+        let text = "2024 [ERROR] db: WARNING retrying, info only in prose";
+        let levels = find_levels(text);
+        let found: Vec<_> = levels.iter().map(|(range, level)| (&text[range.clone()], *level)).collect();
+        assert_eq!(found, vec![("ERROR", LogLevel::Error), ("WARNING", LogLevel::Warn)]);
+
+        let text = "time=12:00 level=debug msg=started INFORMATION";
+        let levels = find_levels(text);
+        assert_eq!(levels.len(), 1);
+        assert_eq!(&text[levels[0].0.clone()], "debug");
+        assert_eq!(levels[0].1, LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_pretty_json() {
+        assert_eq!(
+            pretty_json(r#"{"port":80,"msg":"listening on port","level":"info","time":"12:00"}"#).as_deref(),
+            Some(r#"time=12:00 level=info msg="listening on port" port=80"#)
+        );
+        assert_eq!(
+            pretty_json(r#" {"nested":{"a":1},"tags":["x"]} "#).as_deref(),
+            Some(r#"nested={"a":1} tags=["x"]"#)
+        );
+        assert_eq!(pretty_json("{not json"), None);
+        assert_eq!(pretty_json("[1, 2]"), None);
+        assert_eq!(pretty_json("plain text"), None);
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        convert::Infallible,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use bytes::Bytes;
+    use http_body_util::BodyExt;
+    use hyper::body::{Body, Frame};
+
+    use crate::docker::logs::{LogReader, merge_logs, parse_timestamp};
+    use crate::docker::{LogLine, LogOptions, LogStream, MergedLog};
+    use crate::tests::mock_daemon::{Fixtures, MockDaemon, multiplexed};
+
+    /// A body delivered in the given pieces, to split frames across reads.
+    struct Chunks(VecDeque<Bytes>);
+
+    impl Body for Chunks {
+        type Data = Bytes;
+        type Error = Infallible;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+            Poll::Ready(self.0.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+        }
+    }
+
+    fn reader(chunks: Vec<Bytes>, tty: bool) -> LogReader {
+        let body = Chunks(chunks.into()).map_err(|never| match never {}).boxed_unsync();
+        LogReader::new(body, tty)
+    }
+
+    async fn read_all(mut reader: LogReader) -> Vec<LogLine> {
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next().await {
+            lines.push(line.unwrap());
+        }
+        lines
+    }
+
+    fn line(stream: LogStream, timestamp: Option<&str>, text: &str) -> LogLine {
+        LogLine {
+            stream,
+            timestamp: timestamp.map(str::to_string),
+            time: timestamp.and_then(parse_timestamp),
+            text: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_demultiplexes_split_frames() {
+        let data = multiplexed(&[
+            (1, "2024-01-01T00:00:01.000000000Z listening on :80".to_string()),
+            (2, "2024-01-01T00:00:02.000000000Z warning: no TLS".to_string()),
+            (1, "no timestamp".to_string()),
+        ]);
+        // Cut inside the first header and both payloads
+        let chunks = vec![data.slice(..3), data.slice(3..50), data.slice(50..70), data.slice(70..)];
+
+        let lines = read_all(reader(chunks, false)).await;
+        assert_eq!(
+            lines,
+            vec![
+                line(LogStream::Stdout, Some("2024-01-01T00:00:01.000000000Z"), "listening on :80"),
+                line(LogStream::Stderr, Some("2024-01-01T00:00:02.000000000Z"), "warning: no TLS"),
+                line(LogStream::Stdout, None, "no timestamp"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lines_span_frames_per_stream() {
+        let mut data = Vec::new();
+        for (stream, payload) in [(1u8, "hel"), (2, "oops\n"), (1, "lo\nbye")] {
+            data.extend_from_slice(&[stream, 0, 0, 0]);
+            data.extend_from_slice(&u32::try_from(payload.len()).unwrap().to_be_bytes());
+            data.extend_from_slice(payload.as_bytes());
+        }
+
+        let lines = read_all(reader(vec![Bytes::from(data)], false)).await;
+        assert_eq!(
+            lines,
+            vec![
+                line(LogStream::Stderr, None, "oops"),
+                line(LogStream::Stdout, None, "hello"),
+                // Kept even though the stream ended mid-line
+                line(LogStream::Stdout, None, "bye"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tty_output_is_raw() {
+        let chunks = vec![Bytes::from_static(b"prompt> ls\r\nfile"), Bytes::from_static(b".txt\r\n")];
+        let lines = read_all(reader(chunks, true)).await;
+        assert_eq!(
+            lines,
+            vec![line(LogStream::Stdout, None, "prompt> ls"), line(LogStream::Stdout, None, "file.txt")]
+        );
+    }
+
+    #[test]
+    fn test_log_options_query() {
+        assert_eq!(
+            LogOptions::default().query(),
+            "follow=1&stdout=1&stderr=1&timestamps=1&tail=all"
+        );
+        let options = LogOptions {
+            since: Some(1_700_000_000),
+            tail: Some(100),
+        };
+        assert_eq!(
+            options.query(),
+            "follow=1&stdout=1&stderr=1&timestamps=1&since=1700000000&tail=100"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_checks_for_tty() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111", "web", "running")
+                .container("bbb222", "shell", "running")
+                .logs("aaa111", &[(1, "listening on :80"), (2, "warning: no TLS")])
+                .logs("bbb222", &[(1, "root@bbb222:/# ")])
+                .tty("bbb222"),
+        );
+        let client = daemon.client();
+
+        let lines = read_all(client.logs("aaa111", &LogOptions::default()).await.unwrap()).await;
+        assert_eq!(lines[0], line(LogStream::Stdout, None, "listening on :80"));
+        assert_eq!(lines[1], line(LogStream::Stderr, None, "warning: no TLS"));
+
+        let lines = read_all(client.logs("bbb222", &LogOptions::default()).await.unwrap()).await;
+        assert_eq!(lines, vec![line(LogStream::Stdout, None, "root@bbb222:/# ")]);
+
+        assert!(
+            daemon
+                .requests()
+                .contains(&"GET /v1.52/containers/aaa111/logs?follow=1&stdout=1&stderr=1&timestamps=1&tail=all".to_string())
+        );
+        assert!(client.logs("ccc333", &LogOptions::default()).await.is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some((0, 0)));
+        assert_eq!(parse_timestamp("2024-01-01T00:00:05.000000000Z"), Some((1_704_067_205, 0)));
+        assert_eq!(parse_timestamp("2024-01-01T00:00:05.1Z"), Some((1_704_067_205, 100_000_000)));
+        assert_eq!(parse_timestamp("2024-01-01T00:00:05.12Z"), Some((1_704_067_205, 120_000_000)));
+        assert_eq!(parse_timestamp("2024-01-01T02:00:05+02:00"), Some((1_704_067_205, 0)));
+        assert_eq!(parse_timestamp("2024-02-29T12:00:00Z"), Some((1_709_208_000, 0)));
+        assert_eq!(parse_timestamp("2024-01-01 00:00:05Z"), None);
+        assert_eq!(parse_timestamp("2024-13-01T00:00:05Z"), None);
+        assert_eq!(parse_timestamp("2024-01-01T00:00:05"), None);
+        assert_eq!(parse_timestamp("listening on :80"), None);
+    }
+
+    #[tokio::test]
+    async fn test_merge_logs_orders_fractions_and_untimestamped_lines() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111", "web", "running")
+                .container("bbb222", "worker", "running")
+                .logs(
+                    "aaa111",
+                    &[(1, "2024-01-01T00:00:05.12Z late"), (1, "no timestamp"), (1, "2024-01-01T00:00:06Z last")],
+                )
+                .logs("bbb222", &[(1, "2024-01-01T00:00:05.1Z early")]),
+        );
+        let ids = vec!["aaa111".to_string(), "bbb222".to_string()];
+
+        let mut lines = Vec::new();
+        merge_logs(daemon.client(), ids, LogOptions::default(), |message| {
+            if let MergedLog::Line(_, line) = message {
+                lines.push(line.text);
+            }
+        })
+        .await;
+
+        // The untimestamped line stays after the one before it
+        assert_eq!(lines, ["early", "late", "no timestamp", "last"]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_logs_interleaves_by_timestamp() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111", "web", "running")
+                .container("bbb222", "worker", "running")
+                .logs(
+                    "aaa111",
+                    &[(1, "2024-01-01T00:00:01.000000000Z one"), (1, "2024-01-01T00:00:03.000000000Z three")],
+                )
+                .logs(
+                    "bbb222",
+                    &[(2, "2024-01-01T00:00:02.000000000Z two"), (1, "2024-01-01T00:00:04.000000000Z four")],
+                ),
+        );
+        let ids = vec!["aaa111".to_string(), "bbb222".to_string(), "ccc333".to_string()];
+
+        let mut lines = Vec::new();
+        let mut ended = Vec::new();
+        merge_logs(daemon.client(), ids, LogOptions::default(), |message| match message {
+            MergedLog::Line(index, line) => lines.push((index, line.text)),
+            MergedLog::Ended(index, error) => ended.push((index, error.is_some())),
+        })
+        .await;
+
+        let expected = [(0, "one"), (1, "two"), (0, "three"), (1, "four")];
+        assert_eq!(lines, expected.map(|(index, text)| (index, text.to_string())));
+        ended.sort_unstable();
+        // The missing container fails without stopping the others
+        assert_eq!(ended, vec![(0, false), (1, false), (2, true)]);
+    }
+}
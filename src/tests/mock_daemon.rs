@@ -0,0 +1,1165 @@
+//! An in-process stand-in for the Docker Engine API, served over a temporary
+//! Unix socket from scripted fixtures so tests never need a real daemon.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    path::PathBuf,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+    thread::{self, JoinHandle},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http_body_util::{BodyExt, Collected, Full, combinators::UnsyncBoxBody};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Body, Frame, Incoming},
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper::upgrade::OnUpgrade;
+use hyper_util::rt::TokioIo;
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    sync::oneshot,
+};
+
+use crate::docker::{Connection, ConnectionFactory, DockerEndpoint};
+
+static NEXT_SOCKET: AtomicUsize = AtomicUsize::new(0);
+
+/// ID of `test/image:latest`, which every scripted container runs.
+pub const TEST_IMAGE_ID: &str = "sha256:7e5f0a3c9b1d2e4f6a8b0c1d3e5f7a9b1c3d5e7f9a1b3c5d7e9f1a3b5c7d9e1f";
+
+/// Canned daemon state. Stats samples are streamed in order, then the
+/// stream ends as if the container stopped.
+#[derive(Debug, Clone, Default)]
+pub struct Fixtures {
+    pub containers: Vec<Value>,
+    pub stats: HashMap<String, Vec<Value>>,
+    pub inspect: HashMap<String, Value>,
+    /// `(stream, line)` pairs, where stream 1 is stdout and 2 is stderr.
+    pub logs: HashMap<String, Vec<(u8, String)>>,
+    pub events: Vec<Value>,
+    /// Body of `/containers/{id}/top`.
+    pub top: HashMap<String, Value>,
+    /// `(path, kind)` pairs of `/containers/{id}/changes`; `null` when unset.
+    pub changes: HashMap<String, Vec<(String, u8)>>,
+    /// Entries of `/images/json`.
+    pub images: Vec<Value>,
+    /// Body of `/images/{id}/history`.
+    pub history: HashMap<String, Value>,
+    /// Volumes, with their size in `UsageData`.
+    pub volumes: Vec<Value>,
+    /// Networks as `/networks` lists them; attachments live in each
+    /// container's `NetworkSettings`.
+    pub networks: Vec<Value>,
+    /// Build cache records of `/system/df`.
+    pub build_cache: Vec<Value>,
+    /// Body of `/version`; a current Docker Engine when unset.
+    pub version: Option<Value>,
+    /// Body of Podman's `/libpod/pods/json`; 404 like Docker when unset.
+    pub pods: Option<Vec<Value>>,
+    /// Keep `/events` open after the scripted events instead of closing it.
+    pub hold_events: bool,
+    /// Created execs, indexed by the number in their `exec-N` ID, as
+    /// `/exec/{id}/json` reports them plus the config they were created with.
+    pub execs: Vec<Value>,
+}
+
+impl Fixtures {
+    pub fn container(mut self, id: &str, name: &str, state: &str) -> Self {
+        self.containers.push(container_json(id, name, state));
+        self.inspect.insert(
+            id.to_string(),
+            json!({
+                "Id": id,
+                "Name": format!("/{name}"),
+                "State": { "Status": state, "Running": state == "running" },
+                "Config": { "Image": "test/image:latest", "Env": ["PATH=/usr/bin"], "Labels": {} },
+            }),
+        );
+        self
+    }
+
+    pub fn stats(mut self, id: &str, samples: Vec<Value>) -> Self {
+        self.stats.insert(id.to_string(), samples);
+        self
+    }
+
+    pub fn logs(mut self, id: &str, lines: &[(u8, &str)]) -> Self {
+        self.logs.insert(
+            id.to_string(),
+            lines.iter().map(|(stream, line)| (*stream, (*line).to_string())).collect(),
+        );
+        self
+    }
+
+    /// Give a container a TTY, so its logs are served without frame headers.
+    pub fn tty(mut self, id: &str) -> Self {
+        if let Some(inspect) = self.inspect.get_mut(id) {
+            inspect["Config"]["Tty"] = json!(true);
+        }
+        self
+    }
+
+    /// Processes listed by `ps aux`, as `(pid, user, %cpu, %mem, command)`.
+    pub fn top(mut self, id: &str, processes: &[(u32, &str, f64, f64, &str)]) -> Self {
+        let rows: Vec<Value> = processes
+            .iter()
+            .map(|(pid, user, cpu, mem, command)| {
+                json!([user, pid.to_string(), cpu.to_string(), mem.to_string(), "1024", "512", "?", "Ss", "12:00", "0:00", command])
+            })
+            .collect();
+        self.top.insert(
+            id.to_string(),
+            json!({
+                "Titles": ["USER", "PID", "%CPU", "%MEM", "VSZ", "RSS", "TTY", "STAT", "START", "TIME", "COMMAND"],
+                "Processes": rows,
+            }),
+        );
+        self
+    }
+
+    pub fn changes(mut self, id: &str, changes: &[(&str, u8)]) -> Self {
+        self.changes.insert(
+            id.to_string(),
+            changes.iter().map(|(path, kind)| ((*path).to_string(), *kind)).collect(),
+        );
+        self
+    }
+
+    /// An image; no tags makes it dangling.
+    pub fn image(mut self, id: &str, tags: &[&str], size: u64, created: i64) -> Self {
+        self.images.push(json!({
+            "Id": id,
+            "ParentId": "",
+            "RepoTags": if tags.is_empty() { json!(["<none>:<none>"]) } else { json!(tags) },
+            "Created": created,
+            "Size": size,
+            "Containers": -1,
+        }));
+        self
+    }
+
+    /// Layers of an image, newest first, as `(created by, size)`.
+    pub fn history(mut self, id: &str, layers: &[(&str, u64)]) -> Self {
+        let layers: Vec<Value> = layers
+            .iter()
+            .enumerate()
+            .map(|(index, (created_by, size))| {
+                json!({
+                    "Id": if index == 0 { id } else { "<missing>" },
+                    "Created": 1_700_000_000 - i64::try_from(index).unwrap() * 60,
+                    "CreatedBy": created_by,
+                    "Tags": null,
+                    "Size": size,
+                    "Comment": "",
+                })
+            })
+            .collect();
+        self.history.insert(id.to_string(), Value::Array(layers));
+        self
+    }
+
+    /// A local volume of `size` bytes.
+    pub fn volume(mut self, name: &str, size: i64) -> Self {
+        self.volumes.push(json!({
+            "Name": name,
+            "Driver": "local",
+            "Mountpoint": format!("/var/lib/docker/volumes/{name}/_data"),
+            "CreatedAt": "2024-01-01T00:00:00Z",
+            "Labels": null,
+            "Scope": "local",
+            "UsageData": { "Size": size, "RefCount": -1 },
+        }));
+        self
+    }
+
+    /// Mount a volume into a container.
+    pub fn mount(mut self, id: &str, volume: &str, destination: &str) -> Self {
+        let mount = json!({
+            "Type": "volume",
+            "Name": volume,
+            "Source": format!("/var/lib/docker/volumes/{volume}/_data"),
+            "Destination": destination,
+            "RW": true,
+        });
+        let listed = self.containers.iter_mut().filter(|c| c["Id"] == id);
+        for container in listed.chain(self.inspect.get_mut(id)) {
+            match container["Mounts"].as_array_mut() {
+                Some(mounts) => mounts.push(mount.clone()),
+                None => container["Mounts"] = json!([mount]),
+            }
+        }
+        self
+    }
+
+    /// Bytes a container has written to its writable layer.
+    pub fn size_rw(mut self, id: &str, size: u64) -> Self {
+        for container in self.containers.iter_mut().filter(|c| c["Id"] == id) {
+            container["SizeRw"] = json!(size);
+        }
+        self
+    }
+
+    /// A build cache record of `size` bytes.
+    pub fn build_cache(mut self, id: &str, description: &str, size: u64, in_use: bool) -> Self {
+        self.build_cache.push(json!({
+            "ID": id,
+            "Type": "regular",
+            "Description": description,
+            "InUse": in_use,
+            "Shared": false,
+            "Size": size,
+            "LastUsedAt": "2024-01-01T00:00:00Z",
+            "UsageCount": 1,
+        }));
+        self
+    }
+
+    /// A bridge network with one address pool.
+    pub fn network(mut self, name: &str, subnet: &str, gateway: &str) -> Self {
+        self.networks.push(json!({
+            "Name": name,
+            "Id": format!("{name:0<12}{}", "0".repeat(52)),
+            "Driver": "bridge",
+            "Scope": "local",
+            "Internal": false,
+            "IPAM": { "Driver": "default", "Config": [{ "Subnet": subnet, "Gateway": gateway }] },
+        }));
+        self
+    }
+
+    /// Attach a container to a network at `ip`.
+    pub fn attach(mut self, id: &str, network: &str, ip: &str) -> Self {
+        attach(&mut self, id, network, ip);
+        self
+    }
+
+    pub fn event(mut self, event: Value) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn hold_events(mut self) -> Self {
+        self.hold_events = true;
+        self
+    }
+
+    pub fn version(mut self, version: &str, api_version: &str, min_api_version: &str) -> Self {
+        self.version = Some(json!({
+            "Version": version,
+            "ApiVersion": api_version,
+            "MinAPIVersion": min_api_version,
+            "Os": "linux",
+            "Arch": "amd64",
+            "KernelVersion": "6.1.0",
+        }));
+        self
+    }
+
+    /// Answer like Podman's compat API, with the given `(pod, container ids)`.
+    pub fn podman(mut self, pods: &[(&str, &[&str])]) -> Self {
+        self.version = Some(json!({
+            "Version": "4.9.3",
+            "ApiVersion": "1.41",
+            "MinAPIVersion": "1.24",
+            "Os": "linux",
+            "Components": [{ "Name": "Podman Engine", "Version": "4.9.3" }],
+        }));
+        self.pods = Some(
+            pods.iter()
+                .map(|(name, ids)| {
+                    json!({
+                        "Name": name,
+                        "Containers": ids.iter().map(|id| json!({ "Id": id })).collect::<Vec<_>>(),
+                    })
+                })
+                .collect(),
+        );
+        self
+    }
+}
+
+fn attach(fixtures: &mut Fixtures, id: &str, network: &str, ip: &str) {
+    let gateway = fixtures
+        .networks
+        .iter()
+        .find(|n| n["Name"] == network)
+        .map_or(Value::Null, |n| n["IPAM"]["Config"][0]["Gateway"].clone());
+    let endpoint = json!({
+        "IPAddress": ip,
+        "Gateway": gateway,
+        "MacAddress": "02:42:ac:11:00:02",
+    });
+    let targets = fixtures
+        .containers
+        .iter_mut()
+        .filter(|c| c["Id"] == id)
+        .chain(fixtures.inspect.get_mut(id));
+    for container in targets {
+        container["NetworkSettings"]["Networks"][network] = endpoint.clone();
+    }
+}
+
+pub fn container_json(id: &str, name: &str, state: &str) -> Value {
+    json!({
+        "Id": id,
+        "Names": [format!("/{name}")],
+        "Image": "test/image:latest",
+        "ImageID": TEST_IMAGE_ID,
+        "Command": "/entrypoint.sh",
+        "Created": 1_700_000_000,
+        "State": state,
+        "Status": status_text(state),
+        "Ports": [],
+    })
+}
+
+fn status_text(state: &str) -> &'static str {
+    match state {
+        "running" => "Up 5 minutes",
+        "paused" => "Up 5 minutes (Paused)",
+        _ => "Exited (0) 5 minutes ago",
+    }
+}
+
+/// A stats payload with the given cumulative CPU counters and memory usage.
+pub fn stats_sample(cpu: (u64, u64), system: (u64, u64), online_cpus: u32, memory: (u64, u64)) -> Value {
+    json!({
+        "read": "2024-01-01T00:00:01Z",
+        "preread": "2024-01-01T00:00:00Z",
+        "cpu_stats": {
+            "cpu_usage": { "total_usage": cpu.0 },
+            "system_cpu_usage": system.0,
+            "online_cpus": online_cpus,
+        },
+        "precpu_stats": {
+            "cpu_usage": { "total_usage": cpu.1 },
+            "system_cpu_usage": system.1,
+        },
+        "memory_stats": { "usage": memory.0, "limit": memory.1 },
+    })
+}
+
+pub fn event_json(action: &str, id: &str, name: &str) -> Value {
+    json!({
+        "Type": "container",
+        "Action": action,
+        "Actor": { "ID": id, "Attributes": { "name": name, "image": "test/image:latest" } },
+        "time": 1_700_000_000,
+        "timeNano": 1_700_000_000_000_000_000_i64,
+    })
+}
+
+/// Encode log lines with Docker's 8-byte stream multiplexing headers.
+pub fn multiplexed(lines: &[(u8, String)]) -> Bytes {
+    let mut buf = BytesMut::new();
+    for (stream, line) in lines {
+        let payload = format!("{line}\n");
+        buf.put_u8(*stream);
+        buf.put_bytes(0, 3);
+        buf.put_u32(u32::try_from(payload.len()).unwrap());
+        buf.put_slice(payload.as_bytes());
+    }
+    buf.freeze()
+}
+
+pub struct MockDaemon {
+    socket: PathBuf,
+    fixtures: Arc<Mutex<Fixtures>>,
+    requests: Arc<Mutex<Vec<String>>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockDaemon {
+    /// Bind a fresh socket and serve `fixtures` from a dedicated runtime
+    /// thread, so both sync and async tests can talk to it.
+    pub fn start(fixtures: Fixtures) -> Self {
+        let socket = std::env::temp_dir().join(format!(
+            "cetacea-mock-{}-{}.sock",
+            std::process::id(),
+            NEXT_SOCKET.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_file(&socket);
+        let listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        listener.set_nonblocking(true).unwrap();
+
+        let fixtures = Arc::new(Mutex::new(fixtures));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+
+        let server_fixtures = Arc::clone(&fixtures);
+        let server_requests = Arc::clone(&requests);
+        let thread = thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(async move {
+                let listener = tokio::net::UnixListener::from_std(listener).unwrap();
+                loop {
+                    tokio::select! {
+                        _ = &mut shutdown_rx => break,
+                        accepted = listener.accept() => {
+                            let Ok((stream, _)) = accepted else { break };
+                            let fixtures = Arc::clone(&server_fixtures);
+                            let requests = Arc::clone(&server_requests);
+                            tokio::spawn(async move {
+                                let service = service_fn(move |req: Request<Incoming>| {
+                                    let fixtures = Arc::clone(&fixtures);
+                                    let requests = Arc::clone(&requests);
+                                    async move {
+                                        let (parts, body) = req.into_parts();
+                                        let body = body.collect().await.map(Collected::to_bytes).unwrap_or_default();
+                                        let mut req = Request::from_parts(parts, ());
+                                        Ok::<_, Infallible>(respond(&fixtures, &requests, &mut req, &body))
+                                    }
+                                });
+                                let _ = http1::Builder::new()
+                                    .serve_connection(TokioIo::new(stream), service)
+                                    .with_upgrades()
+                                    .await;
+                            });
+                        }
+                    }
+                }
+            });
+        });
+
+        Self {
+            socket,
+            fixtures,
+            requests,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        }
+    }
+
+    pub fn endpoint(&self) -> DockerEndpoint {
+        DockerEndpoint::Unix(self.socket.clone())
+    }
+
+    pub fn client(&self) -> Arc<dyn Connection> {
+        ConnectionFactory::create(&self.endpoint()).unwrap()
+    }
+
+    /// Change the scripted state while the daemon is running.
+    pub fn update(&self, f: impl FnOnce(&mut Fixtures)) {
+        f(&mut self.fixtures.lock().unwrap());
+    }
+
+    /// Every request seen so far, as `METHOD path?query`.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockDaemon {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = std::fs::remove_file(&self.socket);
+    }
+}
+
+fn respond(
+    shared: &Arc<Mutex<Fixtures>>,
+    requests: &Mutex<Vec<String>>,
+    req: &mut Request<()>,
+    body: &Bytes,
+) -> Response<MockBody> {
+    let path = req.uri().path().to_string();
+    let path = path.as_str();
+    requests.lock().unwrap().push(format!(
+        "{} {}",
+        req.method(),
+        req.uri().path_and_query().map_or(path, |p| p.as_str())
+    ));
+
+    // Strip the `/v1.xx` prefix so fixtures answer for any API version.
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let segments = match segments.first() {
+        Some(first) if first.starts_with('v') && first[1..].parse::<f32>().is_ok() => &segments[1..],
+        _ => &segments[..],
+    };
+
+    let mut fixtures = shared.lock().unwrap();
+    if let Some(response) = exec(&mut fixtures, shared, req, segments, body) {
+        return response.map(BodyExt::boxed_unsync);
+    }
+    if let Some(response) = images(&mut fixtures, req.method(), segments, req.uri().query()) {
+        return response.map(BodyExt::boxed_unsync);
+    }
+    if let Some(response) = volumes(&mut fixtures, req.method(), segments) {
+        return response.map(BodyExt::boxed_unsync);
+    }
+    if let Some(response) = networks(&mut fixtures, req.method(), segments, body) {
+        return response.map(BodyExt::boxed_unsync);
+    }
+    if let Some(response) = disk_usage(&mut fixtures, req.method(), segments) {
+        return response.map(BodyExt::boxed_unsync);
+    }
+    if segments == ["events"] {
+        let body = fixtures
+            .events
+            .iter()
+            .map(|event| event.to_string() + "\n")
+            .collect::<String>();
+        if fixtures.hold_events {
+            return json_lines(body).map(|body| HeldOpen(Some(body)).boxed_unsync());
+        }
+        return json_lines(body).map(BodyExt::boxed_unsync);
+    }
+    if let Some(response) = containers(&fixtures, req.method(), segments, req.uri().query()) {
+        return response.map(BodyExt::boxed_unsync);
+    }
+    if matches!(*req.method(), Method::POST | Method::DELETE) {
+        let force = req.uri().query().is_some_and(|q| q.split('&').any(|pair| pair == "force=1"));
+        return container_action(&mut fixtures, req.method(), segments, force).map(BodyExt::boxed_unsync);
+    }
+    let response = match segments {
+        ["_ping"] => text(StatusCode::OK, "OK"),
+        ["version"] => match &fixtures.version {
+            Some(version) => json_response(StatusCode::OK, version),
+            None => json_response(
+                StatusCode::OK,
+                &json!({ "Version": "24.0.7", "ApiVersion": "1.43", "MinAPIVersion": "1.12", "Os": "linux" }),
+            ),
+        },
+        ["info"] => json_response(
+            StatusCode::OK,
+            &json!({
+                "Name": "mock-host",
+                "ServerVersion": "24.0.7",
+                "OperatingSystem": "Ubuntu 22.04.3 LTS",
+                "OSType": "linux",
+                "KernelVersion": "6.5.0-14-generic",
+                "NCPU": 8,
+                "MemTotal": 16_u64 * 1024 * 1024 * 1024,
+                "Driver": "overlay2",
+            }),
+        ),
+        ["libpod", "pods", "json"] if fixtures.pods.is_some() => {
+            json_response(StatusCode::OK, &Value::Array(fixtures.pods.clone().unwrap_or_default()))
+        }
+        _ => json_response(StatusCode::NOT_FOUND, &json!({ "message": "page not found" })),
+    };
+    response.map(BodyExt::boxed_unsync)
+}
+
+/// Answer the container listing and the read-only per-container endpoints.
+fn containers(
+    fixtures: &Fixtures,
+    method: &Method,
+    segments: &[&str],
+    query: Option<&str>,
+) -> Option<Response<Full<Bytes>>> {
+    if method != Method::GET {
+        return None;
+    }
+    let response = match segments {
+        ["containers", "json"] => {
+            let ids = filter_values(query, "id");
+            let containers = fixtures
+                .containers
+                .iter()
+                .filter(|c| {
+                    let own = c["Id"].as_str().unwrap_or_default();
+                    ids.is_empty() || ids.iter().any(|id| own.starts_with(id.as_str()))
+                })
+                .cloned()
+                .collect();
+            json_response(StatusCode::OK, &Value::Array(containers))
+        }
+        ["containers", id, "json"] => match fixtures.inspect.get(*id) {
+            Some(inspect) => json_response(StatusCode::OK, inspect),
+            None => no_such_container(id),
+        },
+        // The whole script as one stream, which then ends as if the container stopped
+        ["containers", id, "stats"] => match fixtures.stats.get(*id) {
+            Some(samples) => json_lines(samples.iter().map(|sample| sample.to_string() + "\n").collect()),
+            None => no_such_container(id),
+        },
+        ["containers", id, "logs"] => match fixtures.logs.get(*id) {
+            Some(lines) if fixtures.inspect.get(*id).is_some_and(|inspect| inspect["Config"]["Tty"] == true) => {
+                let raw: String = lines.iter().map(|(_, line)| line.to_owned() + "\r\n").collect();
+                let mut response = Response::new(Full::new(Bytes::from(raw)));
+                response
+                    .headers_mut()
+                    .insert("Content-Type", "application/vnd.docker.raw-stream".parse().unwrap());
+                response
+            }
+            Some(lines) => {
+                let mut response = Response::new(Full::new(multiplexed(lines)));
+                response.headers_mut().insert(
+                    "Content-Type",
+                    "application/vnd.docker.multiplexed-stream".parse().unwrap(),
+                );
+                response
+            }
+            None => no_such_container(id),
+        },
+        ["containers", id, "top"] => match (fixtures.containers.iter().find(|c| c["Id"] == *id), fixtures.top.get(*id)) {
+            (Some(container), _) if container["State"] != "running" => json_response(
+                StatusCode::CONFLICT,
+                &json!({ "message": format!("container {id} is not running") }),
+            ),
+            (Some(_), Some(top)) => json_response(StatusCode::OK, top),
+            (Some(_), None) => json_response(StatusCode::OK, &json!({ "Titles": [], "Processes": [] })),
+            (None, _) => no_such_container(id),
+        },
+        ["containers", id, "changes"] => match fixtures.changes.get(*id) {
+            Some(changes) => json_response(
+                StatusCode::OK,
+                &changes
+                    .iter()
+                    .map(|(path, kind)| json!({ "Path": path, "Kind": kind }))
+                    .collect(),
+            ),
+            None if fixtures.inspect.contains_key(*id) => json_response(StatusCode::OK, &Value::Null),
+            None => no_such_container(id),
+        },
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// Answer the exec endpoints. Started execs run [`echo_shell`] on the
+/// hijacked connection.
+fn exec(
+    fixtures: &mut Fixtures,
+    shared: &Arc<Mutex<Fixtures>>,
+    req: &mut Request<()>,
+    segments: &[&str],
+    body: &Bytes,
+) -> Option<Response<Full<Bytes>>> {
+    let index = |id: &str| {
+        id.strip_prefix("exec-")
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|n| *n < fixtures.execs.len())
+    };
+    let response = match (req.method().clone(), segments) {
+        (Method::POST, ["containers", id, "exec"]) => match fixtures.containers.iter().find(|c| c["Id"] == *id) {
+            Some(container) if container["State"] == "running" => {
+                let config: Value = serde_json::from_slice(body).unwrap_or_default();
+                let exec_id = format!("exec-{}", fixtures.execs.len());
+                fixtures.execs.push(json!({
+                    "ID": exec_id,
+                    "ContainerID": id,
+                    "Running": false,
+                    "ExitCode": null,
+                    "Config": config,
+                }));
+                json_response(StatusCode::CREATED, &json!({ "Id": exec_id }))
+            }
+            Some(_) => json_response(
+                StatusCode::CONFLICT,
+                &json!({ "message": format!("container {id} is not running") }),
+            ),
+            None => no_such_container(id),
+        },
+        (Method::POST, ["exec", id, "start"]) => match index(id) {
+            Some(index) => {
+                fixtures.execs[index]["Running"] = json!(true);
+                tokio::spawn(echo_shell(hyper::upgrade::on(&mut *req), Arc::clone(shared), index));
+                let mut response = text(StatusCode::SWITCHING_PROTOCOLS, "");
+                response.headers_mut().insert("Connection", "Upgrade".parse().unwrap());
+                response.headers_mut().insert("Upgrade", "tcp".parse().unwrap());
+                response
+            }
+            None => no_such_exec(id),
+        },
+        (Method::POST, ["exec", id, "resize"]) => match index(id) {
+            Some(_) => text(StatusCode::CREATED, ""),
+            None => no_such_exec(id),
+        },
+        (Method::GET, ["exec", id, "json"]) => match index(id) {
+            Some(index) => json_response(StatusCode::OK, &fixtures.execs[index]),
+            None => no_such_exec(id),
+        },
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// Answer the image endpoints. Removing an image a container uses fails as
+/// it does without `force`; pruning removes the unused dangling ones, or all
+/// unused ones with the `dangling=false` filter.
+fn images(
+    fixtures: &mut Fixtures,
+    method: &Method,
+    segments: &[&str],
+    query: Option<&str>,
+) -> Option<Response<Full<Bytes>>> {
+    let in_use = |fixtures: &Fixtures, id: &str| fixtures.containers.iter().any(|c| c["ImageID"] == id);
+    let response = match (method, segments) {
+        (&Method::GET, ["images", "json"]) => json_response(StatusCode::OK, &Value::Array(fixtures.images.clone())),
+        (&Method::GET, ["images", id, "history"]) => match fixtures.history.get(*id) {
+            Some(history) => json_response(StatusCode::OK, history),
+            None => no_such_image(id),
+        },
+        (&Method::DELETE, ["images", id]) => match fixtures.images.iter().position(|i| i["Id"] == *id) {
+            Some(_) if in_use(fixtures, id) => json_response(
+                StatusCode::CONFLICT,
+                &json!({ "message": format!("conflict: unable to delete {id} (must be forced) - image is being used by a container") }),
+            ),
+            Some(index) => {
+                fixtures.images.remove(index);
+                json_response(StatusCode::OK, &json!([{ "Deleted": id }]))
+            }
+            None => no_such_image(id),
+        },
+        (&Method::POST, ["images", "prune"]) => {
+            let all = filter_values(query, "dangling") == ["false"];
+            let images = std::mem::take(&mut fixtures.images);
+            let (pruned, kept): (Vec<Value>, Vec<Value>) = images.into_iter().partition(|image| {
+                (all || image["RepoTags"] == json!(["<none>:<none>"]))
+                    && !in_use(fixtures, image["Id"].as_str().unwrap_or_default())
+            });
+            fixtures.images = kept;
+            json_response(
+                StatusCode::OK,
+                &json!({
+                    "ImagesDeleted": pruned.iter().map(|image| json!({ "Deleted": image["Id"] })).collect::<Vec<_>>(),
+                    "SpaceReclaimed": pruned.iter().map(|image| image["Size"].as_u64().unwrap_or_default()).sum::<u64>(),
+                }),
+            )
+        }
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// Containers, by ID, whose inspect data mounts the volume `name`.
+fn volume_users(fixtures: &Fixtures, name: &str) -> Vec<String> {
+    fixtures
+        .inspect
+        .iter()
+        .filter(|(_, inspect)| {
+            inspect["Mounts"]
+                .as_array()
+                .is_some_and(|mounts| mounts.iter().any(|m| m["Name"] == name))
+        })
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Answer the volume endpoints. Volumes the scripted mounts use can't be
+/// removed.
+fn volumes(fixtures: &mut Fixtures, method: &Method, segments: &[&str]) -> Option<Response<Full<Bytes>>> {
+    let response = match (method, segments) {
+        (&Method::GET, ["volumes"]) => {
+            let volumes: Vec<Value> = fixtures
+                .volumes
+                .iter()
+                .map(|volume| {
+                    let mut volume = volume.clone();
+                    volume["UsageData"] = Value::Null;
+                    volume
+                })
+                .collect();
+            json_response(StatusCode::OK, &json!({ "Volumes": volumes, "Warnings": null }))
+        }
+        (&Method::DELETE, ["volumes", name]) => match fixtures.volumes.iter().position(|v| v["Name"] == *name) {
+            Some(index) => {
+                let users = volume_users(fixtures, name);
+                if users.is_empty() {
+                    fixtures.volumes.remove(index);
+                    text(StatusCode::NO_CONTENT, "")
+                } else {
+                    json_response(
+                        StatusCode::CONFLICT,
+                        &json!({ "message": format!("remove {name}: volume is in use - {users:?}") }),
+                    )
+                }
+            }
+            None => json_response(
+                StatusCode::NOT_FOUND,
+                &json!({ "message": format!("get {name}: no such volume") }),
+            ),
+        },
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// Answer `/system/df` and the container, volume and build cache prune
+/// endpoints, counting volume references from the scripted mounts. Image
+/// sizes are reported as if no layers were shared.
+fn disk_usage(fixtures: &mut Fixtures, method: &Method, segments: &[&str]) -> Option<Response<Full<Bytes>>> {
+    let size = |value: &Value| value.as_u64().unwrap_or_default();
+    let report = |kind: &str, deleted: Vec<Value>, reclaimed: u64| {
+        json_response(StatusCode::OK, &json!({ kind: deleted, "SpaceReclaimed": reclaimed }))
+    };
+    let response = match (method, segments) {
+        (&Method::GET, ["system", "df"]) => {
+            let images: Vec<Value> = fixtures
+                .images
+                .iter()
+                .map(|image| {
+                    let mut image = image.clone();
+                    let users = fixtures.containers.iter().filter(|c| c["ImageID"] == image["Id"]).count();
+                    image["Containers"] = json!(users);
+                    image["SharedSize"] = json!(0);
+                    image
+                })
+                .collect();
+            let volumes: Vec<Value> = fixtures
+                .volumes
+                .iter()
+                .map(|volume| {
+                    let mut volume = volume.clone();
+                    let users = volume_users(fixtures, volume["Name"].as_str().unwrap_or_default());
+                    volume["UsageData"]["RefCount"] = json!(users.len());
+                    volume
+                })
+                .collect();
+            json_response(
+                StatusCode::OK,
+                &json!({
+                    "LayersSize": fixtures.images.iter().map(|image| size(&image["Size"])).sum::<u64>(),
+                    "Images": images,
+                    "Containers": fixtures.containers,
+                    "Volumes": volumes,
+                    "BuildCache": fixtures.build_cache,
+                }),
+            )
+        }
+        (&Method::POST, ["containers", "prune"]) => {
+            let containers = std::mem::take(&mut fixtures.containers);
+            let (pruned, kept): (Vec<Value>, Vec<Value>) = containers.into_iter().partition(|c| c["State"] != "running");
+            fixtures.containers = kept;
+            for container in &pruned {
+                fixtures.inspect.remove(container["Id"].as_str().unwrap_or_default());
+            }
+            report(
+                "ContainersDeleted",
+                pruned.iter().map(|c| c["Id"].clone()).collect(),
+                pruned.iter().map(|c| size(&c["SizeRw"])).sum(),
+            )
+        }
+        (&Method::POST, ["volumes", "prune"]) => {
+            let volumes = std::mem::take(&mut fixtures.volumes);
+            let (pruned, kept): (Vec<Value>, Vec<Value>) = volumes
+                .into_iter()
+                .partition(|v| volume_users(fixtures, v["Name"].as_str().unwrap_or_default()).is_empty());
+            fixtures.volumes = kept;
+            report(
+                "VolumesDeleted",
+                pruned.iter().map(|v| v["Name"].clone()).collect(),
+                pruned.iter().map(|v| size(&v["UsageData"]["Size"])).sum(),
+            )
+        }
+        (&Method::POST, ["build", "prune"]) => {
+            let cache = std::mem::take(&mut fixtures.build_cache);
+            let (pruned, kept): (Vec<Value>, Vec<Value>) = cache.into_iter().partition(|c| c["InUse"] != true);
+            fixtures.build_cache = kept;
+            report(
+                "CachesDeleted",
+                pruned.iter().map(|c| c["ID"].clone()).collect(),
+                pruned.iter().map(|c| size(&c["Size"])).sum(),
+            )
+        }
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// Containers attached to `network`, by ID, as `/networks/{id}` lists them.
+fn attached(fixtures: &Fixtures, network: &str) -> serde_json::Map<String, Value> {
+    fixtures
+        .containers
+        .iter()
+        .filter_map(|c| {
+            let endpoint = c["NetworkSettings"]["Networks"].get(network)?;
+            let container = json!({
+                "Name": c["Names"][0].as_str().unwrap_or_default().trim_start_matches('/'),
+                "IPv4Address": format!("{}/16", endpoint["IPAddress"].as_str().unwrap_or_default()),
+                "IPv6Address": "",
+                "MacAddress": endpoint["MacAddress"],
+            });
+            Some((c["Id"].as_str().unwrap_or_default().to_string(), container))
+        })
+        .collect()
+}
+
+/// Answer the network endpoints, deriving each network's containers from
+/// their `NetworkSettings`.
+fn networks(
+    fixtures: &mut Fixtures,
+    method: &Method,
+    segments: &[&str],
+    body: &Bytes,
+) -> Option<Response<Full<Bytes>>> {
+    let network = match segments {
+        ["networks", id, ..] => fixtures
+            .networks
+            .iter()
+            .find(|n| n["Id"] == *id || n["Name"] == *id)
+            .map(|n| n["Name"].as_str().unwrap_or_default().to_string()),
+        _ => None,
+    };
+    let container = || {
+        let config: Value = serde_json::from_slice(body).unwrap_or_default();
+        config["Container"].as_str().unwrap_or_default().to_string()
+    };
+    let response = match (method, segments, network) {
+        (&Method::GET, ["networks"], _) => {
+            let networks = fixtures
+                .networks
+                .iter()
+                .map(|network| {
+                    let mut network = network.clone();
+                    network["Containers"] = json!({});
+                    network
+                })
+                .collect();
+            json_response(StatusCode::OK, &Value::Array(networks))
+        }
+        (&Method::GET, ["networks", _], Some(name)) => {
+            let mut network = fixtures.networks.iter().find(|n| n["Name"] == name.as_str())?.clone();
+            network["Containers"] = Value::Object(attached(fixtures, &name));
+            json_response(StatusCode::OK, &network)
+        }
+        (&Method::POST, ["networks", _, "connect"], Some(name)) => {
+            let id = container();
+            let members = attached(fixtures, &name);
+            if !fixtures.containers.iter().any(|c| c["Id"] == id.as_str()) {
+                no_such_container(&id)
+            } else if members.contains_key(&id) {
+                json_response(
+                    StatusCode::FORBIDDEN,
+                    &json!({ "message": format!("endpoint with name {id} already exists in network {name}") }),
+                )
+            } else {
+                let gateway = fixtures.networks.iter().find(|n| n["Name"] == name.as_str())?["IPAM"]["Config"][0]
+                    ["Gateway"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                // The lowest address after the gateway's that no member holds
+                let prefix = gateway.rsplit_once('.').map_or("", |(prefix, _)| prefix);
+                let taken = |ip: &str| {
+                    members.values().any(|m| m["IPv4Address"].as_str().and_then(|a| a.split('/').next()) == Some(ip))
+                };
+                let ip = (2..255).map(|host| format!("{prefix}.{host}")).find(|ip| !taken(ip)).unwrap_or_default();
+                attach(fixtures, &id, &name, &ip);
+                text(StatusCode::OK, "")
+            }
+        }
+        (&Method::POST, ["networks", _, "disconnect"], Some(name)) => {
+            let id = container();
+            if attached(fixtures, &name).contains_key(&id) {
+                for container in fixtures.containers.iter_mut().filter(|c| c["Id"] == id.as_str()) {
+                    if let Some(networks) = container["NetworkSettings"]["Networks"].as_object_mut() {
+                        networks.remove(&name);
+                    }
+                }
+                if let Some(networks) = fixtures
+                    .inspect
+                    .get_mut(&id)
+                    .and_then(|inspect| inspect["NetworkSettings"]["Networks"].as_object_mut())
+                {
+                    networks.remove(&name);
+                }
+                text(StatusCode::OK, "")
+            } else {
+                json_response(
+                    StatusCode::FORBIDDEN,
+                    &json!({ "message": format!("container {id} is not connected to network {name}") }),
+                )
+            }
+        }
+        (_, ["networks", id, ..], None) => json_response(
+            StatusCode::NOT_FOUND,
+            &json!({ "message": format!("network {id} not found") }),
+        ),
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// A shell that echoes what is typed, prints each command back and exits
+/// on `exit [code]`.
+async fn echo_shell(on_upgrade: OnUpgrade, fixtures: Arc<Mutex<Fixtures>>, index: usize) {
+    let Ok(upgraded) = on_upgrade.await else { return };
+    let mut io = TokioIo::new(upgraded);
+    let mut line = Vec::new();
+    let mut buf = [0; 1024];
+    let mut exit_code = 0;
+    let _ = io.write_all(b"# ").await;
+    'session: loop {
+        let n = match io.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        let _ = io.write_all(&buf[..n]).await;
+        line.extend_from_slice(&buf[..n]);
+        while let Some(end) = line.iter().position(|b| *b == b'\r') {
+            let command: Vec<u8> = line.drain(..=end).collect();
+            let command = String::from_utf8_lossy(&command[..end]).into_owned();
+            if let Some(code) = command.strip_prefix("exit") {
+                exit_code = code.trim().parse().unwrap_or(0);
+                let _ = io.write_all(b"\r\n").await;
+                break 'session;
+            }
+            let _ = io.write_all(format!("\r\n{command}\r\n# ").as_bytes()).await;
+        }
+    }
+    {
+        let mut fixtures = fixtures.lock().unwrap();
+        fixtures.execs[index]["Running"] = json!(false);
+        fixtures.execs[index]["ExitCode"] = json!(exit_code);
+    }
+    let _ = io.shutdown().await;
+}
+
+/// Apply a lifecycle action to the scripted containers, answering the way
+/// the daemon does for no-ops and for removing a running container.
+fn container_action(fixtures: &mut Fixtures, method: &Method, segments: &[&str], force: bool) -> Response<Full<Bytes>> {
+    let (id, action) = match (method, segments) {
+        (&Method::DELETE, ["containers", id]) => (*id, "remove"),
+        (&Method::POST, ["containers", id, action]) => (*id, *action),
+        _ => return json_response(StatusCode::NOT_FOUND, &json!({ "message": "page not found" })),
+    };
+    let Some(index) = fixtures.containers.iter().position(|c| c["Id"] == id) else {
+        return no_such_container(id);
+    };
+    let current = fixtures.containers[index]["State"].as_str().unwrap_or_default().to_string();
+    let state = match action {
+        "start" if current == "running" => return text(StatusCode::NOT_MODIFIED, ""),
+        "stop" if current != "running" => return text(StatusCode::NOT_MODIFIED, ""),
+        "remove" if current == "running" && !force => {
+            return json_response(
+                StatusCode::CONFLICT,
+                &json!({ "message": format!("cannot remove container {id}: container is running") }),
+            );
+        }
+        "remove" => {
+            fixtures.containers.remove(index);
+            fixtures.inspect.remove(id);
+            return text(StatusCode::NO_CONTENT, "");
+        }
+        "start" | "restart" | "unpause" => "running",
+        "stop" | "kill" => "exited",
+        "pause" => "paused",
+        _ => return json_response(StatusCode::NOT_FOUND, &json!({ "message": "page not found" })),
+    };
+    fixtures.containers[index]["State"] = json!(state);
+    fixtures.containers[index]["Status"] = json!(status_text(state));
+    text(StatusCode::NO_CONTENT, "")
+}
+
+type MockBody = UnsyncBoxBody<Bytes, Infallible>;
+
+/// A body that stays open after its content, like a live `/events` stream.
+struct HeldOpen(Option<Full<Bytes>>);
+
+impl Body for HeldOpen {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        match self.0.as_mut() {
+            Some(body) => match Pin::new(body).poll_frame(cx) {
+                Poll::Ready(None) => {
+                    self.0 = None;
+                    Poll::Pending
+                }
+                other => other,
+            },
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Values of `key` in the URL-encoded `filters` query parameter.
+fn filter_values(query: Option<&str>, key: &str) -> Vec<String> {
+    let Some(filters) = query
+        .unwrap_or_default()
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("filters="))
+    else {
+        return Vec::new();
+    };
+    let decoded = percent_decode(filters);
+    serde_json::from_str::<HashMap<String, Vec<String>>>(&decoded)
+        .ok()
+        .and_then(|mut filters| filters.remove(key))
+        .unwrap_or_default()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(b) = u8::from_str_radix(&s[i + 1..i + 3], 16) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn no_such_container(id: &str) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        &json!({ "message": format!("No such container: {id}") }),
+    )
+}
+
+fn no_such_image(id: &str) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        &json!({ "message": format!("No such image: {id}") }),
+    )
+}
+
+fn no_such_exec(id: &str) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        &json!({ "message": format!("No such exec instance: {id}") }),
+    )
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(value.to_string())));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert("Content-Type", "application/json".parse().unwrap());
+    response
+}
+
+fn json_lines(body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    response
+        .headers_mut()
+        .insert("Content-Type", "application/json".parse().unwrap());
+    response
+}
+
+fn text(status: StatusCode, body: &'static str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from_static(body.as_bytes())));
+    *response.status_mut() = status;
+    response
+}
@@ -1,5 +1,19 @@
 #[cfg(test)]
+mod changes_tests;
+mod connection_tests;
+mod disk_tests;
 mod docker_client_tests;
 mod docker_models_tests;
+mod endpoint_tests;
+mod events_tests;
+mod images_tests;
+mod inspect_tests;
+mod log_view_tests;
+mod logs_tests;
+mod mock_daemon;
+mod shell_tests;
+mod stats_tests;
 mod utils_tests;
+mod version_tests;
+mod volumes_tests;
 mod app_tests; 
\ No newline at end of file
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use ratui_lib::ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use serde_json::Value;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use crate::docker::connection::Hijacked;
+    use crate::error::WhaleError;
+    use crate::tests::mock_daemon::{Fixtures, MockDaemon};
+    use crate::tui::shell::{SHELL_COMMAND, key_bytes};
+
+    /// Read from the shell until its output ends with `expected`.
+    async fn read_until(stream: &mut Hijacked, output: &mut String, expected: &str) {
+        let mut buf = [0; 256];
+        while !output.ends_with(expected) {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(n > 0, "stream ended before {expected:?}, got {output:?}");
+            output.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exec_session() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111", "web", "running")
+                .container("bbb222", "worker", "exited"),
+        );
+        let client = daemon.client();
+
+        let exec_id = client
+            .create_exec("aaa111", &SHELL_COMMAND, &["TERM=xterm".to_string()])
+            .await
+            .unwrap();
+        let mut stream = client.start_exec(&exec_id).await.unwrap();
+        client.resize_exec(&exec_id, 120, 40).await.unwrap();
+
+        let mut output = String::new();
+        read_until(&mut stream, &mut output, "# ").await;
+        stream.write_all(b"ls\r").await.unwrap();
+        read_until(&mut stream, &mut output, "ls\r\n# ").await;
+        stream.write_all(b"exit 3\r").await.unwrap();
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(client.inspect_exec(&exec_id).await.unwrap().exit_code, Some(3));
+
+        let mut exec = Value::Null;
+        daemon.update(|fixtures| exec = fixtures.execs[0].clone());
+        assert_eq!(exec["Config"]["Cmd"][0], "/bin/sh");
+        assert_eq!(exec["Config"]["Tty"], true);
+        assert_eq!(exec["Config"]["Env"][0], "TERM=xterm");
+        assert!(daemon.requests().contains(&format!("POST /v1.52/exec/{exec_id}/resize?h=40&w=120")));
+
+        assert!(matches!(
+            client.create_exec("bbb222", &SHELL_COMMAND, &[]).await,
+            Err(WhaleError::Api { status: 409, .. })
+        ));
+    }
+
+    #[test]
+    fn test_key_bytes() {
+        let key = |code, modifiers| key_bytes(KeyEvent::new(code, modifiers));
+        assert_eq!(key(KeyCode::Char('a'), KeyModifiers::NONE), Some(b"a".to_vec()));
+        assert_eq!(key(KeyCode::Char('é'), KeyModifiers::NONE), Some("é".as_bytes().to_vec()));
+        assert_eq!(key(KeyCode::Char('c'), KeyModifiers::CONTROL), Some(vec![3]));
+        assert_eq!(key(KeyCode::Char('D'), KeyModifiers::CONTROL), Some(vec![4]));
+        assert_eq!(key(KeyCode::Char('b'), KeyModifiers::ALT), Some(b"\x1bb".to_vec()));
+        assert_eq!(key(KeyCode::Enter, KeyModifiers::NONE), Some(b"\r".to_vec()));
+        assert_eq!(key(KeyCode::Backspace, KeyModifiers::NONE), Some(vec![0x7f]));
+        assert_eq!(key(KeyCode::Up, KeyModifiers::NONE), Some(b"\x1b[A".to_vec()));
+        assert_eq!(key(KeyCode::F(5), KeyModifiers::NONE), Some(b"\x1b[15~".to_vec()));
+        assert_eq!(key(KeyCode::F(12), KeyModifiers::NONE), Some(b"\x1b[24~".to_vec()));
+
+        let mut release = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(key_bytes(release), None);
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::{mpsc, watch};
+
+    use crate::docker::stats::stream_stats;
+    use crate::tests::mock_daemon::{Fixtures, MockDaemon, stats_sample};
+
+    #[tokio::test]
+    async fn test_stream_stats_follows_running_set() {
+        let daemon = MockDaemon::start(
+            Fixtures::default()
+                .container("aaa111", "web", "running")
+                .container("bbb222", "worker", "running")
+                .stats("aaa111", vec![stats_sample((2_000, 1_000), (20_000, 10_000), 2, (256, 1_024))])
+                .stats("bbb222", vec![stats_sample((4_000, 2_000), (30_000, 20_000), 2, (512, 1_024))]),
+        );
+        let (running_tx, running_rx) = watch::channel(vec!["aaa111".to_string()]);
+        let (samples_tx, mut samples_rx) = mpsc::unbounded_channel();
+        let collector = tokio::spawn(stream_stats(daemon.client(), running_rx, move |id, stats| {
+            let _ = samples_tx.send((id.to_string(), stats.cpu_percent()));
+        }));
+
+        let timeout = Duration::from_secs(5);
+        let first = tokio::time::timeout(timeout, samples_rx.recv()).await.unwrap().unwrap();
+        assert_eq!(first, ("aaa111".to_string(), Some(20.0)));
+
+        // A container that starts running gets its own stream
+        running_tx.send_replace(vec!["bbb222".to_string()]);
+        let next = loop {
+            let sample = tokio::time::timeout(timeout, samples_rx.recv()).await.unwrap().unwrap();
+            if sample.0 == "bbb222" {
+                break sample;
+            }
+        };
+        assert_eq!(next.1, Some(40.0));
+
+        drop(running_tx);
+        tokio::time::timeout(timeout, collector).await.unwrap().unwrap();
+        assert!(
+            daemon
+                .requests()
+                .iter()
+                .any(|r| r.ends_with("/containers/bbb222/stats?stream=true"))
+        );
+    }
+}
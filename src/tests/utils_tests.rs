@@ -1,6 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use cetacea::utils::format_duration;
+    use std::time::Duration;
+
+    use cetacea::utils::{
+        base64_encode, format_bytes, format_duration, format_interval, parse_since, parse_tail, truncate,
+    };
 
     #[test]
     fn test_format_duration() {
@@ -8,7 +12,8 @@ mod tests {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
-            .as_secs() as i64;
+            .as_secs();
+        let now = i64::try_from(now).unwrap();
 
         assert_eq!(format_duration(now), "0 seconds ago");
         assert_eq!(format_duration(now - 30), "30 seconds ago");
@@ -16,4 +21,58 @@ mod tests {
         assert_eq!(format_duration(now - 3600), "1 hours ago");
         assert_eq!(format_duration(now - 86400), "1 days ago");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_format_interval() {
+        assert_eq!(format_interval(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_interval(Duration::from_secs(1)), "1s");
+        assert_eq!(format_interval(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_interval(Duration::from_secs(120)), "2m");
+        assert_eq!(format_interval(Duration::from_secs(90)), "90s");
+    }
+
+    #[test]
+    fn test_parse_since_and_tail() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let now = i64::try_from(now).unwrap();
+
+        assert_eq!(parse_since("1700000000"), Ok(1_700_000_000));
+        assert!((parse_since("10m").unwrap() - (now - 600)).abs() <= 1);
+        assert!((parse_since("2h").unwrap() - (now - 7200)).abs() <= 1);
+        assert!(parse_since("10x").is_err());
+        assert!(parse_since("m").is_err());
+
+        assert_eq!(parse_tail("all"), Ok(None));
+        assert_eq!(parse_tail("100"), Ok(Some(100)));
+        assert!(parse_tail("-1").is_err());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(512 * 1024 * 1024), "512.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("ssh://admin@build-01", 30), "ssh://admin@build-01");
+        assert_eq!(truncate("ssh://admin@build-01", 10), "ssh://adm…");
+        assert_eq!(truncate("ssh://admin@build-01", 1), "…");
+        assert_eq!(truncate("ssh://admin@build-01", 0), "");
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"nginx:latest"), "bmdpbng6bGF0ZXN0");
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use crate::docker::models::VersionInfo;
+    use crate::docker::version::{ApiVersion, MAX_API_VERSION, negotiate};
+
+    fn server(api_version: &str, min_api_version: Option<&str>) -> VersionInfo {
+        VersionInfo {
+            api_version: api_version.to_string(),
+            min_api_version: min_api_version.map(str::to_string),
+            ..VersionInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_and_order_api_versions() {
+        let v140: ApiVersion = "1.40".parse().unwrap();
+        let v19: ApiVersion = "1.9".parse().unwrap();
+        assert_eq!(v140, ApiVersion::new(1, 40));
+        assert!(v19 < v140);
+        assert_eq!(v140.to_string(), "1.40");
+        assert!("latest".parse::<ApiVersion>().is_err());
+        assert!("1.x".parse::<ApiVersion>().is_err());
+    }
+
+    #[test]
+    fn test_negotiate() {
+        // Daemon older than the client's maximum: use the daemon's version
+        assert_eq!(negotiate(&server("1.47", Some("1.24"))).unwrap(), ApiVersion::new(1, 47));
+        // Newer daemon: stay on the client's maximum
+        assert_eq!(negotiate(&server("1.60", Some("1.24"))).unwrap(), MAX_API_VERSION);
+        // Older daemon (or Podman's compat API): use the daemon's version
+        assert_eq!(negotiate(&server("1.41", Some("1.12"))).unwrap(), ApiVersion::new(1, 41));
+        // Daemons too old to talk to
+        assert!(negotiate(&server("1.12", None)).is_err());
+        assert!(negotiate(&server("", None)).is_err());
+        // Daemons that no longer accept any version the client speaks
+        assert!(negotiate(&server("1.60", Some("1.55"))).is_err());
+    }
+
+    #[test]
+    fn test_negotiate_with_current_engine() {
+        // Docker Engine 29 dropped everything before API 1.44
+        assert_eq!(negotiate(&server("1.52", Some("1.44"))).unwrap(), MAX_API_VERSION);
+        assert_eq!(negotiate(&server("1.44", Some("1.44"))).unwrap(), ApiVersion::new(1, 44));
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::docker::models::{Volume, VolumeUsage};
+    use crate::tui::volumes::volume_rows;
+
+    fn volume(name: &str, usage: Option<(i64, i64)>) -> Volume {
+        Volume {
+            name: name.to_string(),
+            driver: "local".to_string(),
+            usage_data: usage.map(|(size, ref_count)| VolumeUsage { size, ref_count }),
+            ..Volume::default()
+        }
+    }
+
+    #[test]
+    fn test_volume_rows() {
+        let volumes = vec![volume("cache", None), volume("db", None), volume("tmp", None)];
+        let usage = vec![volume("db", Some((1024, 1))), volume("cache", Some((-1, -1))), volume("tmp", Some((0, 0)))];
+        let mounts = HashMap::from([("db".to_string(), vec!["postgres".to_string()])]);
+
+        let rows = volume_rows(volumes, &usage, &mounts);
+        let names: Vec<&str> = rows.iter().map(|r| r.volume.name.as_str()).collect();
+        assert_eq!(names, vec!["cache", "db", "tmp"]);
+        // The driver couldn't tell
+        assert_eq!((rows[0].size, rows[0].ref_count), (None, None));
+        assert_eq!((rows[1].size, rows[1].ref_count), (Some(1024), Some(1)));
+        assert_eq!(rows[1].containers, vec!["postgres".to_string()]);
+
+        assert!(rows[0].is_orphaned());
+        assert!(!rows[1].is_orphaned());
+        assert!(rows[2].is_orphaned());
+    }
+
+    #[test]
+    fn test_referenced_volume_is_not_orphaned() {
+        // Referenced by a container we didn't inspect, e.g. one created since
+        let rows = volume_rows(vec![volume("new", None)], &[volume("new", Some((0, 1)))], &HashMap::new());
+        assert!(!rows[0].is_orphaned());
+    }
+}
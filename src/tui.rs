@@ -1,37 +1,284 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 use std::{
-    collections::HashMap,
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    ops::{ControlFlow, Range},
     sync::{mpsc, Arc, Mutex},
-    thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+use log::warn;
 use ratui_lib::{
     ResponsiveGrid, TerminalApp,
     ratatui::{
-        Frame,
-        layout::{Constraint, Direction, Layout, Rect},
-        style::{Color, Style},
+        Frame, Terminal,
+        backend::Backend,
+        crossterm::event,
+        layout::{Alignment, Constraint, Direction, Layout, Rect},
+        style::{Color, Modifier, Style},
         symbols,
         text::{Line, Span},
-        widgets::{Axis, Chart, Dataset, GraphType},
+        widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph},
     },
-    restore_terminal, run_app, setup_terminal,
+    restore_terminal, setup_terminal,
     widgets::{Card, StatusColor, StatusIndicator},
-    Widget, Error as RatuiError, Event, KeyCode,
+    Widget, Event, KeyCode,
 };
+use tokio::{sync::watch, task::JoinHandle};
 
-use crate::docker::{Container, DockerClient, Port};
-use crate::utils::format_duration;
+use crate::docker::{
+    Connection, Container, ContainerAction, ContainerStats, LogOptions, Port,
+    events::watch_containers,
+    models::{CpuStats, NetworkSettings, SystemInfo},
+    stats::stream_stats,
+};
+use crate::utils::{format_bytes, format_duration, format_interval, truncate};
+
+pub(crate) mod changes;
+pub(crate) mod disk;
+pub(crate) mod images;
+pub(crate) mod inspect;
+pub(crate) mod logs;
+pub(crate) mod networks;
+pub(crate) mod shell;
+pub(crate) mod top;
+pub(crate) mod tree;
+pub(crate) mod volumes;
+
+use changes::ChangesView;
+use disk::DiskView;
+use images::ImagesView;
+use inspect::InspectView;
+use logs::LogView;
+use networks::NetworksView;
+use top::TopView;
+use volumes::VolumesView;
 
 const HISTORY_SIZE: usize = 60; // 1 minute of history at 1s intervals
+/// Narrowest card worth laying out side by side.
+const MIN_CARD_WIDTH: u16 = 40;
+/// Card heights, matching the layout in `render_container`.
+const CARD_HEIGHT: u16 = 8;
+const CARD_WITH_GRAPH_HEIGHT: u16 = 22;
+/// How long action results stay in the status bar.
+const TOAST_DURATION: Duration = Duration::from_secs(4);
 
 #[derive(Default, Clone)]
-struct ResourceHistory {
-    cpu_usage: Vec<(f64, f64)>,    // (timestamp, percentage)
-    mem_usage: Vec<(f64, f64)>,    // (timestamp, percentage)
-    gpu_usage: Vec<(f64, f64)>,    // (timestamp, percentage)
+pub struct ResourceHistory {
+    pub(crate) cpu_usage: Vec<(f64, f64)>,    // (timestamp, percentage)
+    pub(crate) mem_usage: Vec<(f64, f64)>,    // (timestamp, percentage)
+    pub(crate) gpu_usage: Vec<(f64, f64)>,    // (timestamp, percentage)
+    /// Bytes of memory in use at the latest sample.
+    pub(crate) memory: Option<u64>,
+}
+
+impl ResourceHistory {
+    fn record(&mut self, now: f64, stats: &ContainerStats) {
+        // Update CPU usage
+        if let Some(cpu_percent) = stats.cpu_percent() {
+            push_sample(&mut self.cpu_usage, (now, cpu_percent));
+        }
+
+        // Update memory usage
+        if let Some(mem_percent) = stats.memory_percent() {
+            push_sample(&mut self.mem_usage, (now, mem_percent));
+        }
+        if let Some(usage) = stats.memory_stats.usage {
+            self.memory = Some(usage);
+        }
+
+        // Update GPU usage if available
+        if let Some(gpu) = stats.gpu_stats.as_ref().and_then(|g| g.devices.first()) {
+            // Use first GPU for now
+            push_sample(&mut self.gpu_usage, (now, f64::from(gpu.utilization)));
+        }
+    }
+}
+
+fn push_sample(samples: &mut Vec<(f64, f64)>, sample: (f64, f64)) {
+    samples.push(sample);
+    if samples.len() > HISTORY_SIZE {
+        samples.remove(0);
+    }
+}
+
+/// Container counts by state and the resources the running ones use, for
+/// the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ContainerSummary {
+    pub running: usize,
+    pub paused: usize,
+    /// Every container that is neither running nor paused.
+    pub exited: usize,
+    pub unhealthy: usize,
+    /// Sum of the latest CPU samples, where 100% is one core.
+    pub cpu_percent: f64,
+    pub memory: u64,
+}
+
+impl ContainerSummary {
+    pub(crate) fn new(containers: &[Container], histories: &HashMap<String, ResourceHistory>) -> Self {
+        let mut summary = Self::default();
+        for container in containers {
+            match container.state.as_str() {
+                "running" => {
+                    summary.running += 1;
+                    if let Some(history) = histories.get(&container.id) {
+                        summary.cpu_percent += history.cpu_usage.last().map_or(0.0, |&(_, cpu)| cpu);
+                        summary.memory += history.memory.unwrap_or(0);
+                    }
+                }
+                "paused" => summary.paused += 1,
+                _ => summary.exited += 1,
+            }
+            if container.health.as_ref().is_some_and(|health| health.status == "unhealthy") {
+                summary.unhealthy += 1;
+            }
+        }
+        summary
+    }
+}
+
+fn running_ids(containers: &[Container]) -> Vec<String> {
+    containers
+        .iter()
+        .filter(|c| c.state == "running")
+        .map(|c| c.id.clone())
+        .collect()
+}
+
+/// How often the daemon streams stats samples for a container.
+const STATS_CADENCE: Duration = Duration::from_secs(1);
+
+/// How often each part of the app refreshes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Intervals {
+    /// Full container re-list, as a safety net on top of the event stream.
+    pub list: Duration,
+    /// Minimum time between recorded stats samples for a container. The
+    /// daemon streams about one sample a second, so shorter has no effect.
+    pub stats: Duration,
+    /// UI redraw tick.
+    pub render: Duration,
+}
+
+impl Default for Intervals {
+    fn default() -> Self {
+        Self {
+            list: Duration::from_secs(30),
+            stats: Duration::from_secs(1),
+            render: Duration::from_millis(250),
+        }
+    }
+}
+
+/// The interval adjusted by the `+`/`-` keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalKind {
+    List,
+    Stats,
+    Render,
+}
+
+impl IntervalKind {
+    fn next(self) -> Self {
+        match self {
+            Self::List => Self::Stats,
+            Self::Stats => Self::Render,
+            Self::Render => Self::List,
+        }
+    }
+
+    fn bounds(self) -> (Duration, Duration) {
+        match self {
+            Self::List => (Duration::from_secs(5), Duration::from_secs(600)),
+            Self::Stats => (Duration::from_secs(1), Duration::from_secs(60)),
+            Self::Render => (Duration::from_millis(50), Duration::from_secs(5)),
+        }
+    }
+}
+
+impl Intervals {
+    #[must_use]
+    pub fn get(&self, kind: IntervalKind) -> Duration {
+        match kind {
+            IntervalKind::List => self.list,
+            IntervalKind::Stats => self.stats,
+            IntervalKind::Render => self.render,
+        }
+    }
+
+    /// Double (`slower`) or halve an interval, within sensible bounds.
+    pub fn adjust(&mut self, kind: IntervalKind, slower: bool) {
+        let (min, max) = kind.bounds();
+        let current = self.get(kind);
+        let next = if slower { current * 2 } else { current / 2 }.clamp(min, max);
+        match kind {
+            IntervalKind::List => self.list = next,
+            IntervalKind::Stats => self.stats = next,
+            IntervalKind::Render => self.render = next,
+        }
+    }
+
+    /// Whether to record a stats sample arriving `since_last` after the last
+    /// recorded one. Samples arrive a little early or late, so a tenth of the
+    /// interval is allowed as slack, and at the daemon's own cadence every
+    /// sample is kept.
+    #[must_use]
+    pub fn stats_due(&self, since_last: Duration) -> bool {
+        self.stats <= STATS_CADENCE || since_last + self.stats / 10 >= self.stats
+    }
+
+    /// The same intervals, each brought within the bounds `adjust` keeps to.
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        let clamp = |kind: IntervalKind| {
+            let (min, max) = kind.bounds();
+            self.get(kind).clamp(min, max)
+        };
+        Self {
+            list: clamp(IntervalKind::List),
+            stats: clamp(IntervalKind::Stats),
+            render: clamp(IntervalKind::Render),
+        }
+    }
+}
+
+/// Top-level screens, switched with the number keys. Screens other than the
+/// container grid load when they are switched to.
+enum Screen {
+    Containers,
+    Images(ImagesView),
+    Volumes(VolumesView),
+    Networks(NetworksView),
+    Disk(DiskView),
+}
+
+impl Screen {
+    /// Titles in number key order.
+    const TITLES: [&'static str; 5] = ["Containers", "Images", "Volumes", "Networks", "Disk usage"];
+
+    fn index(&self) -> usize {
+        match self {
+            Self::Containers => 0,
+            Self::Images(_) => 1,
+            Self::Volumes(_) => 2,
+            Self::Networks(_) => 3,
+            Self::Disk(_) => 4,
+        }
+    }
+
+    /// Whether a dialog on the screen should get the number keys.
+    fn is_confirming(&self) -> bool {
+        match self {
+            Self::Containers => false,
+            Self::Images(images) => images.is_confirming(),
+            Self::Volumes(volumes) => volumes.is_confirming(),
+            Self::Networks(networks) => networks.is_confirming(),
+            Self::Disk(disk) => disk.is_confirming(),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -45,17 +292,104 @@ enum ResourceView {
 pub struct App {
     pub containers: Vec<Container>,
     pub should_quit: bool,
-    pub refresh_rate: Duration,
-    client: DockerClient,
+    client: Arc<dyn Connection>,
+    screen: Screen,
+    intervals: watch::Sender<Intervals>,
+    selected_interval: IntervalKind,
     resource_histories: Arc<Mutex<HashMap<String, ResourceHistory>>>,
     current_view: ResourceView,
     show_graphs: bool,
     rx: mpsc::Receiver<Vec<Container>>,
+    /// Daemon host details for the header, once `/info` has answered.
+    info: Option<SystemInfo>,
+    info_rx: mpsc::Receiver<SystemInfo>,
+    tasks: Vec<JoinHandle<()>>,
+    /// ID of the focused container, kept across refreshes.
+    selected: Option<String>,
+    /// First grid row on screen, and the grid shape from the last render.
+    scroll_row: Cell<usize>,
+    grid: Cell<GridShape>,
+    pending_action: Option<PendingAction>,
+    toast: Option<Toast>,
+    toast_tx: mpsc::Sender<Toast>,
+    toast_rx: mpsc::Receiver<Toast>,
+    /// IDs of the containers marked for a merged log pane.
+    marked: HashSet<String>,
+    /// Log pane, shown instead of the grid.
+    logs: Option<LogView>,
+    log_options: LogOptions,
+    /// Inspect view, shown instead of the grid.
+    inspect: Option<InspectView>,
+    /// Process table, shown instead of the grid.
+    processes: Option<TopView>,
+    /// Filesystem changes, shown instead of the grid.
+    changes: Option<ChangesView>,
+    /// `(id, name)` of the container to open a shell in once the event loop
+    /// has handed back the terminal.
+    shell_request: Option<(String, String)>,
+}
+
+/// A destructive action waiting for the user to confirm it.
+#[derive(Debug, Clone)]
+struct PendingAction {
+    action: ContainerAction,
+    id: String,
+    name: String,
+}
+
+/// A short-lived result message shown in the status bar.
+#[derive(Debug, Clone)]
+struct Toast {
+    message: String,
+    error: bool,
+    shown_at: Instant,
+}
+
+/// How a card is drawn besides its container.
+#[derive(Debug, Clone, Copy, Default)]
+struct CardState {
+    focused: bool,
+    /// Marked for a merged log pane.
+    marked: bool,
+}
+
+impl CardState {
+    fn prefix(self) -> &'static str {
+        match (self.focused, self.marked) {
+            (true, true) => "▶ ● ",
+            (true, false) => "▶ ",
+            (false, true) => "● ",
+            (false, false) => "",
+        }
+    }
+}
+
+/// Columns and visible rows of the container grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GridShape {
+    columns: usize,
+    rows: usize,
+}
+
+impl Default for GridShape {
+    fn default() -> Self {
+        Self { columns: 1, rows: 1 }
+    }
 }
 
 impl App {
-    pub fn new(mut containers: Vec<Container>, client: DockerClient) -> Self {
+    /// Create the app with the default intervals; see [`App::with_intervals`].
+    #[cfg(test)]
+    pub fn new(containers: Vec<Container>, client: Arc<dyn Connection>) -> Self {
+        Self::with_intervals(containers, client, Intervals::default())
+    }
+
+    /// Create the app and spawn its background tasks onto the current tokio
+    /// runtime; they stop on quit or when the app is dropped.
+    pub fn with_intervals(mut containers: Vec<Container>, client: Arc<dyn Connection>, intervals: Intervals) -> Self {
         let (tx, rx) = mpsc::channel();
+        let (toast_tx, toast_rx) = mpsc::channel();
+        let (intervals, _) = watch::channel(intervals);
         let resource_histories = Arc::new(Mutex::new(
             containers
                 .iter()
@@ -63,102 +397,71 @@ impl App {
                 .collect::<HashMap<String, ResourceHistory>>(),
         ));
 
-        // Sort containers: running first, then by name
-        containers.sort_by(|a, b| {
-            let a_running = a.state == "running";
-            let b_running = b.state == "running";
-            match (a_running, b_running) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => {
-                    let a_name = a.names.first().map_or("", |s| s.as_str());
-                    let b_name = b.names.first().map_or("", |s| s.as_str());
-                    a_name.cmp(b_name)
-                },
-            }
-        });
+        sort_containers(&mut containers);
+        let selected = containers.first().map(|c| c.id.clone());
 
-        // Spawn container update thread
-        let update_client = client.clone();
-        thread::spawn(move || {
-            loop {
-                thread::sleep(Duration::from_secs(1));
-                if let Ok(mut containers) = update_client.list_containers_blocking() {
-                    containers.sort_by(|a, b| {
-                        let a_running = a.state == "running";
-                        let b_running = b.state == "running";
-                        match (a_running, b_running) {
-                            (true, false) => std::cmp::Ordering::Less,
-                            (false, true) => std::cmp::Ordering::Greater,
-                            _ => {
-                                let a_name = a.names.first().map_or("", |s| s.as_str());
-                                let b_name = b.names.first().map_or("", |s| s.as_str());
-                                a_name.cmp(b_name)
-                            },
-                        }
-                    });
-                    if tx.send(containers).is_err() {
-                        break;
-                    }
+        // Containers whose stats should be streamed, kept current by the update task
+        let (running_tx, running_rx) = watch::channel(running_ids(&containers));
+
+        // Container list updates, driven by the daemon's event stream
+        let update_client = Arc::clone(&client);
+        let list_interval = intervals.subscribe();
+        let updates = tokio::spawn(async move {
+            watch_containers(update_client.as_ref(), || list_interval.borrow().list, |containers| {
+                let mut containers = containers.to_vec();
+                sort_containers(&mut containers);
+                running_tx.send_if_modified(|ids| {
+                    let running = running_ids(&containers);
+                    let changed = *ids != running;
+                    *ids = running;
+                    changed
+                });
+                if tx.send(containers).is_err() {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
                 }
-            }
+            })
+            .await;
         });
 
-        // Spawn stats update thread
-        let stats_client = client.clone();
+        // Stats collection, with one stats stream per running container
         let stats_histories = Arc::clone(&resource_histories);
-        thread::spawn(move || {
-            loop {
-                thread::sleep(Duration::from_secs(1));
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs_f64();
-
-                if let Ok(mut histories) = stats_histories.lock() {
-                    for (id, history) in histories.iter_mut() {
-                        if let Ok(stats) = stats_client.get_container_stats_blocking(id) {
-                            // Update CPU usage
-                            if let (Some(system_cpu), Some(online_cpus)) = (
-                                stats.cpu_stats.system_cpu_usage,
-                                stats.cpu_stats.online_cpus,
-                            ) {
-                                let cpu_delta = stats.cpu_stats.cpu_usage.total_usage as f64
-                                    - stats.precpu_stats.cpu_usage.total_usage as f64;
-                                let system_delta = system_cpu as f64
-                                    - stats.precpu_stats.system_cpu_usage.unwrap_or(0) as f64;
-                                if system_delta > 0.0 {
-                                    let cpu_percent = (cpu_delta / system_delta) * 100.0 * online_cpus as f64;
-                                    history.cpu_usage.push((now, cpu_percent));
-                                    if history.cpu_usage.len() > HISTORY_SIZE {
-                                        history.cpu_usage.remove(0);
-                                    }
-                                }
-                            }
-
-                            // Update memory usage
-                            if let (Some(usage), Some(limit)) = (stats.memory_stats.usage, stats.memory_stats.limit) {
-                                let mem_percent = (usage as f64 / limit as f64) * 100.0;
-                                history.mem_usage.push((now, mem_percent));
-                                if history.mem_usage.len() > HISTORY_SIZE {
-                                    history.mem_usage.remove(0);
-                                }
-                            }
-
-                            // Update GPU usage if available
-                            if let Some(gpu_stats) = stats.gpu_stats {
-                                if !gpu_stats.devices.is_empty() {
-                                    let gpu = &gpu_stats.devices[0]; // Use first GPU for now
-                                    let gpu_percent = gpu.utilization as f64;
-                                    history.gpu_usage.push((now, gpu_percent));
-                                    if history.gpu_usage.len() > HISTORY_SIZE {
-                                        history.gpu_usage.remove(0);
-                                    }
-                                }
-                            }
-                        }
-                    }
+        let stats_interval = intervals.subscribe();
+        // Last CPU sample per container, for daemons that leave precpu_stats empty
+        let mut previous: HashMap<String, CpuStats> = HashMap::new();
+        let mut recorded_at: HashMap<String, Instant> = HashMap::new();
+        let stats = tokio::spawn(stream_stats(Arc::clone(&client), running_rx, move |id, mut stats| {
+            stats.fill_missing_precpu(previous.get(id));
+            previous.insert(id.to_string(), stats.cpu_stats.clone());
+
+            // Drop samples arriving faster than the stats interval
+            let current = *stats_interval.borrow();
+            if recorded_at.get(id).is_some_and(|at| !current.stats_due(at.elapsed())) {
+                return;
+            }
+            recorded_at.insert(id.to_string(), Instant::now());
+
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs_f64();
+            if let Ok(mut histories) = stats_histories.lock() {
+                if let Some(history) = histories.get_mut(id) {
+                    history.record(now, &stats);
+                }
+            }
+        }));
+
+        // Daemon host details for the header; they don't change while running
+        let (info_tx, info_rx) = mpsc::channel();
+        let info_client = Arc::clone(&client);
+        let info = tokio::spawn(async move {
+            match info_client.info().await {
+                Ok(info) => {
+                    let _ = info_tx.send(info);
                 }
+                Err(e) => warn!("Failed to read daemon info: {e}"),
             }
         });
 
@@ -166,18 +469,353 @@ impl App {
             containers,
             client,
             should_quit: false,
-            refresh_rate: Duration::from_millis(250),
+            screen: Screen::Containers,
+            intervals,
+            selected_interval: IntervalKind::Render,
             resource_histories,
             current_view: ResourceView::Cpu,
             show_graphs: true,
             rx,
+            info: None,
+            info_rx,
+            tasks: vec![updates, stats, info],
+            selected,
+            scroll_row: Cell::new(0),
+            grid: Cell::new(GridShape::default()),
+            pending_action: None,
+            toast: None,
+            toast_tx,
+            toast_rx,
+            marked: HashSet::new(),
+            logs: None,
+            log_options: LogOptions::default(),
+            inspect: None,
+            processes: None,
+            changes: None,
+            shell_request: None,
         }
     }
 
+    /// Where the log pane starts in each container's output.
+    #[must_use]
+    pub fn with_log_options(mut self, options: LogOptions) -> Self {
+        self.log_options = options;
+        self
+    }
+
+    /// Open the log pane for the marked containers, merged, or else for
+    /// the focused one.
+    fn open_logs(&mut self) {
+        let containers: Vec<&Container> = if self.marked.is_empty() {
+            self.selected().into_iter().collect()
+        } else {
+            self.containers.iter().filter(|c| self.marked.contains(&c.id)).collect()
+        };
+        if containers.is_empty() {
+            return;
+        }
+        let sources = containers.iter().map(|c| (c.id.clone(), display_name(c))).collect();
+        self.logs = Some(LogView::open(Arc::clone(&self.client), sources, self.log_options.clone()));
+        self.marked.clear();
+    }
+
+    /// Open a merged log pane for the containers with the given names or
+    /// ID prefixes, as for `--logs`.
+    ///
+    /// # Errors
+    ///
+    /// Names the first container that doesn't match.
+    pub fn open_logs_of(&mut self, names: &[String]) -> Result<(), String> {
+        let mut sources = Vec::new();
+        for name in names {
+            let container = self
+                .containers
+                .iter()
+                .find(|c| c.names.iter().any(|n| n.trim_start_matches('/') == name))
+                .or_else(|| self.containers.iter().find(|c| c.id.starts_with(name.as_str())))
+                .ok_or_else(|| format!("No such container: {name}"))?;
+            sources.push((container.id.clone(), display_name(container)));
+        }
+        self.logs = Some(LogView::open(Arc::clone(&self.client), sources, self.log_options.clone()));
+        Ok(())
+    }
+
+    /// Open the inspect view of the focused container.
+    fn open_inspect(&mut self) {
+        if let Some(container) = self.selected() {
+            let view = InspectView::open(Arc::clone(&self.client), container.id.clone(), display_name(container));
+            self.inspect = Some(view);
+        }
+    }
+
+    /// Open the process table of the focused container.
+    fn open_processes(&mut self) {
+        if let Some(container) = self.selected() {
+            let view = TopView::open(
+                Arc::clone(&self.client),
+                container.id.clone(),
+                display_name(container),
+                self.intervals.subscribe(),
+            );
+            self.processes = Some(view);
+        }
+    }
+
+    /// Open the filesystem changes of the focused container.
+    fn open_changes(&mut self) {
+        if let Some(container) = self.selected() {
+            let view = ChangesView::open(Arc::clone(&self.client), container.id.clone(), display_name(container));
+            self.changes = Some(view);
+        }
+    }
+
+    /// Switch to the screen with the given number key, reloading it.
+    fn switch_screen(&mut self, index: usize) {
+        self.screen = match index {
+            1 => Screen::Images(ImagesView::open(Arc::clone(&self.client), &self.containers)),
+            2 => Screen::Volumes(VolumesView::open(Arc::clone(&self.client), &self.containers)),
+            3 => {
+                let container = self.selected().map(|c| (c.id.clone(), display_name(c)));
+                Screen::Networks(NetworksView::open(Arc::clone(&self.client), container))
+            }
+            4 => Screen::Disk(DiskView::open(Arc::clone(&self.client))),
+            _ => Screen::Containers,
+        };
+    }
+
+    /// Mark or unmark the focused container for a merged log pane.
+    fn toggle_mark(&mut self) {
+        if let Some(id) = self.selected.clone() {
+            if !self.marked.remove(&id) {
+                self.marked.insert(id);
+            }
+        }
+    }
+
+    /// Ask for a shell in the focused container; it has to be running.
+    fn request_shell(&mut self) {
+        let Some(container) = self.selected() else {
+            return;
+        };
+        let name = display_name(container);
+        if container.state == "running" {
+            self.shell_request = Some((container.id.clone(), name));
+        } else {
+            self.toast = Some(Toast {
+                message: format!("Cannot open a shell: {name} is not running"),
+                error: true,
+                shown_at: Instant::now(),
+            });
+        }
+    }
+
+    /// Run a shell in a container on the plain terminal until it exits,
+    /// then report how it ended in a toast.
+    fn run_shell(&mut self, id: &str, name: &str) {
+        let client = Arc::clone(&self.client);
+        let result = tokio::runtime::Handle::current().block_on(shell::run_shell(client, id, name));
+        let (message, error) = match result {
+            Ok(Some(code)) if code != 0 => (format!("Shell in {name} exited with code {code}"), false),
+            Ok(_) => (format!("Shell in {name} closed"), false),
+            Err(e) => (format!("Failed to open a shell in {name}: {e}"), true),
+        };
+        self.toast = Some(Toast {
+            message,
+            error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Run `action` on the focused container, asking first when it's
+    /// destructive.
+    /// Pass the key to the open pane or screen, closing it when it asks to.
+    /// False when the container grid should handle the key.
+    fn handle_view_key(&mut self, code: KeyCode) -> bool {
+        if let Some(changes) = &mut self.changes {
+            if changes.handle_key(code) {
+                self.changes = None;
+            }
+            return true;
+        }
+        if let Some(processes) = &mut self.processes {
+            if processes.handle_key(code) {
+                self.processes = None;
+            }
+            return true;
+        }
+        if let Some(inspect) = &mut self.inspect {
+            if inspect.handle_key(code) {
+                self.inspect = None;
+            }
+            return true;
+        }
+        if let Some(logs) = &mut self.logs {
+            if logs.handle_key(code) {
+                self.logs = None;
+            }
+            return true;
+        }
+        if let Some(index) = ('1'..='9').position(|c| code == KeyCode::Char(c)) {
+            if index < Screen::TITLES.len() && !self.screen.is_confirming() {
+                self.switch_screen(index);
+                return true;
+            }
+        }
+        let close = match &mut self.screen {
+            Screen::Containers => None,
+            Screen::Images(images) => Some(images.handle_key(code)),
+            Screen::Volumes(volumes) => Some(volumes.handle_key(code)),
+            Screen::Networks(networks) => Some(networks.handle_key(code)),
+            Screen::Disk(disk) => Some(disk.handle_key(code)),
+        };
+        if let Some(close) = close {
+            if close {
+                self.screen = Screen::Containers;
+            }
+            return true;
+        }
+        false
+    }
+
+    fn request_action(&mut self, action: ContainerAction) {
+        let Some(container) = self.selected() else {
+            return;
+        };
+        let pending = PendingAction {
+            action,
+            id: container.id.clone(),
+            name: display_name(container),
+        };
+        if action.is_destructive() {
+            self.pending_action = Some(pending);
+        } else {
+            self.run_action(pending);
+        }
+    }
+
+    /// Send the request in the background; the outcome comes back as a toast
+    /// and the container list catches up through the event stream.
+    fn run_action(&self, pending: PendingAction) {
+        let client = Arc::clone(&self.client);
+        let toast_tx = self.toast_tx.clone();
+        tokio::spawn(async move {
+            let PendingAction { action, id, name } = pending;
+            let (message, error) = match client.container_action(&id, action).await {
+                Ok(()) => (format!("{name} {}", action.done()), false),
+                Err(e) => (format!("Failed to {action} {name}: {e}"), true),
+            };
+            let _ = toast_tx.send(Toast {
+                message,
+                error,
+                shown_at: Instant::now(),
+            });
+        });
+    }
+
+    /// Keys while the confirmation dialog is open.
+    fn handle_confirmation(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(pending) = self.pending_action.take() {
+                    self.run_action(pending);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => self.pending_action = None,
+            _ => {}
+        }
+    }
+
+    /// The focused container, if there are any.
+    pub fn selected(&self) -> Option<&Container> {
+        let id = self.selected.as_ref()?;
+        self.containers.iter().find(|c| &c.id == id)
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let id = self.selected.as_ref()?;
+        self.containers.iter().position(|c| &c.id == id)
+    }
+
+    fn select_index(&mut self, index: usize) {
+        self.selected = self
+            .containers
+            .get(index.min(self.containers.len().saturating_sub(1)))
+            .map(|c| c.id.clone());
+    }
+
+    /// Cards spanned by `rows` grid rows.
+    fn grid_step(&self, rows: usize) -> isize {
+        isize::try_from(self.grid.get().columns * rows).unwrap_or(isize::MAX)
+    }
+
+    /// Move the focus by `delta` cards, stopping at either end.
+    fn move_selection(&mut self, delta: isize) {
+        let current = self.selected_index().unwrap_or(0);
+        self.select_index(current.saturating_add_signed(delta));
+    }
+
+    /// Cancel the background tasks. Also runs when the app is dropped.
+    pub fn shutdown(&mut self) {
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+        self.logs = None;
+        self.inspect = None;
+        self.processes = None;
+        self.changes = None;
+        self.screen = Screen::Containers;
+    }
+
+    /// The current refresh intervals.
+    pub fn intervals(&self) -> Intervals {
+        *self.intervals.borrow()
+    }
+
+    /// Snapshot of the resource history collected for a container.
+    #[cfg(test)]
+    pub fn history(&self, container_id: &str) -> Option<ResourceHistory> {
+        self.resource_histories
+            .lock()
+            .ok()
+            .and_then(|histories| histories.get(container_id).cloned())
+    }
+
     fn update(&mut self) {
+        while let Ok(toast) = self.toast_rx.try_recv() {
+            self.toast = Some(toast);
+        }
+        if let Ok(info) = self.info_rx.try_recv() {
+            self.info = Some(info);
+        }
+        if let Some(logs) = &mut self.logs {
+            logs.update();
+        }
+        if let Some(inspect) = &mut self.inspect {
+            inspect.update();
+        }
+        if let Some(processes) = &mut self.processes {
+            processes.update();
+        }
+        if let Some(changes) = &mut self.changes {
+            changes.update();
+        }
+        match &mut self.screen {
+            Screen::Containers => {}
+            Screen::Images(images) => images.update(&self.containers),
+            Screen::Volumes(volumes) => volumes.update(),
+            Screen::Networks(networks) => networks.update(),
+            Screen::Disk(disk) => disk.update(),
+        }
         // Try to receive container updates
         while let Ok(new_containers) = self.rx.try_recv() {
+            let previous_index = self.selected_index();
             self.containers = new_containers;
+            let containers = &self.containers;
+            self.marked.retain(|id| containers.iter().any(|c| &c.id == id));
+            // Keep the focus on the same container, or its neighbour once it's gone
+            if self.selected_index().is_none() {
+                self.select_index(previous_index.unwrap_or(0));
+            }
             // Update resource histories map with new containers
             if let Ok(mut histories) = self.resource_histories.lock() {
                 let mut new_histories = HashMap::new();
@@ -194,7 +832,15 @@ impl App {
         }
     }
 
-    fn render_container(f: &mut Frame, container: &Container, area: Rect, history: &ResourceHistory, view: &ResourceView, show_graphs: bool) {
+    fn render_container(
+        f: &mut Frame,
+        container: &Container,
+        area: Rect,
+        history: &ResourceHistory,
+        view: &ResourceView,
+        show_graphs: bool,
+        card: CardState,
+    ) {
         let status_color = match container.state.as_str() {
             "running" => {
                 if let Some(health) = &container.health {
@@ -213,8 +859,9 @@ impl App {
         // Use minimal view for very narrow widths
         if area.width < 30 {
             let name = container.names.first().map_or("", |s| s.as_str());
+            let label = format!("{}{name}", card.prefix());
             StatusIndicator::new(status_color)
-                .label(name)
+                .label(&label)
                 .render(area, f.buffer_mut());
             return;
         }
@@ -225,7 +872,7 @@ impl App {
             .constraints(
                 if show_graphs && container.state == "running" {
                     vec![
-                        Constraint::Min(6),     // Container info gets remaining space
+                        Constraint::Min(7),     // Container info gets remaining space
                         Constraint::Length(15), // Fixed height for graph
                     ]
                 } else {
@@ -240,7 +887,11 @@ impl App {
         } else {
             container.names.join(", ")
         };
-        let title = format!("{} ({})", names, &container.id[..12]);
+        let short_id = container.id.get(..12).unwrap_or(&container.id);
+        let title = match &container.pod_name {
+            Some(pod) => format!("{pod} ▸ {names} ({short_id})"),
+            None => format!("{names} ({short_id})"),
+        };
         let ports_str = format_ports(&container.ports);
         let content = vec![
             Line::from(vec![Span::raw(&container.image)]),
@@ -248,90 +899,436 @@ impl App {
             Line::from(vec![Span::raw(format_duration(container.created))]),
             Line::from(vec![Span::raw(&container.status)]),
             Line::from(vec![Span::raw(format!("Ports: {ports_str}"))]),
+            Line::from(vec![Span::raw(format!("Networks: {}", format_networks(&container.network_settings)))]),
         ];
 
+        let title = format!("{}{title}", card.prefix());
+        let border_style = if card.focused {
+            Style::default().fg(status_color.into()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(status_color.into())
+        };
         Card::new()
             .title(&title)
             .content(content)
-            .border_style(Style::default().fg(status_color.into()))
+            .border_style(border_style)
             .render(chunks[0], f.buffer_mut());
 
         // Only render graph if we're showing graphs and have a second chunk
         if show_graphs && container.state == "running" && chunks.len() > 1 {
-            let datasets = match view {
-                ResourceView::Cpu => vec![Dataset::default()
-                    .name("CPU %")
-                    .marker(symbols::Marker::Braille)
-                    .graph_type(GraphType::Line)
-                    .style(Style::default().fg(Color::Cyan))
-                    .data(&history.cpu_usage)],
-                ResourceView::Memory => vec![Dataset::default()
-                    .name("Memory %")
-                    .marker(symbols::Marker::Braille)
-                    .graph_type(GraphType::Line)
-                    .style(Style::default().fg(Color::Green))
-                    .data(&history.mem_usage)],
-                ResourceView::Gpu => vec![Dataset::default()
-                    .name("GPU %")
-                    .marker(symbols::Marker::Braille)
-                    .graph_type(GraphType::Line)
-                    .style(Style::default().fg(Color::Yellow))
-                    .data(&history.gpu_usage)],
+            f.render_widget(Self::resource_chart(history, view), chunks[1]);
+        }
+    }
+
+    /// Line chart of the selected resource over the container's history.
+    fn resource_chart<'a>(history: &'a ResourceHistory, view: &ResourceView) -> Chart<'a> {
+        let datasets = match view {
+            ResourceView::Cpu => vec![Dataset::default()
+                .name("CPU %")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Cyan))
+                .data(&history.cpu_usage)],
+            ResourceView::Memory => vec![Dataset::default()
+                .name("Memory %")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Green))
+                .data(&history.mem_usage)],
+            ResourceView::Gpu => vec![Dataset::default()
+                .name("GPU %")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Yellow))
+                .data(&history.gpu_usage)],
+        };
+
+        Chart::new(datasets)
+            .block(
+                ratui_lib::ratatui::widgets::Block::default()
+                    .borders(ratui_lib::ratatui::widgets::Borders::ALL)
+                    .title(match view {
+                        ResourceView::Cpu => "CPU Usage",
+                        ResourceView::Memory => "Memory Usage",
+                        ResourceView::Gpu => "GPU Usage",
+                    }),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([
+                        history.cpu_usage.first().map(|p| p.0).unwrap_or_default(),
+                        history.cpu_usage.last().map(|p| p.0).unwrap_or_default(),
+                    ]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, 100.0])
+                    .labels(vec!["0%", "25%", "50%", "75%", "100%"]
+                        .into_iter()
+                        .map(Span::raw)
+                        .collect::<Vec<_>>()),
+            )
+    }
+
+    /// Run until quit. Opening a shell leaves the TUI for the shell's
+    /// duration and sets it up again afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the terminal can't be set up, drawn to or restored.
+    pub fn run(mut self) -> anyhow::Result<()> {
+        // The event loop blocks, so hand this worker's other tasks elsewhere
+        tokio::task::block_in_place(|| {
+            loop {
+                let mut terminal = setup_terminal()?;
+                let result = self.event_loop(&mut terminal);
+                restore_terminal()?;
+                result?;
+                match self.shell_request.take() {
+                    Some((id, name)) => self.run_shell(&id, &name),
+                    None => return Ok(()),
+                }
+            }
+        })
+    }
+
+    /// Redraw every render interval, and right away after any input. Returns
+    /// on quit, or to hand the terminal to a requested shell.
+    fn event_loop<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> anyhow::Result<()> {
+        loop {
+            self.update();
+            terminal.draw(|f| self.ui(f))?;
+
+            let next_frame = Instant::now() + self.intervals().render;
+            while let Some(timeout) = next_frame.checked_duration_since(Instant::now()) {
+                if event::poll(timeout)? {
+                    if self.handle_event(event::read()?)? || self.shell_request.is_some() {
+                        return Ok(());
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    /// The slice of containers that fits in `area`, scrolled to keep the
+    /// focused card on screen.
+    fn visible_containers(&self, area: Rect) -> Range<usize> {
+        let card_height = if self.show_graphs { CARD_WITH_GRAPH_HEIGHT } else { CARD_HEIGHT };
+        let columns = usize::from((area.width / MIN_CARD_WIDTH).max(1)).min(self.containers.len().max(1));
+        let rows = usize::from((area.height / card_height).max(1));
+        self.grid.set(GridShape { columns, rows });
+
+        let total_rows = self.containers.len().div_ceil(columns);
+        let mut first_row = self.scroll_row.get();
+        if let Some(selected_row) = self.selected_index().map(|index| index / columns) {
+            if selected_row < first_row {
+                first_row = selected_row;
+            } else if selected_row >= first_row + rows {
+                first_row = selected_row + 1 - rows;
+            }
+        }
+        first_row = first_row.min(total_rows.saturating_sub(rows));
+        self.scroll_row.set(first_row);
+
+        let start = first_row * columns;
+        start..(start + rows * columns).min(self.containers.len())
+    }
+
+    /// Two lines: the endpoint and daemon version with the screen tabs, then
+    /// the daemon's host with container counts and their total usage.
+    fn render_header(&self, f: &mut Frame, area: Rect) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)])
+            .split(area);
+
+        let current = self.screen.index();
+        let mut tabs = Vec::new();
+        for (index, title) in Screen::TITLES.iter().enumerate() {
+            let style = if index == current {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::Gray)
             };
+            tabs.push(Span::styled(format!(" {} {title} ", index + 1), style));
+            tabs.push(Span::raw(" "));
+        }
+        let tabs = Line::from(tabs);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(u16::try_from(tabs.width()).unwrap_or(u16::MAX)),
+            ])
+            .split(rows[0]);
+        f.render_widget(tabs, top[1]);
 
-            let chart = Chart::new(datasets)
-                .block(
-                    ratui_lib::ratatui::widgets::Block::default()
-                        .borders(ratui_lib::ratatui::widgets::Borders::ALL)
-                        .title(match view {
-                            ResourceView::Cpu => "CPU Usage",
-                            ResourceView::Memory => "Memory Usage",
-                            ResourceView::Gpu => "GPU Usage",
-                        }),
-                )
-                .x_axis(
-                    Axis::default()
-                        .style(Style::default().fg(Color::Gray))
-                        .bounds([
-                            history.cpu_usage.first().map(|p| p.0).unwrap_or_default(),
-                            history.cpu_usage.last().map(|p| p.0).unwrap_or_default(),
-                        ]),
-                )
-                .y_axis(
-                    Axis::default()
-                        .style(Style::default().fg(Color::Gray))
-                        .bounds([0.0, 100.0])
-                        .labels(vec!["0%", "25%", "50%", "75%", "100%"]
-                            .into_iter()
-                            .map(Span::raw)
-                            .collect::<Vec<_>>()),
-                );
-
-            f.render_widget(chart, chunks[1]);
-        }
-    }
-
-    pub fn run_with_options(mut self, refresh_rate: Duration) -> Result<(), RatuiError> {
-        self.refresh_rate = refresh_rate;
-        let mut terminal = setup_terminal()?;
-        run_app(&mut terminal, self)?;
-        restore_terminal()?;
-        Ok(())
+        let daemon = self.client.version().daemon();
+        let version = daemon.as_ref().map(|daemon| {
+            format!(
+                " · {} {} (API {})",
+                if daemon.is_podman() { "Podman" } else { "Docker" },
+                daemon.version,
+                self.client.version().api()
+            )
+        });
+        // The endpoint gets what's left beside the tabs; ssh:// and tcp:// ones can be long
+        let fixed = "cetacea · ".chars().count() + version.as_ref().map_or(0, |v| v.chars().count()) + 1;
+        let endpoint = truncate(&self.client.endpoint().to_string(), usize::from(top[0].width).saturating_sub(fixed));
+        let mut header = vec![
+            Span::styled("cetacea", Style::default().fg(Color::Cyan)),
+            Span::raw(" · "),
+            Span::styled(endpoint, Style::default().fg(Color::Gray)),
+        ];
+        if let Some(version) = version {
+            header.push(Span::styled(version, Style::default().fg(Color::Gray)));
+        }
+        f.render_widget(Line::from(header), top[0]);
+
+        // Host details, falling back to what `/version` knows until `/info` answers
+        let host: Vec<String> = match (&self.info, &daemon) {
+            (Some(info), _) => vec![
+                info.name.clone(),
+                info.operating_system.clone(),
+                info.kernel_version.clone(),
+                format!("{} CPUs", info.ncpu),
+                format_bytes(info.mem_total),
+                info.driver.clone(),
+            ],
+            (None, Some(daemon)) => vec![daemon.os.clone(), daemon.kernel_version.clone()],
+            (None, None) => Vec::new(),
+        };
+        let host = host.into_iter().filter(|field| !field.is_empty()).collect::<Vec<_>>().join(" · ");
+
+        let summary = self
+            .resource_histories
+            .lock()
+            .map(|histories| ContainerSummary::new(&self.containers, &histories))
+            .unwrap_or_default();
+        let counts = [
+            (summary.running, "running", Color::Green),
+            (summary.paused, "paused", Color::Yellow),
+            (summary.exited, "exited", Color::Gray),
+            (summary.unhealthy, "unhealthy", Color::Red),
+        ];
+        let mut usage = Vec::new();
+        for (count, label, color) in counts {
+            usage.push(Span::styled("● ", Style::default().fg(if count > 0 { color } else { Color::DarkGray })));
+            usage.push(Span::raw(format!("{count} {label}  ")));
+        }
+        usage.push(Span::styled(
+            format!("CPU {:.1}%  Mem {} ", summary.cpu_percent, format_bytes(summary.memory)),
+            Style::default().fg(Color::Cyan),
+        ));
+        let usage = Line::from(usage);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(u16::try_from(usage.width()).unwrap_or(u16::MAX)),
+            ])
+            .split(rows[1]);
+        f.render_widget(Line::from(Span::styled(host, Style::default().fg(Color::DarkGray))), columns[0]);
+        f.render_widget(usage, columns[1]);
+    }
+
+    fn status_bar(&self) -> Line<'static> {
+        let intervals = self.intervals();
+        let mut spans = Vec::new();
+        for (kind, label) in [
+            (IntervalKind::List, "list"),
+            (IntervalKind::Stats, "stats"),
+            (IntervalKind::Render, "render"),
+        ] {
+            let style = if kind == self.selected_interval {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            spans.push(Span::styled(format!(" {label} {} ", format_interval(intervals.get(kind))), style));
+            spans.push(Span::raw(" "));
+        }
+        match self.toast.as_ref().filter(|toast| toast.shown_at.elapsed() < TOAST_DURATION) {
+            Some(toast) => spans.push(Span::styled(
+                toast.message.clone(),
+                Style::default().fg(if toast.error { Color::Red } else { Color::Green }),
+            )),
+            None if matches!(&self.screen, Screen::Images(images) if images.is_history()) => spans.push(Span::styled(
+                "[↑↓/jk] move  [Esc] back to images",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if matches!(self.screen, Screen::Images(_)) => spans.push(Span::styled(
+                "[↑↓/jk] select  [Enter] history  [s/a] sort by size/age, again to reverse  [D] remove  [P] prune dangling  [r] refresh  [1-5] screens  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if matches!(self.screen, Screen::Volumes(_)) => spans.push(Span::styled(
+                "[↑↓/jk] select  [s] sort by size/name  [D] remove unused  [r] refresh  [1-5] screens  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if matches!(self.screen, Screen::Networks(_)) => spans.push(Span::styled(
+                "[↑↓/jk] select  [c] connect container  [d] disconnect container  [r] refresh  [1-5] screens  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if matches!(self.screen, Screen::Disk(_)) => spans.push(Span::styled(
+                "[↑↓/jk] select  [P] prune selected  [r] refresh  [1-5] screens  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.changes.is_some() => spans.push(Span::styled(
+                "[↑↓/jk] move  [→←/lh] expand/collapse  [Enter] toggle  [E/C] expand/collapse all  [x] export  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.processes.is_some() => spans.push(Span::styled(
+                "[↑↓/jk] select  [p/u/c/m/n] sort by pid/user/cpu/mem/command, again to reverse  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.inspect.as_ref().is_some_and(InspectView::is_searching) => spans.push(Span::styled(
+                "type part of a key  [Enter] keep  [Esc] clear",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.inspect.as_ref().is_some_and(InspectView::is_tree) => spans.push(Span::styled(
+                "[↑↓/jk] move  [→←/lh] expand/collapse  [Enter] toggle  [E/C] expand/collapse all  [/] search keys  [n/N] next/prev  [y] copy  [Tab] summary  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.inspect.is_some() => spans.push(Span::styled(
+                "[↑↓/jk] scroll  [g/G] top/bottom  [Tab] tree  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.logs.as_ref().is_some_and(LogView::is_searching) => spans.push(Span::styled(
+                "type a regex (smart case)  [Enter] keep  [Esc] clear",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None if self.logs.is_some() => spans.push(Span::styled(
+                "[↑↓/jk] scroll  [g/G] top/bottom  [f] follow/pause  [/] search  [n/N] next/prev  [F] filter  [Esc] back",
+                Style::default().fg(Color::DarkGray),
+            )),
+            None => spans.push(Span::styled(
+                "[←↓↑→/hjkl] select  [Enter] logs  [Space] mark  [e] shell  [d] details  [t] processes  [c] changes  [u/s/r/K/p/D] start/stop/restart/kill/pause/remove  [v] view  [i] interval  [+/-] slower/faster  [g] graphs  [1-5] screens  [q] quit",
+                Style::default().fg(Color::DarkGray),
+            )),
+        }
+        Line::from(spans)
+    }
+
+    fn render_confirmation(f: &mut Frame, pending: &PendingAction) {
+        render_dialog(f, &format!("{} {}?", capitalize(&pending.action.to_string()), pending.name));
+    }
+}
+
+/// A yes/no dialog over the middle of the screen.
+fn render_dialog(f: &mut Frame, message: &str) {
+    let width = u16::try_from(message.len() + 4).unwrap_or(u16::MAX).max(30).min(f.area().width);
+    let area = centered(f.area(), width, 5);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(vec![
+            Line::from(Span::raw(message.to_string())),
+            Line::from(""),
+            Line::from(Span::styled("[y] confirm  [n] cancel", Style::default().fg(Color::DarkGray))),
+        ])
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title("Confirm"),
+        ),
+        area,
+    );
+}
+
+impl Drop for App {
+    fn drop(&mut self) {
+        self.shutdown();
     }
 }
 
 impl TerminalApp for App {
     fn ui(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0), Constraint::Length(1)])
+            .split(f.area());
+
+        self.render_header(f, chunks[0]);
+
+        match &self.screen {
+            Screen::Containers => {}
+            Screen::Images(images) => {
+                images.render(f, chunks[1]);
+                f.render_widget(self.status_bar(), chunks[2]);
+                return;
+            }
+            Screen::Volumes(volumes) => {
+                volumes.render(f, chunks[1]);
+                f.render_widget(self.status_bar(), chunks[2]);
+                return;
+            }
+            Screen::Networks(networks) => {
+                networks.render(f, chunks[1]);
+                f.render_widget(self.status_bar(), chunks[2]);
+                return;
+            }
+            Screen::Disk(disk) => {
+                disk.render(f, chunks[1]);
+                f.render_widget(self.status_bar(), chunks[2]);
+                return;
+            }
+        }
+
+        if let Some(changes) = &self.changes {
+            changes.render(f, chunks[1]);
+            f.render_widget(self.status_bar(), chunks[2]);
+            return;
+        }
+        if let Some(processes) = &self.processes {
+            processes.render(f, chunks[1]);
+            f.render_widget(self.status_bar(), chunks[2]);
+            return;
+        }
+        if let Some(inspect) = &self.inspect {
+            inspect.render(f, chunks[1]);
+            f.render_widget(self.status_bar(), chunks[2]);
+            return;
+        }
+        if let Some(logs) = &self.logs {
+            logs.render(f, chunks[1]);
+            f.render_widget(self.status_bar(), chunks[2]);
+            return;
+        }
+
+        let visible = self.visible_containers(chunks[1]);
         let grid = ResponsiveGrid::new();
-        let cells = grid.split(f.area(), self.containers.len());
+        let cells = grid.split(chunks[1], visible.len());
+        // Navigate by the columns the grid actually used
+        if let Some(first) = cells.first() {
+            let columns = cells.iter().take_while(|cell| cell.y == first.y).count().max(1);
+            self.grid.set(GridShape {
+                columns,
+                ..self.grid.get()
+            });
+        }
 
-        for (container, area) in self.containers.iter().zip(cells) {
+        let selected = self.selected.as_deref();
+        for (container, area) in self.containers[visible].iter().zip(cells) {
             if let Ok(histories) = self.resource_histories.lock() {
                 if let Some(history) = histories.get(&container.id) {
-                    Self::render_container(f, container, area, history, &self.current_view, self.show_graphs);
+                    let card = CardState {
+                        focused: selected == Some(container.id.as_str()),
+                        marked: self.marked.contains(&container.id),
+                    };
+                    Self::render_container(f, container, area, history, &self.current_view, self.show_graphs, card);
                 }
             }
         }
+
+        f.render_widget(self.status_bar(), chunks[2]);
+
+        if let Some(pending) = &self.pending_action {
+            Self::render_confirmation(f, pending);
+        }
     }
 
     fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
@@ -339,28 +1336,63 @@ impl TerminalApp for App {
         self.update();
 
         if let Event::Key(key) = event {
+            if self.pending_action.is_some() {
+                self.handle_confirmation(key.code);
+                return Ok(false);
+            }
+            if self.handle_view_key(key.code) {
+                return Ok(false);
+            }
             match key.code {
+                KeyCode::Enter => self.open_logs(),
+                KeyCode::Char(' ') => self.toggle_mark(),
+                KeyCode::Char('e') => self.request_shell(),
+                KeyCode::Char('d') => self.open_inspect(),
+                KeyCode::Char('t') => self.open_processes(),
+                KeyCode::Char('c') => self.open_changes(),
+                KeyCode::Char('u') => self.request_action(ContainerAction::Start),
+                KeyCode::Char('s') => self.request_action(ContainerAction::Stop),
+                KeyCode::Char('r') => self.request_action(ContainerAction::Restart),
+                KeyCode::Char('K') => self.request_action(ContainerAction::Kill),
+                KeyCode::Char('D') => self.request_action(ContainerAction::Remove),
+                KeyCode::Char('p') => {
+                    let paused = self.selected().is_some_and(|c| c.state == "paused");
+                    self.request_action(if paused { ContainerAction::Unpause } else { ContainerAction::Pause });
+                }
                 KeyCode::Char('q') => {
                     self.should_quit = true;
+                    self.shutdown();
                     return Ok(true);
                 }
                 KeyCode::Char('g') => {
                     self.show_graphs = !self.show_graphs;
                 }
-                KeyCode::Left => {
-                    self.current_view = match self.current_view {
-                        ResourceView::Cpu => ResourceView::Gpu,
-                        ResourceView::Memory => ResourceView::Cpu,
-                        ResourceView::Gpu => ResourceView::Memory,
-                    };
+                KeyCode::Char('i') => {
+                    self.selected_interval = self.selected_interval.next();
+                }
+                KeyCode::Char('+' | '=') => {
+                    let kind = self.selected_interval;
+                    self.intervals.send_modify(|intervals| intervals.adjust(kind, true));
+                }
+                KeyCode::Char('-') => {
+                    let kind = self.selected_interval;
+                    self.intervals.send_modify(|intervals| intervals.adjust(kind, false));
                 }
-                KeyCode::Right => {
+                KeyCode::Char('v') => {
                     self.current_view = match self.current_view {
                         ResourceView::Cpu => ResourceView::Memory,
                         ResourceView::Memory => ResourceView::Gpu,
                         ResourceView::Gpu => ResourceView::Cpu,
                     };
                 }
+                KeyCode::Left | KeyCode::Char('h') => self.move_selection(-1),
+                KeyCode::Right | KeyCode::Char('l') => self.move_selection(1),
+                KeyCode::Up | KeyCode::Char('k') => self.move_selection(-self.grid_step(1)),
+                KeyCode::Down | KeyCode::Char('j') => self.move_selection(self.grid_step(1)),
+                KeyCode::PageUp => self.move_selection(-self.grid_step(self.grid.get().rows)),
+                KeyCode::PageDown => self.move_selection(self.grid_step(self.grid.get().rows)),
+                KeyCode::Home => self.select_index(0),
+                KeyCode::End => self.select_index(usize::MAX),
                 _ => {}
             }
         }
@@ -369,6 +1401,209 @@ impl TerminalApp for App {
     }
 }
 
+/// Sort containers: grouped by pod (unpodded first), then running first,
+/// then by name.
+pub fn sort_containers(containers: &mut [Container]) {
+    containers.sort_by(|a, b| {
+        let a_name = a.names.first().map_or("", |s| s.as_str());
+        let b_name = b.names.first().map_or("", |s| s.as_str());
+        a.pod_name
+            .cmp(&b.pod_name)
+            .then_with(|| (b.state == "running").cmp(&(a.state == "running")))
+            .then_with(|| a_name.cmp(b_name))
+    });
+}
+
+/// Container name without the leading slash, or its short ID.
+fn display_name(container: &Container) -> String {
+    container
+        .names
+        .first()
+        .map_or_else(|| container.id.get(..12).unwrap_or(&container.id).to_string(), |n| n.trim_start_matches('/').to_string())
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// The first row to show so `selected` stays within `height` rows.
+fn scroll(top: &Cell<usize>, selected: usize, height: usize, len: usize) -> usize {
+    let mut first = top.get();
+    if selected < first {
+        first = selected;
+    } else if selected >= first + height {
+        first = selected + 1 - height;
+    }
+    first = first.min(len.saturating_sub(height));
+    top.set(first);
+    first
+}
+
+/// Take what a confirmation dialog was asking about when `code` is `y` or
+/// Enter; any other key dismisses the dialog.
+fn confirmed<T>(pending: &mut Option<T>, code: KeyCode) -> Option<T> {
+    pending.take().filter(|_| matches!(code, KeyCode::Char('y') | KeyCode::Enter))
+}
+
+/// Background requests of a screen. Results come back over a channel for
+/// the screen's `update` to take, and dropping the set aborts the requests
+/// still running.
+pub(crate) struct TaskSet<M> {
+    tx: mpsc::Sender<M>,
+    rx: mpsc::Receiver<M>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl<M> Default for TaskSet<M> {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            tx,
+            rx,
+            tasks: Vec::new(),
+        }
+    }
+}
+
+impl<M: Send + 'static> TaskSet<M> {
+    pub(crate) fn spawn(&mut self, task: impl Future<Output = M> + Send + 'static) {
+        let tx = self.tx.clone();
+        self.tasks.retain(|task| !task.is_finished());
+        self.tasks.push(tokio::spawn(async move {
+            let _ = tx.send(task.await);
+        }));
+    }
+
+    /// The result of a finished request, if there is one.
+    pub(crate) fn try_recv(&self) -> Option<M> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl<M> Drop for TaskSet<M> {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// The selected row of a list screen, kept by key so it stays on the same
+/// item across refreshes and re-sorting.
+pub(crate) struct Selection<T, K> {
+    key: fn(&T) -> &K,
+    selected: Option<K>,
+    /// First row on screen, kept around the selection on render.
+    top: Cell<usize>,
+    /// Rows that fit on screen, from the last render.
+    height: Cell<usize>,
+}
+
+impl<T, K: Clone + PartialEq> Selection<T, K> {
+    pub(crate) fn new(key: fn(&T) -> &K) -> Self {
+        Self {
+            key,
+            selected: None,
+            top: Cell::new(0),
+            height: Cell::new(1),
+        }
+    }
+
+    /// Index of the selected row, or of the first when it's gone.
+    pub(crate) fn index(&self, rows: &[T]) -> usize {
+        self.selected
+            .as_ref()
+            .and_then(|selected| rows.iter().position(|row| (self.key)(row) == selected))
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn get<'a>(&self, rows: &'a [T]) -> Option<&'a T> {
+        rows.get(self.index(rows))
+    }
+
+    pub(crate) fn select_index(&mut self, rows: &[T], index: usize) {
+        self.selected = rows
+            .get(index.min(rows.len().saturating_sub(1)))
+            .map(|row| (self.key)(row).clone());
+    }
+
+    /// Select the first row when the selected one is gone.
+    pub(crate) fn retain(&mut self, rows: &[T]) {
+        if self.selected.as_ref().is_none_or(|selected| !rows.iter().any(|row| (self.key)(row) == selected)) {
+            self.selected = rows.first().map(|row| (self.key)(row).clone());
+        }
+    }
+
+    /// Rows the page keys move, from the last render.
+    pub(crate) fn page(&self) -> isize {
+        isize::try_from(self.height.get().max(1)).unwrap_or(isize::MAX)
+    }
+
+    /// Move with the arrow keys, `j`/`k`, PgUp/PgDn, Home/End and `g`/`G`.
+    /// Returns whether `code` was one of them.
+    pub(crate) fn handle_key(&mut self, code: KeyCode, rows: &[T]) -> bool {
+        let index = self.index(rows);
+        let page = self.page();
+        let index = match code {
+            KeyCode::Up | KeyCode::Char('k') => index.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => index + 1,
+            KeyCode::PageUp => index.saturating_add_signed(-page),
+            KeyCode::PageDown => index.saturating_add_signed(page),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => usize::MAX,
+            _ => return false,
+        };
+        self.select_index(rows, index);
+        true
+    }
+
+    /// The first row to show so the selection stays within `height` rows,
+    /// which is also how far the page keys move from then on.
+    pub(crate) fn scroll(&self, rows: &[T], height: usize) -> usize {
+        self.height.set(height);
+        scroll(&self.top, self.index(rows), height, rows.len())
+    }
+}
+
+/// A `width` x `height` rectangle in the middle of `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Network names with the container's address on each, e.g.
+/// `bridge 172.17.0.2, backend 10.0.1.5`. Stopped containers have no address.
+#[must_use]
+pub fn format_networks(settings: &NetworkSettings) -> String {
+    if settings.networks.is_empty() {
+        return "None".to_string();
+    }
+
+    settings
+        .networks
+        .iter()
+        .map(|(name, endpoint)| {
+            if endpoint.ip_address.is_empty() {
+                name.clone()
+            } else {
+                format!("{name} {}", endpoint.ip_address)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[must_use]
 pub fn format_ports(ports: &[Port]) -> String {
     if ports.is_empty() {
         return "None".to_string();
@@ -377,25 +1612,25 @@ pub fn format_ports(ports: &[Port]) -> String {
     ports
         .iter()
         .map(|p| {
-            let mut parts = Vec::new();
+            let mut fields = Vec::new();
 
             if let Some(ip) = &p.ip {
                 if !ip.is_empty() {
-                    parts.push(ip.to_string());
+                    fields.push(ip.clone());
                 }
             }
 
             if let Some(external) = p.external {
-                parts.push(external.to_string());
+                fields.push(external.to_string());
             }
 
-            parts.push(p.internal.to_string());
+            fields.push(p.internal.to_string());
 
             // Format as address:port/protocol
-            let addr_port = if parts.len() > 1 {
-                parts.join(":")
+            let addr_port = if fields.len() > 1 {
+                fields.join(":")
             } else {
-                parts[0].clone()
+                fields[0].clone()
             };
 
             format!("{}/{}", addr_port, p.protocol.to_lowercase())
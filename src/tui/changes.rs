@@ -0,0 +1,388 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashSet},
+    fmt::Write as _,
+    path::PathBuf,
+    sync::{Arc, mpsc},
+};
+
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph},
+    },
+};
+use tokio::task::JoinHandle;
+
+use super::{
+    scroll,
+    tree::{Tree, TreeItem, TreeSource},
+};
+use crate::docker::{
+    Connection,
+    models::{ChangeKind, FilesystemChange},
+};
+use crate::error::WhaleError;
+
+/// Number of changes of each kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Counts {
+    pub added: usize,
+    pub changed: usize,
+    pub deleted: usize,
+}
+
+impl Counts {
+    fn add(&mut self, kind: ChangeKind) {
+        match kind {
+            ChangeKind::Added => self.added += 1,
+            ChangeKind::Modified => self.changed += 1,
+            ChangeKind::Deleted => self.deleted += 1,
+        }
+    }
+
+    fn merge(&mut self, other: Counts) {
+        self.added += other.added;
+        self.changed += other.changed;
+        self.deleted += other.deleted;
+    }
+
+    fn total(self) -> usize {
+        self.added + self.changed + self.deleted
+    }
+}
+
+/// A changed path, or a directory holding changes.
+#[derive(Debug, Default)]
+pub(crate) struct Node {
+    /// The change to this path itself; directories that only hold changes
+    /// may not be listed.
+    kind: Option<ChangeKind>,
+    children: BTreeMap<String, Node>,
+    /// Changes below this path.
+    counts: Counts,
+}
+
+impl Node {
+    fn count(&mut self) -> Counts {
+        let mut counts = Counts::default();
+        for child in self.children.values_mut() {
+            counts.merge(child.count());
+            if let Some(kind) = child.kind {
+                counts.add(kind);
+            }
+        }
+        self.counts = counts;
+        counts
+    }
+}
+
+/// One visible row of a [`ChangeTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChangeRow {
+    pub path: String,
+    pub name: String,
+    pub depth: usize,
+    pub kind: Option<ChangeKind>,
+    /// Changes below this path; zero for files.
+    pub counts: Counts,
+}
+
+impl TreeItem for ChangeRow {
+    fn id(&self) -> &str {
+        &self.path
+    }
+
+    fn has_children(&self) -> bool {
+        self.counts.total() > 0
+    }
+}
+
+impl TreeSource for Node {
+    type Row = ChangeRow;
+
+    fn rows(&self, expanded: &HashSet<String>) -> Vec<ChangeRow> {
+        let mut rows = Vec::new();
+        push_children(self, "", 0, expanded, &mut rows);
+        rows
+    }
+
+    fn branches(&self) -> HashSet<String> {
+        fn directories(node: &Node, path: &str, out: &mut HashSet<String>) {
+            for (name, child) in &node.children {
+                if !child.children.is_empty() {
+                    let child_path = format!("{path}/{name}");
+                    directories(child, &child_path, out);
+                    out.insert(child_path);
+                }
+            }
+        }
+        let mut branches = HashSet::new();
+        directories(self, "", &mut branches);
+        branches
+    }
+}
+
+/// Changed paths as a directory tree with collapsible directories.
+pub(crate) type ChangeTree = Tree<Node>;
+
+impl ChangeTree {
+    /// Show the top-level paths of `changes`, all collapsed.
+    pub(crate) fn from_changes(changes: &[FilesystemChange]) -> Self {
+        let mut root = Node::default();
+        for change in changes {
+            let mut node = &mut root;
+            for part in change.path.split('/').filter(|part| !part.is_empty()) {
+                node = node.children.entry(part.to_string()).or_default();
+            }
+            node.kind = Some(change.kind);
+        }
+        root.count();
+        Tree::new(root)
+    }
+
+    /// Changes in the whole container.
+    pub(crate) fn counts(&self) -> Counts {
+        self.source().counts
+    }
+}
+
+fn push_children(node: &Node, path: &str, depth: usize, expanded: &HashSet<String>, rows: &mut Vec<ChangeRow>) {
+    for (name, child) in &node.children {
+        let child_path = format!("{path}/{name}");
+        rows.push(ChangeRow {
+            path: child_path.clone(),
+            name: name.clone(),
+            depth,
+            kind: child.kind,
+            counts: child.counts,
+        });
+        if expanded.contains(&child_path) {
+            push_children(child, &child_path, depth + 1, expanded, rows);
+        }
+    }
+}
+
+/// The changes as `docker diff` prints them, one `<A|C|D> <path>` per line.
+pub(crate) fn export_text(changes: &[FilesystemChange]) -> String {
+    let mut changes: Vec<_> = changes.iter().collect();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes.iter().fold(String::new(), |mut text, change| {
+        let _ = writeln!(text, "{} {}", change.kind.marker(), change.path);
+        text
+    })
+}
+
+fn kind_style(kind: ChangeKind) -> Style {
+    Style::default().fg(match kind {
+        ChangeKind::Added => Color::Green,
+        ChangeKind::Modified => Color::Yellow,
+        ChangeKind::Deleted => Color::Red,
+    })
+}
+
+/// `2 A · 1 C`, leaving out kinds with no changes.
+fn count_spans(counts: Counts) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (count, kind) in [
+        (counts.added, ChangeKind::Added),
+        (counts.changed, ChangeKind::Modified),
+        (counts.deleted, ChangeKind::Deleted),
+    ] {
+        if count > 0 {
+            if !spans.is_empty() {
+                spans.push(Span::styled(" · ", Style::default().fg(Color::DarkGray)));
+            }
+            spans.push(Span::styled(format!("{count} {}", kind.marker()), kind_style(kind)));
+        }
+    }
+    spans
+}
+
+enum Listing {
+    Loading,
+    Failed(String),
+    Ready {
+        changes: Vec<FilesystemChange>,
+        tree: ChangeTree,
+    },
+}
+
+/// Full-screen tree of the paths a container changed in its writable layer.
+pub(crate) struct ChangesView {
+    name: String,
+    listing: Listing,
+    rx: mpsc::Receiver<Result<Vec<FilesystemChange>, WhaleError>>,
+    task: JoinHandle<()>,
+    /// First row on screen, kept around the selection on render.
+    top: Cell<usize>,
+    /// Rows that fit in the view, from the last render.
+    height: Cell<usize>,
+    /// Outcome of the last export.
+    message: Option<(String, bool)>,
+}
+
+impl ChangesView {
+    /// List the changes of a container in the background.
+    pub(crate) fn open(client: Arc<dyn Connection>, id: String, name: String) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let task = tokio::spawn(async move {
+            let _ = tx.send(client.container_changes(&id).await);
+        });
+        Self {
+            name,
+            listing: Listing::Loading,
+            rx,
+            task,
+            top: Cell::new(0),
+            height: Cell::new(1),
+            message: None,
+        }
+    }
+
+    /// Take the listing once it has arrived.
+    pub(crate) fn update(&mut self) {
+        if let Ok(result) = self.rx.try_recv() {
+            self.listing = match result {
+                Ok(changes) => Listing::Ready {
+                    tree: ChangeTree::from_changes(&changes),
+                    changes,
+                },
+                Err(e) => Listing::Failed(e.to_string()),
+            };
+        }
+    }
+
+    /// Write the changes to `<name>-changes.txt` in the working directory.
+    fn export(&mut self) {
+        let Listing::Ready { changes, .. } = &self.listing else {
+            return;
+        };
+        let path = PathBuf::from(format!("{}-changes.txt", self.name));
+        self.message = Some(match std::fs::write(&path, export_text(changes)) {
+            Ok(()) => (format!("exported to {}", path.display()), false),
+            Err(e) => (format!("export failed: {e}"), true),
+        });
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        self.message = None;
+        let page = isize::try_from(self.height.get().max(1)).unwrap_or(isize::MAX);
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Char('x') => self.export(),
+            _ => {}
+        }
+        let Listing::Ready { tree, .. } = &mut self.listing else {
+            return false;
+        };
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => tree.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => tree.move_by(1),
+            KeyCode::PageUp => tree.move_by(-page),
+            KeyCode::PageDown => tree.move_by(page),
+            KeyCode::Home | KeyCode::Char('g') => tree.move_to(0),
+            KeyCode::End | KeyCode::Char('G') => tree.move_to(usize::MAX),
+            KeyCode::Right | KeyCode::Char('l') => tree.expand(),
+            KeyCode::Left | KeyCode::Char('h') => tree.collapse(),
+            KeyCode::Enter | KeyCode::Char(' ') => tree.toggle(),
+            KeyCode::Char('E') => tree.expand_all(),
+            KeyCode::Char('C') => tree.collapse_all(),
+            _ => {}
+        }
+        false
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        let height = usize::from(area.height.saturating_sub(2)).max(1);
+        self.height.set(height);
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Changes: {} ", self.name));
+        let content = match &self.listing {
+            Listing::Loading => vec![Line::from(Span::styled("Loading…", Style::default().fg(Color::DarkGray)))],
+            Listing::Failed(error) => vec![Line::from(Span::styled(
+                format!("Failed to list changes of {}: {error}", self.name),
+                Style::default().fg(Color::Red),
+            ))],
+            Listing::Ready { tree, .. } if tree.rows().is_empty() => vec![Line::from(Span::styled(
+                "No changes to the image's filesystem",
+                Style::default().fg(Color::DarkGray),
+            ))],
+            Listing::Ready { tree, .. } => {
+                let mut totals = count_spans(tree.counts());
+                totals.insert(0, Span::raw(" "));
+                totals.push(Span::raw(" "));
+                block = block.title(Line::from(totals).right_aligned());
+                self.tree_lines(tree, height)
+            }
+        };
+        if let Some((message, error)) = &self.message {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {message} "),
+                    Style::default().fg(if *error { Color::Red } else { Color::Green }),
+                ))
+                .right_aligned(),
+            );
+        }
+        f.render_widget(Paragraph::new(content).block(block), area);
+    }
+
+    /// The rows on screen, scrolled to keep the selection visible.
+    fn tree_lines(&self, tree: &ChangeTree, height: usize) -> Vec<Line<'static>> {
+        let selected = tree.selected();
+        let top = scroll(&self.top, selected, height, tree.rows().len());
+
+        tree.rows()
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(index, row)| {
+                let directory = row.counts.total() > 0;
+                let marker = match (directory, tree.is_expanded(&row.path)) {
+                    (true, true) => "▾ ",
+                    (true, false) => "▸ ",
+                    (false, _) => "  ",
+                };
+                let (kind, style) = match row.kind {
+                    Some(kind) => (kind.marker(), kind_style(kind)),
+                    None => (' ', Style::default()),
+                };
+                let mut spans = vec![
+                    Span::raw(format!("{}{marker}", "  ".repeat(row.depth))),
+                    Span::styled(format!("{kind} "), style.add_modifier(Modifier::BOLD)),
+                    Span::styled(
+                        if directory { format!("{}/", row.name) } else { row.name.clone() },
+                        style,
+                    ),
+                ];
+                if directory {
+                    spans.push(Span::raw("  "));
+                    spans.extend(count_spans(row.counts));
+                }
+                let line = Line::from(spans);
+                if index == selected {
+                    line.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for ChangesView {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
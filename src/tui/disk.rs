@@ -0,0 +1,455 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::sync::Arc;
+
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::{Constraint, Direction, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph, Row, Table},
+    },
+};
+
+use super::{Selection, TaskSet, confirmed, render_dialog};
+use crate::docker::{
+    Connection,
+    models::{DiskUsage, PruneReport, VolumeUsage},
+};
+use crate::error::WhaleError;
+use crate::utils::format_bytes;
+
+/// A kind of object `/system/df` accounts for, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Category {
+    Images,
+    Containers,
+    Volumes,
+    BuildCache,
+}
+
+impl Category {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            Self::Images => "Images",
+            Self::Containers => "Containers",
+            Self::Volumes => "Local volumes",
+            Self::BuildCache => "Build cache",
+        }
+    }
+
+    /// Header of the breakdown's detail column.
+    fn detail(self) -> &'static str {
+        match self {
+            Self::Images | Self::Volumes => "CONTAINERS",
+            Self::Containers => "STATE",
+            Self::BuildCache => "TYPE",
+        }
+    }
+
+    /// What pruning removes, for the confirmation dialog.
+    fn prune_target(self) -> &'static str {
+        match self {
+            Self::Images => "every image no container uses",
+            Self::Containers => "every stopped container",
+            Self::Volumes => "every volume no container mounts",
+            Self::BuildCache => "all build cache not in use",
+        }
+    }
+
+    /// Plural noun for what a prune removed.
+    fn noun(self) -> &'static str {
+        match self {
+            Self::Images => "images",
+            Self::Containers => "containers",
+            Self::Volumes => "volumes",
+            Self::BuildCache => "build cache records",
+        }
+    }
+
+    async fn prune(self, client: &dyn Connection) -> Result<PruneReport, WhaleError> {
+        match self {
+            Self::Images => client.prune_images(true).await,
+            Self::Containers => client.prune_containers().await,
+            Self::Volumes => client.prune_volumes().await,
+            Self::BuildCache => client.prune_build_cache().await,
+        }
+    }
+}
+
+/// One image, container, volume or build cache record.
+#[derive(Debug, Clone)]
+pub(crate) struct Item {
+    pub name: String,
+    pub size: u64,
+    /// Whether pruning its category would remove it.
+    pub reclaimable: bool,
+    pub detail: String,
+}
+
+/// Totals of one category, with its items biggest first.
+#[derive(Debug, Clone)]
+pub(crate) struct Summary {
+    pub category: Category,
+    pub items: Vec<Item>,
+    /// Items in use: by a container, running, or by a build.
+    pub active: usize,
+    pub size: u64,
+    pub reclaimable: u64,
+}
+
+impl Summary {
+    fn new(category: Category, mut items: Vec<Item>, active: usize, size: u64, reclaimable: u64) -> Self {
+        items.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        Self {
+            category,
+            items,
+            active,
+            size,
+            reclaimable,
+        }
+    }
+}
+
+/// Totals per category the way `docker system df` computes them: shared
+/// image layers count once, and only stopped containers, unmounted volumes
+/// and idle build cache are reclaimable.
+pub(crate) fn summarize(usage: &DiskUsage) -> Vec<Summary> {
+    let count = |n: i64| if n < 0 { "-".to_string() } else { n.to_string() };
+
+    // Layers of used images stay, including the ones they share
+    let used: u64 = usage
+        .images
+        .iter()
+        .filter(|image| image.containers > 0)
+        .map(|image| image.size.saturating_sub(u64::try_from(image.shared_size).unwrap_or(0)))
+        .sum();
+    let images = usage
+        .images
+        .iter()
+        .map(|image| Item {
+            name: image
+                .repo_tags
+                .iter()
+                .find(|tag| *tag != "<none>:<none>")
+                .cloned()
+                .unwrap_or_else(|| image.short_id().to_string()),
+            size: image.size,
+            reclaimable: image.containers == 0,
+            detail: count(image.containers),
+        })
+        .collect();
+    let images_active = usage.images.iter().filter(|image| image.containers > 0).count();
+
+    let containers = usage
+        .containers
+        .iter()
+        .map(|container| Item {
+            name: container
+                .names
+                .first()
+                .map_or_else(|| container.id.chars().take(12).collect(), |name| name.trim_start_matches('/').to_string()),
+            size: container.size_rw,
+            reclaimable: matches!(container.state.as_str(), "created" | "exited" | "dead"),
+            detail: container.state.clone(),
+        })
+        .collect::<Vec<_>>();
+    let containers_active = usage.containers.iter().filter(|c| c.state == "running").count();
+    let containers_size = containers.iter().map(|c| c.size).sum();
+    let containers_reclaimable = containers.iter().filter(|c| c.reclaimable).map(|c| c.size).sum();
+
+    let volumes = usage
+        .volumes
+        .iter()
+        .map(|volume| {
+            // Sizes and references the driver can't tell count as zero and in use
+            let data = volume.usage_data;
+            Item {
+                name: volume.name.clone(),
+                size: data.and_then(VolumeUsage::size).unwrap_or(0),
+                reclaimable: data.and_then(VolumeUsage::ref_count) == Some(0),
+                detail: data.map_or_else(|| "-".to_string(), |data| count(data.ref_count)),
+            }
+        })
+        .collect::<Vec<_>>();
+    let volumes_active = usage
+        .volumes
+        .iter()
+        .filter(|volume| volume.usage_data.is_some_and(|data| data.ref_count > 0))
+        .count();
+    let volumes_size = volumes.iter().map(|v| v.size).sum();
+    let volumes_reclaimable = volumes.iter().filter(|v| v.reclaimable).map(|v| v.size).sum();
+
+    // Records shared with images are accounted for there
+    let cache: Vec<_> = usage.build_cache.iter().filter(|record| !record.shared).collect();
+    let build_cache = cache
+        .iter()
+        .map(|record| Item {
+            name: if record.description.is_empty() {
+                record.id.chars().take(12).collect()
+            } else {
+                record.description.clone()
+            },
+            size: record.size,
+            reclaimable: !record.in_use,
+            detail: record.kind.clone(),
+        })
+        .collect();
+    let cache_active = cache.iter().filter(|record| record.in_use).count();
+    let cache_size = cache.iter().map(|record| record.size).sum();
+    let cache_reclaimable = cache.iter().filter(|record| !record.in_use).map(|record| record.size).sum();
+
+    vec![
+        Summary::new(
+            Category::Images,
+            images,
+            images_active,
+            usage.layers_size,
+            usage.layers_size.saturating_sub(used),
+        ),
+        Summary::new(Category::Containers, containers, containers_active, containers_size, containers_reclaimable),
+        Summary::new(Category::Volumes, volumes, volumes_active, volumes_size, volumes_reclaimable),
+        Summary::new(Category::BuildCache, build_cache, cache_active, cache_size, cache_reclaimable),
+    ]
+}
+
+/// `reclaimable` with its share of `size`, e.g. `1.5 GiB (75%)`.
+pub(crate) fn format_reclaimable(reclaimable: u64, size: u64) -> String {
+    if size == 0 {
+        return format_bytes(reclaimable);
+    }
+    format!("{} ({}%)", format_bytes(reclaimable), reclaimable.min(size) * 100 / size)
+}
+
+enum Message {
+    Usage(Result<DiskUsage, WhaleError>),
+    /// Outcome of a prune, and whether it failed.
+    Done(String, bool),
+}
+
+/// Disk usage by images, containers, volumes and build cache, with a
+/// prune for each.
+pub(crate) struct DiskView {
+    client: Arc<dyn Connection>,
+    summaries: Vec<Summary>,
+    error: Option<String>,
+    loaded: bool,
+    tasks: TaskSet<Message>,
+    selection: Selection<Summary, Category>,
+    /// Category waiting for the user to confirm its prune.
+    confirm: Option<Category>,
+    /// Outcome of the last prune.
+    message: Option<(String, bool)>,
+}
+
+impl DiskView {
+    /// Request the disk usage in the background.
+    pub(crate) fn open(client: Arc<dyn Connection>) -> Self {
+        let mut view = Self {
+            client,
+            summaries: Vec::new(),
+            error: None,
+            loaded: false,
+            tasks: TaskSet::default(),
+            selection: Selection::new(|summary: &Summary| &summary.category),
+            confirm: None,
+            message: None,
+        };
+        view.reload();
+        view
+    }
+
+    fn reload(&mut self) {
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move { Message::Usage(client.disk_usage().await) });
+    }
+
+    /// Take finished requests.
+    pub(crate) fn update(&mut self) {
+        while let Some(message) = self.tasks.try_recv() {
+            match message {
+                Message::Usage(result) => {
+                    self.loaded = true;
+                    match result {
+                        Ok(usage) => {
+                            self.summaries = summarize(&usage);
+                            self.error = None;
+                            self.selection.retain(&self.summaries);
+                        }
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+                Message::Done(message, error) => {
+                    self.message = Some((message, error));
+                    self.reload();
+                }
+            }
+        }
+    }
+
+    /// Whether a prune is waiting for confirmation.
+    pub(crate) fn is_confirming(&self) -> bool {
+        self.confirm.is_some()
+    }
+
+    fn prune(&mut self, category: Category) {
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move {
+            match category.prune(client.as_ref()).await {
+                Ok(report) => Message::Done(
+                    format!(
+                        "Pruned {} {}, reclaimed {}",
+                        report.deleted.len(),
+                        category.noun(),
+                        format_bytes(report.space_reclaimed)
+                    ),
+                    false,
+                ),
+                Err(e) => Message::Done(format!("Failed to prune {}: {e}", category.noun()), true),
+            }
+        });
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.confirm.is_some() {
+            if let Some(category) = confirmed(&mut self.confirm, code) {
+                self.prune(category);
+            }
+            return false;
+        }
+        self.message = None;
+        if self.selection.handle_key(code, &self.summaries) {
+            return false;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Char('P') => self.confirm = self.selection.get(&self.summaries).map(|s| s.category),
+            KeyCode::Char('r') => self.reload(),
+            _ => {}
+        }
+        false
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            // Borders, the header row and a row per category
+            .constraints([Constraint::Length(7), Constraint::Min(4)])
+            .split(area);
+
+        let size: u64 = self.summaries.iter().map(|s| s.size).sum();
+        let reclaimable: u64 = self.summaries.iter().map(|s| s.reclaimable).sum();
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Disk usage ")
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} · {} reclaimable ", format_bytes(size), format_bytes(reclaimable)),
+                    Style::default().fg(Color::Gray),
+                ))
+                .right_aligned(),
+            );
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {error} "),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if let Some((message, error)) = &self.message {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {message} "),
+                    Style::default().fg(if *error { Color::Red } else { Color::Green }),
+                ))
+                .right_aligned(),
+            );
+        }
+        if !self.loaded {
+            // The daemon walks every volume, which can take a while
+            let loading = Paragraph::new(Span::styled("Measuring…", Style::default().fg(Color::DarkGray)));
+            f.render_widget(loading.block(block), area);
+            return;
+        }
+
+        let header = Row::new(["TYPE", "TOTAL", "ACTIVE", "SIZE", "RECLAIMABLE"])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let selected = self.selection.index(&self.summaries);
+        let rows = self.summaries.iter().enumerate().map(|(index, summary)| {
+            let row = Row::new([
+                summary.category.title().to_string(),
+                summary.items.len().to_string(),
+                summary.active.to_string(),
+                format_bytes(summary.size),
+                format_reclaimable(summary.reclaimable, summary.size),
+            ]);
+            if index == selected {
+                row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                row
+            }
+        });
+        let widths = [
+            Constraint::Min(14),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Min(18),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), chunks[0]);
+
+        if let Some(summary) = self.selection.get(&self.summaries) {
+            Self::render_items(f, chunks[1], summary);
+        }
+        if let Some(category) = self.confirm {
+            let reclaimable = self
+                .summaries
+                .iter()
+                .find(|s| s.category == category)
+                .map_or(0, |s| s.reclaimable);
+            render_dialog(
+                f,
+                &format!("Remove {}? Frees up to {}.", category.prune_target(), format_bytes(reclaimable)),
+            );
+        }
+    }
+
+    /// The selected category's items, biggest first; reclaimable ones in
+    /// yellow.
+    fn render_items(f: &mut Frame, area: Rect, summary: &Summary) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray))
+            .title(format!(" {} ", summary.category.title()));
+        if summary.items.is_empty() {
+            let empty = Paragraph::new(Span::styled("Nothing here", Style::default().fg(Color::DarkGray)));
+            f.render_widget(empty.block(block), area);
+            return;
+        }
+        let header = Row::new(["NAME", "SIZE", summary.category.detail(), "RECLAIMABLE"])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = summary.items.iter().map(|item| {
+            let row = Row::new([
+                item.name.clone(),
+                format_bytes(item.size),
+                item.detail.clone(),
+                if item.reclaimable { "yes" } else { "" }.to_string(),
+            ]);
+            if item.reclaimable {
+                row.style(Style::default().fg(Color::Yellow))
+            } else {
+                row
+            }
+        });
+        let widths = [
+            Constraint::Min(24),
+            Constraint::Length(11),
+            Constraint::Length(12),
+            Constraint::Length(11),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+}
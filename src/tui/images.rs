@@ -0,0 +1,468 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{cell::Cell, collections::HashMap, sync::Arc};
+
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::{Constraint, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph, Row, Table},
+    },
+};
+
+use super::{Selection, TaskSet, confirmed, render_dialog, scroll};
+use crate::docker::{
+    Connection, Container,
+    models::{ImageHistory, ImageSummary},
+};
+use crate::error::WhaleError;
+use crate::utils::{format_bytes, format_duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortColumn {
+    Size,
+    Created,
+}
+
+/// Sort images by `column`; ties keep ID order.
+pub(crate) fn sort_images(images: &mut [ImageSummary], column: SortColumn, descending: bool) {
+    images.sort_by(|a, b| {
+        let ordering = match column {
+            SortColumn::Size => a.size.cmp(&b.size),
+            SortColumn::Created => a.created.cmp(&b.created),
+        };
+        if descending { ordering.reverse() } else { ordering }.then_with(|| a.id.cmp(&b.id))
+    });
+}
+
+/// How many containers were created from each image, by image ID.
+pub(crate) fn count_usage(containers: &[Container]) -> HashMap<String, usize> {
+    let mut usage = HashMap::new();
+    for container in containers {
+        *usage.entry(container.image_id.clone()).or_default() += 1;
+    }
+    usage
+}
+
+/// A layer's `CreatedBy` as the Dockerfile line that made it, e.g.
+/// `/bin/sh -c #(nop)  CMD ["nginx"]` becomes `CMD ["nginx"]`.
+pub(crate) fn instruction(created_by: &str) -> String {
+    let created_by = created_by.trim();
+    if let Some(nop) = created_by.strip_prefix("/bin/sh -c #(nop)") {
+        nop.trim().to_string()
+    } else if let Some(run) = created_by.strip_prefix("/bin/sh -c ") {
+        format!("RUN {}", run.trim())
+    } else {
+        created_by.to_string()
+    }
+}
+
+/// The first tag, with a count of the others.
+fn image_name(image: &ImageSummary) -> String {
+    let tags: Vec<&String> = image.repo_tags.iter().filter(|tag| *tag != "<none>:<none>").collect();
+    match tags.as_slice() {
+        [] => "<none>".to_string(),
+        [tag] => (*tag).clone(),
+        [tag, rest @ ..] => format!("{tag} (+{})", rest.len()),
+    }
+}
+
+enum Message {
+    Images(Result<Vec<ImageSummary>, WhaleError>),
+    History(String, Result<Vec<ImageHistory>, WhaleError>),
+    /// Outcome of a removal or prune, and whether it failed.
+    Done(String, bool),
+}
+
+/// A removal waiting for the user to confirm it.
+enum Confirm {
+    Remove { id: String, name: String, containers: usize },
+    Prune { count: usize, size: u64 },
+}
+
+/// Layers of one image.
+struct History {
+    id: String,
+    name: String,
+    layers: Option<Result<Vec<ImageHistory>, String>>,
+    selected: usize,
+    top: Cell<usize>,
+}
+
+/// The local images, with the number of containers using each.
+pub(crate) struct ImagesView {
+    client: Arc<dyn Connection>,
+    images: Vec<ImageSummary>,
+    /// Containers per image ID, from the container list.
+    usage: HashMap<String, usize>,
+    error: Option<String>,
+    loaded: bool,
+    tasks: TaskSet<Message>,
+    sort: SortColumn,
+    descending: bool,
+    selection: Selection<ImageSummary, String>,
+    history: Option<History>,
+    confirm: Option<Confirm>,
+    /// Outcome of the last removal or prune.
+    message: Option<(String, bool)>,
+}
+
+impl ImagesView {
+    /// List the images in the background.
+    pub(crate) fn open(client: Arc<dyn Connection>, containers: &[Container]) -> Self {
+        let mut view = Self {
+            client,
+            images: Vec::new(),
+            usage: count_usage(containers),
+            error: None,
+            loaded: false,
+            tasks: TaskSet::default(),
+            sort: SortColumn::Created,
+            descending: true,
+            selection: Selection::new(|image: &ImageSummary| &image.id),
+            history: None,
+            confirm: None,
+            message: None,
+        };
+        view.reload();
+        view
+    }
+
+    fn reload(&mut self) {
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move { Message::Images(client.list_images().await) });
+    }
+
+    /// Take finished requests, and recount usage from the latest container list.
+    pub(crate) fn update(&mut self, containers: &[Container]) {
+        self.usage = count_usage(containers);
+        while let Some(message) = self.tasks.try_recv() {
+            match message {
+                Message::Images(result) => {
+                    self.loaded = true;
+                    match result {
+                        Ok(images) => {
+                            self.images = images;
+                            self.error = None;
+                            self.sort();
+                        }
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+                Message::History(id, result) => {
+                    if let Some(history) = self.history.as_mut().filter(|history| history.id == id) {
+                        history.layers = Some(result.map_err(|e| e.to_string()));
+                    }
+                }
+                Message::Done(message, error) => {
+                    self.message = Some((message, error));
+                    self.reload();
+                }
+            }
+        }
+    }
+
+    /// Whether the layer breakdown is shown instead of the list.
+    pub(crate) fn is_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Whether a removal is waiting for confirmation.
+    pub(crate) fn is_confirming(&self) -> bool {
+        self.confirm.is_some()
+    }
+
+    fn sort(&mut self) {
+        sort_images(&mut self.images, self.sort, self.descending);
+        self.selection.retain(&self.images);
+    }
+
+    /// Sort by `column`, or reverse the order if it already is. Biggest and
+    /// newest come first.
+    fn sort_by(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = true;
+        }
+        self.sort();
+    }
+
+    fn open_history(&mut self) {
+        let Some(image) = self.selection.get(&self.images) else {
+            return;
+        };
+        let id = image.id.clone();
+        self.history = Some(History {
+            id: id.clone(),
+            name: image_name(image),
+            layers: None,
+            selected: 0,
+            top: Cell::new(0),
+        });
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move {
+            let result = client.image_history(&id).await;
+            Message::History(id, result)
+        });
+    }
+
+    fn request_remove(&mut self) {
+        if let Some(image) = self.selection.get(&self.images) {
+            self.confirm = Some(Confirm::Remove {
+                id: image.id.clone(),
+                name: if image.is_dangling() { image.short_id().to_string() } else { image_name(image) },
+                containers: self.usage.get(&image.id).copied().unwrap_or_default(),
+            });
+        }
+    }
+
+    fn request_prune(&mut self) {
+        let dangling = self
+            .images
+            .iter()
+            .filter(|i| i.is_dangling() && !self.usage.contains_key(&i.id));
+        let (count, size) = dangling.fold((0, 0), |(count, size), i| (count + 1, size + i.size));
+        if count == 0 {
+            self.message = Some(("No unused dangling images to prune".to_string(), false));
+        } else {
+            self.confirm = Some(Confirm::Prune { count, size });
+        }
+    }
+
+    fn run(&mut self, confirm: Confirm) {
+        let client = Arc::clone(&self.client);
+        match confirm {
+            Confirm::Remove { id, name, .. } => self.tasks.spawn(async move {
+                match client.remove_image(&id).await {
+                    Ok(()) => Message::Done(format!("{name} removed"), false),
+                    Err(e) => Message::Done(format!("Failed to remove {name}: {e}"), true),
+                }
+            }),
+            Confirm::Prune { .. } => self.tasks.spawn(async move {
+                match client.prune_images(false).await {
+                    Ok(report) => Message::Done(
+                        format!(
+                            "Pruned {} images, reclaimed {}",
+                            report.deleted.len(),
+                            format_bytes(report.space_reclaimed)
+                        ),
+                        false,
+                    ),
+                    Err(e) => Message::Done(format!("Failed to prune images: {e}"), true),
+                }
+            }),
+        }
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.confirm.is_some() {
+            if let Some(confirm) = confirmed(&mut self.confirm, code) {
+                self.run(confirm);
+            }
+            return false;
+        }
+        self.message = None;
+        let page = self.selection.page();
+        if let Some(history) = &mut self.history {
+            let layers = history.layers.as_ref().and_then(|layers| layers.as_ref().ok()).map_or(0, Vec::len);
+            let last = layers.saturating_sub(1);
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Backspace => self.history = None,
+                KeyCode::Up | KeyCode::Char('k') => history.selected = history.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => history.selected = (history.selected + 1).min(last),
+                KeyCode::PageUp => history.selected = history.selected.saturating_add_signed(-page),
+                KeyCode::PageDown => history.selected = history.selected.saturating_add_signed(page).min(last),
+                KeyCode::Home | KeyCode::Char('g') => history.selected = 0,
+                KeyCode::End | KeyCode::Char('G') => history.selected = last,
+                _ => {}
+            }
+            return false;
+        }
+        if self.selection.handle_key(code, &self.images) {
+            return false;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Char('s') => self.sort_by(SortColumn::Size),
+            KeyCode::Char('a') => self.sort_by(SortColumn::Created),
+            KeyCode::Char('r') => self.reload(),
+            KeyCode::Enter => self.open_history(),
+            KeyCode::Char('D') => self.request_remove(),
+            KeyCode::Char('P') => self.request_prune(),
+            _ => {}
+        }
+        false
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        // Borders and the header row
+        let height = usize::from(area.height.saturating_sub(3)).max(1);
+        match &self.history {
+            Some(history) => Self::render_history(f, area, history, height),
+            None => self.render_list(f, area, height),
+        }
+        match &self.confirm {
+            Some(Confirm::Remove { name, containers: 0, .. }) => render_dialog(f, &format!("Remove image {name}?")),
+            Some(Confirm::Remove { name, containers, .. }) => render_dialog(
+                f,
+                &format!("Remove image {name}? {containers} containers use it, so this will fail"),
+            ),
+            Some(Confirm::Prune { count, size }) => render_dialog(
+                f,
+                &format!("Prune {count} dangling images ({})?", format_bytes(*size)),
+            ),
+            None => {}
+        }
+    }
+
+    fn render_list(&self, f: &mut Frame, area: Rect, height: usize) {
+        let dangling = self.images.iter().filter(|i| i.is_dangling()).count();
+        let total: u64 = self.images.iter().map(|i| i.size).sum();
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Images ")
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} images · {} · {dangling} dangling ", self.images.len(), format_bytes(total)),
+                    Style::default().fg(Color::Gray),
+                ))
+                .right_aligned(),
+            );
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {error} "),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if let Some((message, error)) = &self.message {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {message} "),
+                    Style::default().fg(if *error { Color::Red } else { Color::Green }),
+                ))
+                .right_aligned(),
+            );
+        }
+        if !self.loaded {
+            let loading = Paragraph::new(Span::styled("Loading…", Style::default().fg(Color::DarkGray)));
+            f.render_widget(loading.block(block), area);
+            return;
+        }
+
+        let selected = self.selection.index(&self.images);
+        let top = self.selection.scroll(&self.images, height);
+        let arrow = |column: SortColumn| match (column == self.sort, self.descending) {
+            (true, true) => " ▼",
+            (true, false) => " ▲",
+            (false, _) => "",
+        };
+        let header = Row::new([
+            "REPOSITORY:TAG".to_string(),
+            "IMAGE ID".to_string(),
+            format!("SIZE{}", arrow(SortColumn::Size)),
+            format!("CREATED{}", arrow(SortColumn::Created)),
+            "CONTAINERS".to_string(),
+        ])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = self
+            .images
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(index, image)| {
+                let containers = self.usage.get(&image.id).copied().unwrap_or_default();
+                let row = Row::new([
+                    if image.is_dangling() { "<none> (dangling)".to_string() } else { image_name(image) },
+                    image.short_id().to_string(),
+                    format_bytes(image.size),
+                    format_duration(image.created),
+                    containers.to_string(),
+                ]);
+                let row = if image.is_dangling() {
+                    row.style(Style::default().fg(Color::Yellow))
+                } else if containers == 0 {
+                    row.style(Style::default().fg(Color::Gray))
+                } else {
+                    row
+                };
+                if index == selected {
+                    row.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    row
+                }
+            });
+        let widths = [
+            Constraint::Min(20),
+            Constraint::Length(12),
+            Constraint::Length(11),
+            Constraint::Length(16),
+            Constraint::Length(10),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+
+    fn render_history(f: &mut Frame, area: Rect, history: &History, height: usize) {
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" History: {} ", history.name));
+        let layers = match &history.layers {
+            None => {
+                let loading = Paragraph::new(Span::styled("Loading…", Style::default().fg(Color::DarkGray)));
+                f.render_widget(loading.block(block), area);
+                return;
+            }
+            Some(Err(error)) => {
+                let failed = Paragraph::new(Span::styled(
+                    format!("Failed to read the history of {}: {error}", history.name),
+                    Style::default().fg(Color::Red),
+                ));
+                f.render_widget(failed.block(block), area);
+                return;
+            }
+            Some(Ok(layers)) => layers,
+        };
+        let total: u64 = layers.iter().map(|layer| layer.size).sum();
+        block = block.title(
+            Line::from(Span::styled(
+                format!(" {} layers · {} ", layers.len(), format_bytes(total)),
+                Style::default().fg(Color::Gray),
+            ))
+            .right_aligned(),
+        );
+
+        let selected = history.selected.min(layers.len().saturating_sub(1));
+        let top = scroll(&history.top, selected, height, layers.len());
+        let header = Row::new(["SIZE", "CREATED", "CREATED BY"])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = layers
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(index, layer)| {
+                let row = Row::new([
+                    format_bytes(layer.size),
+                    format_duration(layer.created),
+                    instruction(&layer.created_by),
+                ]);
+                // Empty layers only change metadata
+                let row = if layer.size == 0 { row.style(Style::default().fg(Color::DarkGray)) } else { row };
+                if index == selected {
+                    row.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    row
+                }
+            });
+        let widths = [Constraint::Length(11), Constraint::Length(16), Constraint::Min(20)];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+}
@@ -0,0 +1,669 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    io::Write,
+    sync::{Arc, mpsc},
+};
+
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph},
+    },
+};
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use super::{
+    scroll,
+    tree::{Tree, TreeItem, TreeSource},
+};
+use crate::docker::{Connection, models::ContainerDetails};
+use crate::error::WhaleError;
+use crate::utils::{base64_encode, format_bytes};
+
+/// One visible row of a [`JsonTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TreeRow {
+    /// JSON pointer of the node.
+    pub pointer: String,
+    pub depth: usize,
+    /// Object key, or `[index]` in arrays.
+    pub key: String,
+    /// Number of children of objects and arrays; `None` for scalars.
+    pub children: Option<usize>,
+}
+
+impl TreeItem for TreeRow {
+    fn id(&self) -> &str {
+        &self.pointer
+    }
+
+    fn has_children(&self) -> bool {
+        self.children.is_some()
+    }
+}
+
+impl TreeSource for Value {
+    type Row = TreeRow;
+
+    fn rows(&self, expanded: &HashSet<String>) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        push_children(self, "", 0, expanded, &mut rows);
+        rows
+    }
+
+    fn branches(&self) -> HashSet<String> {
+        let mut branches = HashSet::new();
+        walk(self, "", &mut |pointer, _, value| {
+            if value.is_object() || value.is_array() {
+                branches.insert(pointer.to_string());
+            }
+        });
+        branches
+    }
+}
+
+/// A JSON document as a tree of collapsible objects and arrays, with nodes
+/// addressed by JSON pointer.
+pub(crate) type JsonTree = Tree<Value>;
+
+impl JsonTree {
+    /// Pointers of every node, visible or not, whose key contains `query`
+    /// ignoring case, in document order.
+    pub(crate) fn find(&self, query: &str) -> Vec<String> {
+        let query = query.to_lowercase();
+        let mut matches = Vec::new();
+        walk(self.source(), "", &mut |pointer, key, _| {
+            if key.is_some_and(|key| key.to_lowercase().contains(&query)) {
+                matches.push(pointer.to_string());
+            }
+        });
+        matches
+    }
+
+    pub(crate) fn value(&self, pointer: &str) -> Option<&Value> {
+        self.source().pointer(pointer)
+    }
+
+    /// The selected value as it would be copied: strings without quotes,
+    /// anything else as pretty-printed JSON.
+    pub(crate) fn selected_text(&self) -> Option<String> {
+        match self.value(&self.selected_row()?.pointer)? {
+            Value::String(s) => Some(s.clone()),
+            value => serde_json::to_string_pretty(value).ok(),
+        }
+    }
+}
+
+fn push_children(value: &Value, pointer: &str, depth: usize, expanded: &HashSet<String>, rows: &mut Vec<TreeRow>) {
+    let children: Vec<(String, String, &Value)> = match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, child)| (key.clone(), format!("{pointer}/{}", escape(key)), child))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, child)| (format!("[{index}]"), format!("{pointer}/{index}"), child))
+            .collect(),
+        _ => return,
+    };
+    for (key, child_pointer, child) in children {
+        let children = child_count(child);
+        let open = children.is_some() && expanded.contains(&child_pointer);
+        rows.push(TreeRow {
+            pointer: child_pointer.clone(),
+            depth,
+            key,
+            children,
+        });
+        if open {
+            push_children(child, &child_pointer, depth + 1, expanded, rows);
+        }
+    }
+}
+
+/// Call `f` with the pointer, object key and value of every node below
+/// `value`, depth first.
+fn walk(value: &Value, pointer: &str, f: &mut impl FnMut(&str, Option<&str>, &Value)) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_pointer = format!("{pointer}/{}", escape(key));
+                f(&child_pointer, Some(key), child);
+                walk(child, &child_pointer, f);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                let child_pointer = format!("{pointer}/{index}");
+                f(&child_pointer, None, child);
+                walk(child, &child_pointer, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn child_count(value: &Value) -> Option<usize> {
+    match value {
+        Value::Object(map) => Some(map.len()),
+        Value::Array(items) => Some(items.len()),
+        _ => None,
+    }
+}
+
+/// Escape a key for use in a JSON pointer (RFC 6901).
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Put `text` on the clipboard of the terminal, which works over SSH too,
+/// with an OSC 52 escape sequence.
+pub(crate) fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    stdout.flush()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Summary,
+    Tree,
+}
+
+enum Document {
+    Loading,
+    Failed(String),
+    Ready {
+        summary: Vec<Line<'static>>,
+        tree: JsonTree,
+    },
+}
+
+/// Key search over the tree.
+#[derive(Default)]
+struct Search {
+    /// What's being typed, while the prompt is open.
+    input: Option<String>,
+    query: String,
+    matches: Vec<String>,
+    current: usize,
+}
+
+/// Full-screen view of a container's inspect document: a summary of the
+/// usual questions, and the whole document as a tree.
+pub(crate) struct InspectView {
+    name: String,
+    document: Document,
+    rx: mpsc::Receiver<Result<Value, WhaleError>>,
+    task: JoinHandle<()>,
+    tab: Tab,
+    /// First summary line on screen.
+    summary_top: usize,
+    /// First tree row on screen, kept around the selection on render.
+    tree_top: Cell<usize>,
+    /// Rows that fit in the view, from the last render.
+    height: Cell<usize>,
+    search: Search,
+    /// Outcome of the last copy.
+    message: Option<(String, bool)>,
+}
+
+impl InspectView {
+    /// Fetch the inspect document of a container in the background.
+    pub(crate) fn open(client: Arc<dyn Connection>, id: String, name: String) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let task = tokio::spawn(async move {
+            let _ = tx.send(client.inspect_container_json(&id).await);
+        });
+        Self {
+            name,
+            document: Document::Loading,
+            rx,
+            task,
+            tab: Tab::Summary,
+            summary_top: 0,
+            tree_top: Cell::new(0),
+            height: Cell::new(1),
+            search: Search::default(),
+            message: None,
+        }
+    }
+
+    /// Take the document once it has arrived.
+    pub(crate) fn update(&mut self) {
+        let Ok(result) = self.rx.try_recv() else {
+            return;
+        };
+        self.document = match result {
+            Ok(value) => match serde_json::from_value::<ContainerDetails>(value.clone()) {
+                Ok(details) => Document::Ready {
+                    summary: summary_lines(&details),
+                    tree: JsonTree::new(value),
+                },
+                Err(e) => Document::Failed(format!("unexpected inspect document: {e}")),
+            },
+            Err(e) => Document::Failed(e.to_string()),
+        };
+    }
+
+    pub(crate) fn is_searching(&self) -> bool {
+        self.search.input.is_some()
+    }
+
+    pub(crate) fn is_tree(&self) -> bool {
+        self.tab == Tab::Tree
+    }
+
+    fn tree_mut(&mut self) -> Option<&mut JsonTree> {
+        match &mut self.document {
+            Document::Ready { tree, .. } => Some(tree),
+            _ => None,
+        }
+    }
+
+    fn set_query(&mut self, query: &str) {
+        self.search.query = query.to_string();
+        self.search.current = 0;
+        let Some(tree) = self.tree_mut() else {
+            return;
+        };
+        let matches = if query.is_empty() { Vec::new() } else { tree.find(query) };
+        if let Some(first) = matches.first() {
+            tree.reveal(first);
+        }
+        self.search.matches = matches;
+    }
+
+    fn clear_search(&mut self) {
+        self.search = Search::default();
+    }
+
+    /// Select the next or previous match, wrapping around.
+    fn jump(&mut self, forward: bool) {
+        let total = self.search.matches.len();
+        if total == 0 {
+            return;
+        }
+        self.search.current = if forward {
+            (self.search.current + 1) % total
+        } else {
+            (self.search.current + total - 1) % total
+        };
+        let pointer = self.search.matches[self.search.current].clone();
+        if let Some(tree) = self.tree_mut() {
+            tree.reveal(&pointer);
+        }
+    }
+
+    fn handle_search_key(&mut self, mut input: String, code: KeyCode) {
+        match code {
+            KeyCode::Enter if input.is_empty() => self.clear_search(),
+            KeyCode::Enter => {}
+            KeyCode::Esc => self.clear_search(),
+            KeyCode::Backspace => {
+                input.pop();
+                self.set_query(&input);
+                self.search.input = Some(input);
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                self.set_query(&input);
+                self.search.input = Some(input);
+            }
+            _ => self.search.input = Some(input),
+        }
+    }
+
+    fn copy_selected(&mut self) {
+        let Document::Ready { tree, .. } = &self.document else {
+            return;
+        };
+        let (Some(row), Some(text)) = (tree.rows().get(tree.selected()), tree.selected_text()) else {
+            return;
+        };
+        let pointer = row.pointer.clone();
+        self.message = Some(match copy_to_clipboard(&text) {
+            Ok(()) => (format!("copied {pointer}"), false),
+            Err(e) => (format!("copy failed: {e}"), true),
+        });
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        if let Some(input) = self.search.input.take() {
+            self.handle_search_key(input, code);
+            return false;
+        }
+        self.message = None;
+        let page = isize::try_from(self.height.get().max(1)).unwrap_or(isize::MAX);
+        match code {
+            KeyCode::Esc if !self.search.query.is_empty() => self.clear_search(),
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.tab = if self.tab == Tab::Summary { Tab::Tree } else { Tab::Summary };
+            }
+            _ if self.tab == Tab::Summary => self.handle_summary_key(code, page),
+            _ => self.handle_tree_key(code, page),
+        }
+        false
+    }
+
+    fn handle_summary_key(&mut self, code: KeyCode, page: isize) {
+        let lines = match &self.document {
+            Document::Ready { summary, .. } => summary.len(),
+            _ => 0,
+        };
+        let last_top = lines.saturating_sub(self.height.get());
+        let scroll = |top: usize, delta: isize| top.saturating_add_signed(delta).min(last_top);
+        self.summary_top = match code {
+            KeyCode::Up | KeyCode::Char('k') => scroll(self.summary_top, -1),
+            KeyCode::Down | KeyCode::Char('j') => scroll(self.summary_top, 1),
+            KeyCode::PageUp => scroll(self.summary_top, -page),
+            KeyCode::PageDown => scroll(self.summary_top, page),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last_top,
+            _ => self.summary_top,
+        };
+    }
+
+    fn handle_tree_key(&mut self, code: KeyCode, page: isize) {
+        match code {
+            KeyCode::Char('/') => {
+                self.clear_search();
+                self.search.input = Some(String::new());
+                return;
+            }
+            KeyCode::Char('n' | 'N') => {
+                self.jump(code == KeyCode::Char('n'));
+                return;
+            }
+            KeyCode::Char('y') => {
+                self.copy_selected();
+                return;
+            }
+            _ => {}
+        }
+        let Some(tree) = self.tree_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => tree.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => tree.move_by(1),
+            KeyCode::PageUp => tree.move_by(-page),
+            KeyCode::PageDown => tree.move_by(page),
+            KeyCode::Home | KeyCode::Char('g') => tree.move_to(0),
+            KeyCode::End | KeyCode::Char('G') => tree.move_to(usize::MAX),
+            KeyCode::Right | KeyCode::Char('l') => tree.expand(),
+            KeyCode::Left | KeyCode::Char('h') => tree.collapse(),
+            KeyCode::Enter | KeyCode::Char(' ') => tree.toggle(),
+            KeyCode::Char('E') => tree.expand_all(),
+            KeyCode::Char('C') => tree.collapse_all(),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        let height = usize::from(area.height.saturating_sub(2)).max(1);
+        self.height.set(height);
+        let content = match &self.document {
+            Document::Loading => vec![Line::from(Span::styled("Loading…", Style::default().fg(Color::DarkGray)))],
+            Document::Failed(error) => vec![Line::from(Span::styled(
+                format!("Failed to inspect {}: {error}", self.name),
+                Style::default().fg(Color::Red),
+            ))],
+            Document::Ready { summary, .. } if self.tab == Tab::Summary => {
+                summary.iter().skip(self.summary_top).take(height).cloned().collect()
+            }
+            Document::Ready { tree, .. } => self.tree_lines(tree, height),
+        };
+
+        let tab_style = |tab| {
+            if self.tab == tab {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::Gray)
+            }
+        };
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Inspect: {} ", self.name))
+            .title(
+                Line::from(vec![
+                    Span::styled(" summary ", tab_style(Tab::Summary)),
+                    Span::raw(" "),
+                    Span::styled(" tree ", tab_style(Tab::Tree)),
+                    Span::raw(" "),
+                ])
+                .right_aligned(),
+            );
+        if let Some(search) = self.search_line() {
+            block = block.title_bottom(search);
+        }
+        if let Some((message, error)) = &self.message {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {message} "),
+                    Style::default().fg(if *error { Color::Red } else { Color::Green }),
+                ))
+                .right_aligned(),
+            );
+        }
+        f.render_widget(Paragraph::new(content).block(block), area);
+    }
+
+    /// The rows on screen, scrolled to keep the selection visible.
+    fn tree_lines(&self, tree: &JsonTree, height: usize) -> Vec<Line<'static>> {
+        let selected = tree.selected();
+        let top = scroll(&self.tree_top, selected, height, tree.rows().len());
+
+        let current = self.search.matches.get(self.search.current);
+        tree.rows()
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(index, row)| {
+                let matched = self.search.matches.contains(&row.pointer);
+                let key_style = if current == Some(&row.pointer) {
+                    Style::default().fg(Color::Black).bg(Color::Yellow)
+                } else if matched {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Cyan)
+                };
+                let marker = match row.children {
+                    Some(_) if tree.is_expanded(&row.pointer) => "▾ ",
+                    Some(_) => "▸ ",
+                    None => "  ",
+                };
+                let mut spans = vec![
+                    Span::raw(format!("{}{marker}", "  ".repeat(row.depth))),
+                    Span::styled(row.key.clone(), key_style),
+                    Span::raw(": "),
+                ];
+                spans.push(match (row.children, tree.value(&row.pointer)) {
+                    (Some(count), Some(Value::Array(_))) => {
+                        Span::styled(format!("[{count}]"), Style::default().fg(Color::DarkGray))
+                    }
+                    (Some(count), _) => Span::styled(format!("{{{count}}}"), Style::default().fg(Color::DarkGray)),
+                    (None, Some(value)) => scalar_span(value),
+                    (None, None) => Span::raw(""),
+                });
+                let line = Line::from(spans);
+                if index == selected {
+                    line.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    /// The search prompt while typing, or the match count after.
+    fn search_line(&self) -> Option<Line<'static>> {
+        let mut spans = Vec::new();
+        if let Some(input) = &self.search.input {
+            spans.push(Span::styled(format!(" /{input}▏"), Style::default().fg(Color::Yellow)));
+        } else if !self.search.query.is_empty() {
+            spans.push(Span::styled(format!(" /{}", self.search.query), Style::default().fg(Color::Cyan)));
+        } else {
+            return None;
+        }
+        let count = match self.search.matches.len() {
+            0 => " no matching keys ".to_string(),
+            total => format!(" {}/{total} ", self.search.current + 1),
+        };
+        spans.push(Span::styled(count, Style::default().fg(Color::Gray)));
+        Some(Line::from(spans))
+    }
+}
+
+impl Drop for InspectView {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn scalar_span(value: &Value) -> Span<'static> {
+    let color = match value {
+        Value::String(_) => Color::Green,
+        Value::Number(_) => Color::Magenta,
+        Value::Bool(_) => Color::Yellow,
+        _ => Color::DarkGray,
+    };
+    Span::styled(value.to_string(), Style::default().fg(color))
+}
+
+/// The summary tab: what is usually looked up in an inspect document.
+pub(crate) fn summary_lines(details: &ContainerDetails) -> Vec<Line<'static>> {
+    let config = &details.config;
+    let host = &details.host_config;
+    let mut lines = vec![
+        heading("General"),
+        field("Image", config.image.clone()),
+        field("Entrypoint", or_dash(&config.entrypoint.join(" "))),
+        field("Command", or_dash(&config.cmd.join(" "))),
+        field("Restart policy", restart_policy(details)),
+        Line::from(""),
+        heading("Resources"),
+        field(
+            "Memory",
+            u64::try_from(host.memory).ok().filter(|m| *m > 0).map_or_else(|| "unlimited".to_string(), format_bytes),
+        ),
+        field("CPUs", if host.nano_cpus > 0 { format_cpus(host.nano_cpus) } else { "unlimited".to_string() }),
+        field("CPU shares", if host.cpu_shares > 0 { host.cpu_shares.to_string() } else { "1024 (default)".to_string() }),
+        field(
+            "PIDs",
+            host.pids_limit.filter(|limit| *limit > 0).map_or_else(|| "unlimited".to_string(), |limit| limit.to_string()),
+        ),
+    ];
+
+    section(
+        &mut lines,
+        "Environment",
+        config.env.iter().map(|var| match var.split_once('=') {
+            Some((key, value)) => pair(key, value),
+            None => item(var),
+        }),
+    );
+    section(
+        &mut lines,
+        "Mounts",
+        details.mounts.iter().map(|mount| {
+            let source = mount.name.clone().filter(|_| mount.kind == "volume").unwrap_or_else(|| mount.source.clone());
+            let mode = if mount.rw { "rw" } else { "ro" };
+            item(&format!("{} {source} → {} ({mode})", mount.kind, mount.destination))
+        }),
+    );
+    section(
+        &mut lines,
+        "Networks",
+        details.network_settings.networks.iter().map(|(name, network)| {
+            let mut text = format!("{name}  {}", or_dash(&network.ip_address));
+            if !network.gateway.is_empty() {
+                text.push_str("  gateway ");
+                text.push_str(&network.gateway);
+            }
+            if !network.mac_address.is_empty() {
+                text.push_str("  mac ");
+                text.push_str(&network.mac_address);
+            }
+            item(&text)
+        }),
+    );
+    section(
+        &mut lines,
+        "Labels",
+        config.labels.iter().map(|(key, value)| pair(key, value)),
+    );
+    lines
+}
+
+/// A blank line, a heading with the entry count, and the entries.
+fn section(lines: &mut Vec<Line<'static>>, title: &str, entries: impl Iterator<Item = Line<'static>>) {
+    let entries: Vec<_> = entries.collect();
+    lines.push(Line::from(""));
+    lines.push(heading(&format!("{title} ({})", entries.len())));
+    if entries.is_empty() {
+        lines.push(Line::from(Span::styled("  none", Style::default().fg(Color::DarkGray))));
+    }
+    lines.extend(entries);
+}
+
+fn heading(title: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        title.to_string(),
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn field(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("  {label:<16}"), Style::default().fg(Color::Gray)),
+        Span::raw(value),
+    ])
+}
+
+fn pair(key: &str, value: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("  {key}"), Style::default().fg(Color::Yellow)),
+        Span::raw(format!("={value}")),
+    ])
+}
+
+fn item(text: &str) -> Line<'static> {
+    Line::from(Span::raw(format!("  {text}")))
+}
+
+fn or_dash(s: &str) -> String {
+    if s.is_empty() { "-".to_string() } else { s.to_string() }
+}
+
+fn restart_policy(details: &ContainerDetails) -> String {
+    let policy = &details.host_config.restart_policy;
+    match policy.name.as_str() {
+        "" | "no" => "no".to_string(),
+        "on-failure" if policy.maximum_retry_count > 0 => {
+            format!("on-failure (at most {} retries)", policy.maximum_retry_count)
+        }
+        name => name.to_string(),
+    }
+}
+
+/// Billionths of a CPU as a decimal, e.g. `1.5`.
+fn format_cpus(nano_cpus: i64) -> String {
+    let whole = nano_cpus / 1_000_000_000;
+    let fraction = format!("{:09}", nano_cpus % 1_000_000_000);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() { whole.to_string() } else { format!("{whole}.{fraction}") }
+}
@@ -0,0 +1,638 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    ops::Range,
+    sync::{mpsc, Arc},
+};
+
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph},
+    },
+};
+use regex::{Regex, RegexBuilder};
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use crate::docker::{Connection, LogLine, LogOptions, LogStream, MergedLog, logs::merge_logs};
+
+/// Lines kept per container; the oldest are dropped as new ones arrive.
+pub(crate) const LOG_BUFFER_LINES: usize = 10_000;
+
+/// Colours of container names in a merged pane, by position.
+const SOURCE_COLORS: [Color; 6] = [Color::Cyan, Color::Magenta, Color::Green, Color::Yellow, Color::Blue, Color::LightCyan];
+
+/// Bounded line storage, oldest first.
+pub(crate) struct LogBuffer<T> {
+    lines: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> LogBuffer<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Append a line, returning the oldest if it was dropped for it.
+    pub(crate) fn push(&mut self, line: T) -> Option<T> {
+        self.lines.push_back(line);
+        if self.lines.len() > self.capacity { self.lines.pop_front() } else { None }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        self.lines.get(index)
+    }
+
+    /// Index of the first line `pred` is false for, which must be false for
+    /// every line after it too.
+    pub(crate) fn partition_point(&self, pred: impl FnMut(&T) -> bool) -> usize {
+        self.lines.partition_point(pred)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.lines.iter()
+    }
+}
+
+/// A line and its sequence number, counting from the first line the pane
+/// got from any container. Positions are kept as sequence numbers so they
+/// survive dropping old lines.
+struct LogEntry {
+    seq: u64,
+    line: LogLine,
+}
+
+/// Search state: the pattern being typed, and what the last one matched.
+#[derive(Default)]
+struct Search {
+    /// Pattern typed after `/`, until Enter or Esc.
+    input: Option<String>,
+    regex: Option<Regex>,
+    error: Option<String>,
+    /// Sequence numbers of matching lines, oldest first.
+    matches: VecDeque<u64>,
+    /// The match last jumped to.
+    current: Option<u64>,
+    /// Show only the matching lines.
+    filter: bool,
+}
+
+/// Full-screen log pane following one or more containers, interleaved by
+/// timestamp.
+pub(crate) struct LogView {
+    /// Names of the containers, by source index.
+    sources: Vec<String>,
+    /// Lines of each container, by source index; merged in arrival order
+    /// when shown.
+    buffers: Vec<LogBuffer<LogEntry>>,
+    /// Sequence number of the next line.
+    next_seq: u64,
+    rx: mpsc::Receiver<MergedLog>,
+    task: JoinHandle<()>,
+    /// Keep the newest line on screen as output arrives.
+    follow: bool,
+    /// First row on screen while paused.
+    top: usize,
+    /// Rows that fit in the pane, from the last render.
+    height: Cell<usize>,
+    /// Streams that haven't ended yet.
+    open_streams: usize,
+    errors: Vec<String>,
+    search: Search,
+}
+
+impl LogView {
+    /// Start following the logs of `containers`, as `(id, name)`, in the
+    /// background.
+    pub(crate) fn open(client: Arc<dyn Connection>, containers: Vec<(String, String)>, options: LogOptions) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (ids, sources): (Vec<_>, Vec<_>) = containers.into_iter().unzip();
+        let task = tokio::spawn(merge_logs(client, ids, options, move |message| {
+            let _ = tx.send(message);
+        }));
+        Self {
+            open_streams: sources.len(),
+            errors: Vec::new(),
+            buffers: sources.iter().map(|_| LogBuffer::new(LOG_BUFFER_LINES)).collect(),
+            next_seq: 0,
+            sources,
+            rx,
+            task,
+            follow: true,
+            top: 0,
+            height: Cell::new(1),
+            search: Search::default(),
+        }
+    }
+
+    /// Take the lines that arrived since the last call.
+    pub(crate) fn update(&mut self) {
+        while let Ok(message) = self.rx.try_recv() {
+            match message {
+                MergedLog::Line(source, mut line) => {
+                    if let Some(pretty) = pretty_json(&line.text) {
+                        line.text = pretty;
+                    }
+                    self.push(source, line);
+                }
+                MergedLog::Ended(source, error) => {
+                    self.open_streams = self.open_streams.saturating_sub(1);
+                    if let Some(e) = error {
+                        self.errors.push(if self.sources.len() > 1 {
+                            format!("{}: log stream failed: {e}", self.sources[source])
+                        } else {
+                            format!("log stream failed: {e}")
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, source: usize, line: LogLine) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let matched = self.search.regex.as_ref().is_some_and(|regex| regex.is_match(&line.text));
+        if let Some(dropped) = self.buffers[source].push(LogEntry { seq, line }) {
+            // Keep a paused view on the same lines as the oldest goes
+            let dropped_match = self.search.matches.binary_search(&dropped.seq).ok();
+            if let Some(index) = dropped_match {
+                self.search.matches.remove(index);
+            }
+            let row = if self.search.filter { dropped_match } else { Some(self.row_at(dropped.seq)) };
+            if row.is_some_and(|row| row < self.top) {
+                self.top -= 1;
+            }
+        }
+        if matched {
+            self.search.matches.push_back(seq);
+        }
+    }
+
+    /// Lines of every container, as `(source, entry)`, in arrival order.
+    fn merged(&self) -> impl Iterator<Item = (usize, &LogEntry)> {
+        let mut sources: Vec<_> = self.buffers.iter().map(|buffer| buffer.iter().peekable()).collect();
+        std::iter::from_fn(move || {
+            let (_, source) = sources
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(source, lines)| Some((lines.peek()?.seq, source)))
+                .min()?;
+            Some((source, sources[source].next()?))
+        })
+    }
+
+    /// The line numbered `seq`, with its source, if it's still kept.
+    fn entry(&self, seq: u64) -> Option<(usize, &LogEntry)> {
+        self.buffers.iter().enumerate().find_map(|(source, buffer)| {
+            let entry = buffer.get(buffer.partition_point(|entry| entry.seq < seq))?;
+            (entry.seq == seq).then_some((source, entry))
+        })
+    }
+
+    /// Rows are every buffered line, or only the matches while filtering.
+    fn row_count(&self) -> usize {
+        if self.search.filter {
+            self.search.matches.len()
+        } else {
+            self.buffers.iter().map(LogBuffer::len).sum()
+        }
+    }
+
+    fn row_seq(&self, row: usize) -> Option<u64> {
+        if self.search.filter {
+            self.search.matches.get(row).copied()
+        } else {
+            self.merged().nth(row).map(|(_, entry)| entry.seq)
+        }
+    }
+
+    /// The first row showing `seq` or a later line.
+    fn row_at(&self, seq: u64) -> usize {
+        if self.search.filter {
+            self.search.matches.partition_point(|&m| m < seq)
+        } else {
+            self.buffers.iter().map(|buffer| buffer.partition_point(|entry| entry.seq < seq)).sum()
+        }
+    }
+
+    fn last_top(&self) -> usize {
+        self.row_count().saturating_sub(self.height.get())
+    }
+
+    /// First row on screen.
+    fn first_visible(&self) -> usize {
+        if self.follow { self.last_top() } else { self.top.min(self.last_top()) }
+    }
+
+    /// Scrolling pauses following, so new output doesn't move the view.
+    fn scroll(&mut self, delta: isize) {
+        self.top = self.first_visible().saturating_add_signed(delta).min(self.last_top());
+        self.follow = false;
+    }
+
+    /// Compile `pattern`, smart-case, and jump to its first match from the
+    /// top of the screen. An invalid pattern keeps the previous search.
+    fn set_pattern(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            self.search.regex = None;
+            self.search.error = None;
+            self.search.matches.clear();
+            self.search.current = None;
+            return;
+        }
+        let Ok(regex) = RegexBuilder::new(pattern)
+            .case_insensitive(!pattern.chars().any(char::is_uppercase))
+            .build()
+        else {
+            self.search.error = Some("invalid pattern".to_string());
+            return;
+        };
+        self.search.matches = self
+            .merged()
+            .filter(|(_, entry)| regex.is_match(&entry.line.text))
+            .map(|(_, entry)| entry.seq)
+            .collect();
+        self.search.regex = Some(regex);
+        self.search.error = None;
+        self.search.current = None;
+
+        let from = self.row_seq(self.first_visible()).unwrap_or(0);
+        let target = self
+            .search
+            .matches
+            .iter()
+            .find(|&&m| m >= from)
+            .or(self.search.matches.back())
+            .copied();
+        if let Some(seq) = target {
+            self.search.current = Some(seq);
+            self.reveal(seq);
+        }
+    }
+
+    fn clear_search(&mut self) {
+        let anchor = self.row_seq(self.first_visible());
+        self.set_pattern("");
+        self.search.filter = false;
+        if let Some(seq) = anchor.filter(|_| !self.follow) {
+            self.top = self.row_at(seq);
+        }
+    }
+
+    /// Move to the next (or previous) match, wrapping around.
+    fn jump(&mut self, forward: bool) {
+        let matches = &self.search.matches;
+        let from = self.search.current.or_else(|| self.row_seq(self.first_visible()));
+        let target = match from {
+            Some(from) if forward => matches.iter().find(|&&m| m > from).or(matches.front()),
+            Some(from) => matches.iter().rev().find(|&&m| m < from).or(matches.back()),
+            None => matches.front(),
+        };
+        if let Some(&seq) = target {
+            self.search.current = Some(seq);
+            self.reveal(seq);
+        }
+    }
+
+    /// Scroll so `seq` is on screen, pausing to keep it there.
+    fn reveal(&mut self, seq: u64) {
+        let row = self.row_at(seq);
+        let top = self.first_visible();
+        if row < top || row >= top + self.height.get() {
+            self.top = row.saturating_sub(self.height.get() / 2).min(self.last_top());
+        } else {
+            self.top = top;
+        }
+        self.follow = false;
+    }
+
+    fn toggle_filter(&mut self) {
+        if self.search.regex.is_none() {
+            return;
+        }
+        let anchor = self.search.current.or_else(|| self.row_seq(self.first_visible()));
+        self.search.filter = !self.search.filter;
+        if let Some(seq) = anchor.filter(|_| !self.follow) {
+            self.top = self.row_at(seq).min(self.last_top());
+        }
+    }
+
+    /// Keys while typing a search pattern.
+    fn handle_search_key(&mut self, mut input: String, code: KeyCode) {
+        match code {
+            KeyCode::Enter if input.is_empty() => self.clear_search(),
+            KeyCode::Enter => {}
+            KeyCode::Esc => self.clear_search(),
+            KeyCode::Backspace => {
+                input.pop();
+                self.set_pattern(&input);
+                self.search.input = Some(input);
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                self.set_pattern(&input);
+                self.search.input = Some(input);
+            }
+            _ => self.search.input = Some(input),
+        }
+    }
+
+    /// Handle a key; returns whether the pane should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        if let Some(input) = self.search.input.take() {
+            self.handle_search_key(input, code);
+            return false;
+        }
+        let page = isize::try_from(self.height.get().max(1)).unwrap_or(isize::MAX);
+        match code {
+            KeyCode::Esc if self.search.regex.is_some() => self.clear_search(),
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Char('/') => {
+                self.search.input = Some(String::new());
+                self.search.error = None;
+            }
+            KeyCode::Char('n') => self.jump(true),
+            KeyCode::Char('N') => self.jump(false),
+            KeyCode::Char('F') => self.toggle_filter(),
+            KeyCode::Char('f') => {
+                if self.follow {
+                    self.top = self.last_top();
+                }
+                self.follow = !self.follow;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.scroll(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll(1),
+            KeyCode::PageUp => self.scroll(-page),
+            KeyCode::PageDown => self.scroll(page),
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.top = 0;
+                self.follow = false;
+            }
+            KeyCode::End | KeyCode::Char('G') => self.follow = true,
+            _ => {}
+        }
+        false
+    }
+
+    /// Whether typed characters go to the search prompt.
+    pub(crate) fn is_searching(&self) -> bool {
+        self.search.input.is_some()
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        self.height.set(usize::from(area.height.saturating_sub(2)).max(1));
+        let top = self.first_visible();
+        let end = (top + self.height.get()).min(self.row_count());
+        // Pad names so the lines of a merged pane line up
+        let name_width = self.sources.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+        let rows: Vec<(usize, &LogEntry)> = if self.search.filter {
+            self.search.matches.range(top..end).filter_map(|&seq| self.entry(seq)).collect()
+        } else {
+            self.merged().skip(top).take(end - top).collect()
+        };
+        let content: Vec<Line> = rows
+            .into_iter()
+            .map(|(source, entry)| {
+                let current = self.search.current == Some(entry.seq);
+                let mut line = render_line(&entry.line, self.search.regex.as_ref(), current);
+                if self.sources.len() > 1 {
+                    let color = SOURCE_COLORS[source % SOURCE_COLORS.len()];
+                    line.spans.insert(
+                        0,
+                        Span::styled(format!("{:<name_width$} | ", self.sources[source]), Style::default().fg(color)),
+                    );
+                }
+                line
+            })
+            .collect();
+
+        let position = if self.row_count() == 0 {
+            "no output".to_string()
+        } else {
+            format!("{}-{} of {}", top + 1, end, self.row_count())
+        };
+        let (mode, mode_color) = if self.follow { ("following", Color::Green) } else { ("paused", Color::Yellow) };
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Logs: {} ", self.sources.join(", ")))
+            .title(
+                Line::from(vec![
+                    Span::styled(format!(" {mode} "), Style::default().fg(mode_color)),
+                    Span::styled(format!("{position} "), Style::default().fg(Color::Gray)),
+                ])
+                .right_aligned(),
+            );
+        if let Some(search) = self.search_line() {
+            block = block.title_bottom(search);
+        }
+        if let Some(ended) = self.stream_status() {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {ended} "),
+                    Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+                ))
+                .right_aligned(),
+            );
+        }
+        f.render_widget(Paragraph::new(content).block(block), area);
+    }
+
+    /// Why following stopped, once it has.
+    fn stream_status(&self) -> Option<String> {
+        if !self.errors.is_empty() {
+            Some(self.errors.join("; "))
+        } else if self.open_streams == 0 {
+            Some(if self.sources.len() > 1 { "log streams ended" } else { "log stream ended" }.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// The search prompt while typing, or the match count after.
+    fn search_line(&self) -> Option<Line<'static>> {
+        let mut spans = Vec::new();
+        if let Some(input) = &self.search.input {
+            spans.push(Span::styled(format!(" /{input}▏"), Style::default().fg(Color::Yellow)));
+        } else if let Some(regex) = &self.search.regex {
+            spans.push(Span::styled(format!(" /{}", regex.as_str()), Style::default().fg(Color::Cyan)));
+        } else {
+            return None;
+        }
+        if let Some(error) = &self.search.error {
+            spans.push(Span::styled(format!(" {error} "), Style::default().fg(Color::Red)));
+        } else if self.search.regex.is_some() {
+            let total = self.search.matches.len();
+            let count = match self.search.current.and_then(|seq| self.search.matches.iter().position(|&m| m == seq)) {
+                Some(index) => format!(" {}/{total} ", index + 1),
+                None => format!(" {total} matches "),
+            };
+            spans.push(Span::styled(count, Style::default().fg(Color::Gray)));
+            if self.search.filter {
+                spans.push(Span::styled("filtered ", Style::default().fg(Color::Yellow)));
+            }
+        }
+        Some(Line::from(spans))
+    }
+}
+
+impl Drop for LogView {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Severity of a level token found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_ascii_uppercase().as_str() {
+            "ERROR" | "ERR" | "FATAL" | "PANIC" | "CRIT" | "CRITICAL" => Some(Self::Error),
+            "WARN" | "WARNING" => Some(Self::Warn),
+            "INFO" | "NOTICE" => Some(Self::Info),
+            "DEBUG" | "TRACE" => Some(Self::Debug),
+            _ => None,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Error => Color::Red,
+            Self::Warn => Color::Yellow,
+            Self::Info => Color::Green,
+            Self::Debug => Color::Magenta,
+        }
+    }
+}
+
+/// Level tokens in `text`: upper-case words like `ERROR`, or any case after
+/// `level=`, as in logfmt and pretty-printed JSON lines.
+pub(crate) fn find_levels(text: &str) -> Vec<(Range<usize>, LogLevel)> {
+    let mut levels = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_ascii_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(from)) => {
+                start = None;
+                let word = &text[from..index];
+                let keyed = ["level=", "lvl=", "severity="].iter().any(|key| {
+                    text.get(from.saturating_sub(key.len())..from)
+                        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(key))
+                });
+                if !keyed && word.chars().any(|c| !c.is_ascii_uppercase()) {
+                    continue;
+                }
+                if let Some(level) = LogLevel::parse(word) {
+                    levels.push((from..index, level));
+                }
+            }
+            _ => {}
+        }
+    }
+    levels
+}
+
+/// Show a JSON object line as `key=value` pairs, with time, level and
+/// message first.
+pub(crate) fn pretty_json(text: &str) -> Option<String> {
+    let text = text.trim();
+    if !text.starts_with('{') {
+        return None;
+    }
+    let Value::Object(fields) = serde_json::from_str(text).ok()? else {
+        return None;
+    };
+    let rank = |key: &str| match key {
+        "time" | "ts" | "timestamp" | "@timestamp" => 0,
+        "level" | "lvl" | "severity" => 1,
+        "msg" | "message" => 2,
+        _ => 3,
+    };
+    let mut fields: Vec<_> = fields.iter().collect();
+    fields.sort_by_key(|(key, _)| rank(key));
+    Some(
+        fields
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(s) if !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || c == '"') => {
+                    format!("{key}={s}")
+                }
+                _ => format!("{key}={value}"),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// A line with its time of day: stderr in red, level tokens coloured and
+/// search matches highlighted, the current one brighter.
+fn render_line<'a>(line: &'a LogLine, search: Option<&Regex>, current: bool) -> Line<'a> {
+    let mut spans = Vec::new();
+    if let Some(timestamp) = &line.timestamp {
+        let time = timestamp.get(11..19).unwrap_or(timestamp);
+        spans.push(Span::styled(format!("{time} "), Style::default().fg(Color::DarkGray)));
+    }
+    let base = match line.stream {
+        LogStream::Stdout => Style::default(),
+        LogStream::Stderr => Style::default().fg(Color::LightRed),
+    };
+    let mut highlights: Vec<(Range<usize>, Style)> = find_levels(&line.text)
+        .into_iter()
+        .map(|(range, level)| (range, Style::default().fg(level.color()).add_modifier(Modifier::BOLD)))
+        .collect();
+    if let Some(regex) = search {
+        let background = if current { Color::Yellow } else { Color::Cyan };
+        highlights.extend(
+            regex
+                .find_iter(&line.text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.range(), Style::default().fg(Color::Black).bg(background))),
+        );
+    }
+    spans.extend(styled_spans(&line.text, base, &highlights));
+    Line::from(spans)
+}
+
+/// Split `text` into spans, applying each highlight over `base` in order.
+fn styled_spans<'a>(text: &'a str, base: Style, highlights: &[(Range<usize>, Style)]) -> Vec<Span<'a>> {
+    let mut bounds: Vec<usize> = highlights
+        .iter()
+        .flat_map(|(range, _)| [range.start, range.end])
+        .chain([0, text.len()])
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+    bounds
+        .windows(2)
+        .map(|pair| {
+            let style = highlights
+                .iter()
+                .filter(|(range, _)| range.start <= pair[0] && pair[1] <= range.end)
+                .fold(base, |style, (_, highlight)| style.patch(*highlight));
+            Span::styled(&text[pair[0]..pair[1]], style)
+        })
+        .collect()
+}
@@ -0,0 +1,335 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::sync::Arc;
+
+use log::warn;
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::{Constraint, Direction, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph, Row, Table},
+    },
+};
+
+use super::{Selection, TaskSet, confirmed, render_dialog};
+use crate::docker::{Connection, models::Network};
+use crate::error::WhaleError;
+
+/// Subnets and gateways of every address pool, e.g. `172.18.0.0/16` or
+/// `172.18.0.0/16, fd00::/64`.
+pub(crate) fn subnets(network: &Network) -> (String, String) {
+    let join = |values: Vec<&str>| if values.is_empty() { "-".to_string() } else { values.join(", ") };
+    let config = &network.ipam.config;
+    (
+        join(config.iter().map(|c| c.subnet.as_str()).filter(|s| !s.is_empty()).collect()),
+        join(config.iter().map(|c| c.gateway.as_str()).filter(|g| !g.is_empty()).collect()),
+    )
+}
+
+/// The networks, each inspected for its containers. Networks removed in
+/// between are left as listed.
+async fn load(client: Arc<dyn Connection>) -> Result<Vec<Network>, WhaleError> {
+    let mut networks = client.list_networks().await?;
+    for network in &mut networks {
+        match client.inspect_network(&network.id).await {
+            Ok(inspected) => *network = inspected,
+            Err(e) => warn!("Failed to inspect network {}: {}", network.name, e),
+        }
+    }
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(networks)
+}
+
+enum Message {
+    Networks(Result<Vec<Network>, WhaleError>),
+    /// Outcome of a connect or disconnect, and whether it failed.
+    Done(String, bool),
+}
+
+/// A disconnect waiting for the user to confirm it.
+struct Disconnect {
+    network_id: String,
+    network: String,
+}
+
+/// The networks with their address pools and attached containers, and
+/// connect/disconnect for the container focused in the grid.
+pub(crate) struct NetworksView {
+    client: Arc<dyn Connection>,
+    /// `(id, name)` of the container focused in the grid.
+    container: Option<(String, String)>,
+    networks: Vec<Network>,
+    error: Option<String>,
+    loaded: bool,
+    tasks: TaskSet<Message>,
+    selection: Selection<Network, String>,
+    confirm: Option<Disconnect>,
+    /// Outcome of the last connect or disconnect.
+    message: Option<(String, bool)>,
+}
+
+impl NetworksView {
+    /// List the networks in the background.
+    pub(crate) fn open(client: Arc<dyn Connection>, container: Option<(String, String)>) -> Self {
+        let mut view = Self {
+            client,
+            container,
+            networks: Vec::new(),
+            error: None,
+            loaded: false,
+            tasks: TaskSet::default(),
+            selection: Selection::new(|network: &Network| &network.id),
+            confirm: None,
+            message: None,
+        };
+        view.reload();
+        view
+    }
+
+    fn reload(&mut self) {
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move { Message::Networks(load(client).await) });
+    }
+
+    /// Take finished requests.
+    pub(crate) fn update(&mut self) {
+        while let Some(message) = self.tasks.try_recv() {
+            match message {
+                Message::Networks(result) => {
+                    self.loaded = true;
+                    match result {
+                        Ok(networks) => {
+                            self.networks = networks;
+                            self.error = None;
+                            self.selection.retain(&self.networks);
+                        }
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+                Message::Done(message, error) => {
+                    self.message = Some((message, error));
+                    self.reload();
+                }
+            }
+        }
+    }
+
+    /// Whether a disconnect is waiting for confirmation.
+    pub(crate) fn is_confirming(&self) -> bool {
+        self.confirm.is_some()
+    }
+
+    /// The selected network and the focused container, and whether it is
+    /// attached to it.
+    fn target(&self) -> Option<(&Network, &str, &str, bool)> {
+        let (id, name) = self.container.as_ref()?;
+        let network = self.selection.get(&self.networks)?;
+        Some((network, id, name, network.containers.contains_key(id)))
+    }
+
+    fn connect(&mut self) {
+        let Some((network, id, name, attached)) = self.target() else {
+            return;
+        };
+        if attached {
+            let message = format!("{name} is already connected to {}", network.name);
+            self.message = Some((message, true));
+            return;
+        }
+        let (client, network_id, network, id, name) =
+            (Arc::clone(&self.client), network.id.clone(), network.name.clone(), id.to_string(), name.to_string());
+        self.tasks.spawn(async move {
+            match client.connect_network(&network_id, &id).await {
+                Ok(()) => Message::Done(format!("{name} connected to {network}"), false),
+                Err(e) => Message::Done(format!("Failed to connect {name} to {network}: {e}"), true),
+            }
+        });
+    }
+
+    fn request_disconnect(&mut self) {
+        let Some((network, _, name, attached)) = self.target() else {
+            return;
+        };
+        if attached {
+            let disconnect = Disconnect {
+                network_id: network.id.clone(),
+                network: network.name.clone(),
+            };
+            self.confirm = Some(disconnect);
+        } else {
+            let message = format!("{name} is not connected to {}", network.name);
+            self.message = Some((message, true));
+        }
+    }
+
+    fn disconnect(&mut self, Disconnect { network_id, network }: Disconnect) {
+        let Some((id, name)) = self.container.clone() else {
+            return;
+        };
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move {
+            match client.disconnect_network(&network_id, &id).await {
+                Ok(()) => Message::Done(format!("{name} disconnected from {network}"), false),
+                Err(e) => Message::Done(format!("Failed to disconnect {name} from {network}: {e}"), true),
+            }
+        });
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.confirm.is_some() {
+            if let Some(disconnect) = confirmed(&mut self.confirm, code) {
+                self.disconnect(disconnect);
+            }
+            return false;
+        }
+        self.message = None;
+        if self.selection.handle_key(code, &self.networks) {
+            return false;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Char('c' | 'd') if self.container.is_none() => {
+                self.message = Some(("No container selected in the grid".to_string(), true));
+            }
+            KeyCode::Char('c') => self.connect(),
+            KeyCode::Char('d') => self.request_disconnect(),
+            KeyCode::Char('r') => self.reload(),
+            _ => {}
+        }
+        false
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        let selected = self.selection.get(&self.networks);
+        let attached = selected.map_or(0, |n| n.containers.len());
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(6),
+                // Borders, the header row and up to ten containers
+                Constraint::Length(u16::try_from(attached.clamp(1, 10)).unwrap_or(10) + 3),
+            ])
+            .split(area);
+
+        self.render_networks(f, chunks[0]);
+        if let Some(network) = selected {
+            self.render_containers(f, chunks[1], network);
+        }
+        if let (Some(Disconnect { network, .. }), Some((_, name))) = (&self.confirm, &self.container) {
+            render_dialog(f, &format!("Disconnect {name} from {network}?"));
+        }
+    }
+
+    fn render_networks(&self, f: &mut Frame, area: Rect) {
+        // Borders and the header row
+        let height = usize::from(area.height.saturating_sub(3)).max(1);
+        let target = self
+            .container
+            .as_ref()
+            .map_or_else(String::new, |(_, name)| format!(" · [c/d] connect/disconnect {name} "));
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Networks{target}"))
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} networks ", self.networks.len()),
+                    Style::default().fg(Color::Gray),
+                ))
+                .right_aligned(),
+            );
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {error} "),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if let Some((message, error)) = &self.message {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {message} "),
+                    Style::default().fg(if *error { Color::Red } else { Color::Green }),
+                ))
+                .right_aligned(),
+            );
+        }
+        if !self.loaded {
+            let loading = Paragraph::new(Span::styled("Loading…", Style::default().fg(Color::DarkGray)));
+            f.render_widget(loading.block(block), area);
+            return;
+        }
+
+        let selected = self.selection.index(&self.networks);
+        let top = self.selection.scroll(&self.networks, height);
+        let container_id = self.container.as_ref().map(|(id, _)| id);
+        let header = Row::new(["NAME", "DRIVER", "SCOPE", "SUBNET", "GATEWAY", "CONTAINERS"])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = self.networks.iter().enumerate().skip(top).take(height).map(|(index, network)| {
+            let (subnet, gateway) = subnets(network);
+            // Mark the networks the focused container is attached to
+            let attached = container_id.is_some_and(|id| network.containers.contains_key(id));
+            let row = Row::new([
+                format!("{}{}", if attached { "● " } else { "  " }, network.name),
+                network.driver.clone(),
+                network.scope.clone(),
+                subnet,
+                gateway,
+                network.containers.len().to_string(),
+            ]);
+            let row = if network.internal { row.style(Style::default().fg(Color::Gray)) } else { row };
+            if index == selected {
+                row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                row
+            }
+        });
+        let widths = [
+            Constraint::Min(16),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Min(18),
+            Constraint::Min(16),
+            Constraint::Length(10),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+
+    fn render_containers(&self, f: &mut Frame, area: Rect, network: &Network) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray))
+            .title(format!(" Attached to {} ", network.name));
+        if network.containers.is_empty() {
+            let empty = Paragraph::new(Span::styled("No containers", Style::default().fg(Color::DarkGray)));
+            f.render_widget(empty.block(block), area);
+            return;
+        }
+        let container_id = self.container.as_ref().map(|(id, _)| id);
+        let header = Row::new(["CONTAINER", "IPV4", "IPV6", "MAC"])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = network.containers.iter().map(|(id, endpoint)| {
+            let row = Row::new([
+                endpoint.name.clone(),
+                endpoint.ipv4_address.clone(),
+                endpoint.ipv6_address.clone(),
+                endpoint.mac_address.clone(),
+            ]);
+            if container_id == Some(id) {
+                row.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                row
+            }
+        });
+        let widths = [
+            Constraint::Min(16),
+            Constraint::Length(18),
+            Constraint::Min(12),
+            Constraint::Length(17),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+}
@@ -0,0 +1,175 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use log::debug;
+use ratui_lib::ratatui::crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    sync::mpsc,
+};
+
+use crate::docker::{Connection, connection::Hijacked};
+use crate::error::WhaleError;
+
+/// Runs bash where the image has it, and sh otherwise.
+pub(crate) const SHELL_COMMAND: [&str; 3] = [
+    "/bin/sh",
+    "-c",
+    "if command -v bash >/dev/null 2>&1; then exec bash; else exec sh; fi",
+];
+
+/// How often the input thread checks whether the session is over, so it
+/// never holds on to a key meant for the grid.
+const INPUT_POLL: Duration = Duration::from_millis(50);
+
+enum Input {
+    Bytes(Vec<u8>),
+    Resize(u16, u16),
+}
+
+/// Run an interactive shell in a container on the plain terminal, which the
+/// TUI must have given up. Returns the shell's exit code.
+pub(crate) async fn run_shell(client: Arc<dyn Connection>, id: &str, name: &str) -> Result<Option<i64>, WhaleError> {
+    let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+    let exec_id = client.create_exec(id, &SHELL_COMMAND, &[format!("TERM={term}")]).await?;
+    let mut stream = client.start_exec(&exec_id).await?;
+    println!("Connected to {name}; exit the shell to return to cetacea.");
+
+    let (width, height) = terminal::size()?;
+    if let Err(e) = client.resize_exec(&exec_id, width, height).await {
+        debug!("Failed to resize exec {exec_id}: {e}");
+    }
+    terminal::enable_raw_mode()?;
+    let result = forward(client.as_ref(), &exec_id, &mut stream).await;
+    terminal::disable_raw_mode()?;
+    result?;
+
+    Ok(client.inspect_exec(&exec_id).await?.exit_code)
+}
+
+/// Copy the shell's output to stdout and key presses and resizes to the
+/// shell until it closes the stream.
+async fn forward(client: &dyn Connection, exec_id: &str, stream: &mut Hijacked) -> Result<(), WhaleError> {
+    let (tx, mut rx) = mpsc::channel(64);
+    let done = Arc::new(AtomicBool::new(false));
+    let input = tokio::task::spawn_blocking({
+        let done = Arc::clone(&done);
+        move || read_input(&tx, &done)
+    });
+
+    let mut stdout = tokio::io::stdout();
+    let mut buf = vec![0; 8192];
+    let result = loop {
+        tokio::select! {
+            read = stream.read(&mut buf) => match read {
+                Ok(0) => break Ok(()),
+                Ok(n) => {
+                    if let Err(e) = write_out(&mut stdout, &buf[..n]).await {
+                        break Err(e.into());
+                    }
+                }
+                Err(e) => break Err(e.into()),
+            },
+            Some(input) = rx.recv() => match input {
+                Input::Bytes(bytes) => {
+                    if let Err(e) = stream.write_all(&bytes).await {
+                        break Err(e.into());
+                    }
+                }
+                Input::Resize(width, height) => {
+                    if let Err(e) = client.resize_exec(exec_id, width, height).await {
+                        debug!("Failed to resize exec {exec_id}: {e}");
+                    }
+                }
+            },
+        }
+    };
+
+    done.store(true, Ordering::Relaxed);
+    let _ = input.await;
+    result
+}
+
+async fn write_out(stdout: &mut tokio::io::Stdout, data: &[u8]) -> std::io::Result<()> {
+    stdout.write_all(data).await?;
+    stdout.flush().await
+}
+
+/// Read terminal events until `done`, passing on what the shell needs.
+fn read_input(tx: &mpsc::Sender<Input>, done: &AtomicBool) {
+    while !done.load(Ordering::Relaxed) {
+        match event::poll(INPUT_POLL) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(_) => return,
+        }
+        let input = match event::read() {
+            Ok(Event::Key(key)) => key_bytes(key).map(Input::Bytes),
+            Ok(Event::Paste(text)) => Some(Input::Bytes(text.into_bytes())),
+            Ok(Event::Resize(width, height)) => Some(Input::Resize(width, height)),
+            Ok(_) => None,
+            Err(_) => return,
+        };
+        if let Some(input) = input {
+            if tx.blocking_send(input).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Encode a key press the way a terminal sends it to the program in it.
+pub(crate) fn key_bytes(key: KeyEvent) -> Option<Vec<u8>> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '[' | '\\' | ']' | '^' | '_') => vec![u8::try_from(c).ok()? & 0x1f],
+            '@' | ' ' => vec![0],
+            _ => return None,
+        },
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => b"\r".to_vec(),
+        KeyCode::Tab => b"\t".to_vec(),
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Backspace => b"\x7f".to_vec(),
+        KeyCode::Esc => b"\x1b".to_vec(),
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::F(n) => match n {
+            1 => b"\x1bOP".to_vec(),
+            2 => b"\x1bOQ".to_vec(),
+            3 => b"\x1bOR".to_vec(),
+            4 => b"\x1bOS".to_vec(),
+            5 => b"\x1b[15~".to_vec(),
+            6..=10 => format!("\x1b[{}~", n + 11).into_bytes(),
+            11 | 12 => format!("\x1b[{}~", n + 12).into_bytes(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    // Alt sends an escape first
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        bytes.insert(0, 0x1b);
+    }
+    Some(bytes)
+}
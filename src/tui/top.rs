@@ -0,0 +1,280 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{
+    cell::Cell,
+    sync::{Arc, mpsc},
+};
+
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::{Constraint, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph, Row, Table},
+    },
+};
+use tokio::{sync::watch, task::JoinHandle};
+
+use super::{Intervals, scroll};
+use crate::docker::{Connection, models::Process};
+use crate::error::WhaleError;
+use crate::utils::format_interval;
+
+/// `ps` arguments for the process table: every process, with usage columns.
+const PS_ARGS: &str = "aux";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortColumn {
+    Pid,
+    User,
+    Cpu,
+    Memory,
+    Command,
+}
+
+impl SortColumn {
+    const ALL: [Self; 5] = [Self::Pid, Self::User, Self::Cpu, Self::Memory, Self::Command];
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Pid => "PID",
+            Self::User => "USER",
+            Self::Cpu => "%CPU",
+            Self::Memory => "%MEM",
+            Self::Command => "COMMAND",
+        }
+    }
+
+    /// Usage is most interesting highest first, the rest in order.
+    fn descending_by_default(self) -> bool {
+        matches!(self, Self::Cpu | Self::Memory)
+    }
+}
+
+/// Sort processes by `column`; ties keep PID order.
+pub(crate) fn sort_processes(processes: &mut [Process], column: SortColumn, descending: bool) {
+    processes.sort_by(|a, b| {
+        let ordering = match column {
+            SortColumn::Pid => a.pid.cmp(&b.pid),
+            SortColumn::User => a.user.cmp(&b.user),
+            // Missing usage sorts below any value
+            SortColumn::Cpu => a.cpu_percent.unwrap_or(-1.0).total_cmp(&b.cpu_percent.unwrap_or(-1.0)),
+            SortColumn::Memory => a.memory_percent.unwrap_or(-1.0).total_cmp(&b.memory_percent.unwrap_or(-1.0)),
+            SortColumn::Command => a.command.cmp(&b.command),
+        };
+        if descending { ordering.reverse() } else { ordering }.then(a.pid.cmp(&b.pid))
+    });
+}
+
+/// Full-screen process table of a container, refreshed on the stats
+/// interval.
+pub(crate) struct TopView {
+    name: String,
+    processes: Vec<Process>,
+    /// Why the last refresh failed, e.g. because the container stopped.
+    error: Option<String>,
+    loaded: bool,
+    rx: mpsc::Receiver<Result<Vec<Process>, WhaleError>>,
+    task: JoinHandle<()>,
+    intervals: watch::Receiver<Intervals>,
+    sort: SortColumn,
+    descending: bool,
+    /// PID of the selected process, kept across refreshes.
+    selected: Option<u32>,
+    /// First row on screen, kept around the selection on render.
+    top: Cell<usize>,
+    /// Rows that fit in the table, from the last render.
+    height: Cell<usize>,
+}
+
+impl TopView {
+    /// List the processes of a container now and then every stats interval.
+    pub(crate) fn open(client: Arc<dyn Connection>, id: String, name: String, intervals: watch::Receiver<Intervals>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let mut interval = intervals.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let result = client.top(&id, PS_ARGS).await.map(|top| top.processes());
+                if tx.send(result).is_err() {
+                    return;
+                }
+                let period = interval.borrow_and_update().stats;
+                tokio::select! {
+                    () = tokio::time::sleep(period) => {}
+                    changed = interval.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+        Self {
+            name,
+            processes: Vec::new(),
+            error: None,
+            loaded: false,
+            rx,
+            task,
+            intervals,
+            sort: SortColumn::Cpu,
+            descending: true,
+            selected: None,
+            top: Cell::new(0),
+            height: Cell::new(1),
+        }
+    }
+
+    /// Take the latest listing.
+    pub(crate) fn update(&mut self) {
+        while let Ok(result) = self.rx.try_recv() {
+            self.loaded = true;
+            match result {
+                Ok(processes) => {
+                    self.processes = processes;
+                    self.error = None;
+                    self.sort();
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+    }
+
+    fn sort(&mut self) {
+        sort_processes(&mut self.processes, self.sort, self.descending);
+        if self.selected.is_none_or(|pid| !self.processes.iter().any(|p| p.pid == pid)) {
+            self.selected = self.processes.first().map(|p| p.pid);
+        }
+    }
+
+    /// Sort by `column`, or reverse the order if it already is.
+    fn sort_by(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = column.descending_by_default();
+        }
+        self.sort();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected
+            .and_then(|pid| self.processes.iter().position(|p| p.pid == pid))
+            .unwrap_or(0)
+    }
+
+    fn select_index(&mut self, index: usize) {
+        self.selected = self
+            .processes
+            .get(index.min(self.processes.len().saturating_sub(1)))
+            .map(|p| p.pid);
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        let page = isize::try_from(self.height.get().max(1)).unwrap_or(isize::MAX);
+        let index = self.selected_index();
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.select_index(index.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select_index(index + 1),
+            KeyCode::PageUp => self.select_index(index.saturating_add_signed(-page)),
+            KeyCode::PageDown => self.select_index(index.saturating_add_signed(page)),
+            KeyCode::Home | KeyCode::Char('g') => self.select_index(0),
+            KeyCode::End | KeyCode::Char('G') => self.select_index(usize::MAX),
+            KeyCode::Char('p') => self.sort_by(SortColumn::Pid),
+            KeyCode::Char('u') => self.sort_by(SortColumn::User),
+            KeyCode::Char('c') => self.sort_by(SortColumn::Cpu),
+            KeyCode::Char('m') => self.sort_by(SortColumn::Memory),
+            KeyCode::Char('n') => self.sort_by(SortColumn::Command),
+            _ => {}
+        }
+        false
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Processes: {} ", self.name))
+            .title(
+                Line::from(Span::styled(
+                    format!(
+                        " {} processes · every {} ",
+                        self.processes.len(),
+                        format_interval(self.intervals.borrow().stats)
+                    ),
+                    Style::default().fg(Color::Gray),
+                ))
+                .right_aligned(),
+            );
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {error} "),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if !self.loaded {
+            let loading = Paragraph::new(Span::styled("Loading…", Style::default().fg(Color::DarkGray)));
+            f.render_widget(loading.block(block), area);
+            return;
+        }
+
+        // Borders and the header row
+        let height = usize::from(area.height.saturating_sub(3)).max(1);
+        self.height.set(height);
+        let selected = self.selected_index();
+        let top = scroll(&self.top, selected, height, self.processes.len());
+
+        let header = Row::new(SortColumn::ALL.map(|column| {
+            let arrow = match (column == self.sort, self.descending) {
+                (true, true) => " ▼",
+                (true, false) => " ▲",
+                (false, _) => "",
+            };
+            format!("{}{arrow}", column.title())
+        }))
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = self
+            .processes
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(index, process)| {
+                let row = Row::new([
+                    process.pid.to_string(),
+                    process.user.clone(),
+                    format_percent(process.cpu_percent),
+                    format_percent(process.memory_percent),
+                    process.command.clone(),
+                ]);
+                if index == selected {
+                    row.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    row
+                }
+            });
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Min(10),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+    }
+}
+
+impl Drop for TopView {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn format_percent(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| format!("{value:.1}"))
+}
@@ -0,0 +1,159 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::collections::HashSet;
+
+/// A visible row of a [`Tree`]. Nodes are addressed by `/`-separated paths
+/// such as `/a/b`, whose prefixes up to each `/` are the enclosing nodes.
+pub(crate) trait TreeItem {
+    fn id(&self) -> &str;
+    /// Whether the node can be expanded.
+    fn has_children(&self) -> bool;
+}
+
+/// The nodes a [`Tree`] shows.
+pub(crate) trait TreeSource {
+    type Row: TreeItem;
+
+    /// The top-level nodes, each followed by its children when it's expanded.
+    fn rows(&self, expanded: &HashSet<String>) -> Vec<Self::Row>;
+
+    /// Every node that can be expanded, visible or not.
+    fn branches(&self) -> HashSet<String>;
+}
+
+/// Nodes with collapsible children and a selected row. Expansion is kept by
+/// path, so it survives rebuilding the rows.
+pub(crate) struct Tree<S: TreeSource> {
+    source: S,
+    /// Paths of the expanded nodes.
+    expanded: HashSet<String>,
+    rows: Vec<S::Row>,
+    selected: usize,
+}
+
+impl<S: TreeSource> Tree<S> {
+    /// Show the top-level nodes, all collapsed.
+    pub(crate) fn new(source: S) -> Self {
+        let mut tree = Self {
+            source,
+            expanded: HashSet::new(),
+            rows: Vec::new(),
+            selected: 0,
+        };
+        tree.rebuild();
+        tree
+    }
+
+    pub(crate) fn source(&self) -> &S {
+        &self.source
+    }
+
+    pub(crate) fn rows(&self) -> &[S::Row] {
+        &self.rows
+    }
+
+    pub(crate) fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub(crate) fn selected_row(&self) -> Option<&S::Row> {
+        self.rows.get(self.selected)
+    }
+
+    pub(crate) fn is_expanded(&self, id: &str) -> bool {
+        self.expanded.contains(id)
+    }
+
+    /// Recompute the visible rows, keeping the selected node when it's
+    /// still visible.
+    fn rebuild(&mut self) {
+        let selected = self.selected_row().map(|row| row.id().to_string());
+        self.rows = self.source.rows(&self.expanded);
+        self.selected = selected
+            .and_then(|id| self.rows.iter().position(|row| row.id() == id))
+            .unwrap_or_else(|| self.selected.min(self.rows.len().saturating_sub(1)));
+    }
+
+    fn select_id(&mut self, id: &str) {
+        if let Some(index) = self.rows.iter().position(|row| row.id() == id) {
+            self.selected = index;
+        }
+    }
+
+    pub(crate) fn move_by(&mut self, delta: isize) {
+        self.move_to(self.selected.saturating_add_signed(delta));
+    }
+
+    pub(crate) fn move_to(&mut self, index: usize) {
+        self.selected = index.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Open the selected node.
+    pub(crate) fn expand(&mut self) {
+        let Some(id) = self
+            .selected_row()
+            .filter(|row| row.has_children())
+            .map(|row| row.id().to_string())
+        else {
+            return;
+        };
+        self.expanded.insert(id);
+        self.rebuild();
+    }
+
+    /// Close the selected node, or go to its parent if it's not open.
+    pub(crate) fn collapse(&mut self) {
+        let Some(id) = self.selected_row().map(|row| row.id().to_string()) else {
+            return;
+        };
+        if self.expanded.remove(&id) {
+            self.rebuild();
+        } else if let Some(parent) = parent(&id) {
+            self.select_id(parent);
+        }
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        match self.selected_row().map(|row| self.expanded.contains(row.id())) {
+            Some(true) => self.collapse(),
+            Some(false) => self.expand(),
+            None => {}
+        }
+    }
+
+    pub(crate) fn expand_all(&mut self) {
+        self.expanded = self.source.branches();
+        self.rebuild();
+    }
+
+    /// Close everything, keeping the selection on the top-level node that
+    /// contained it.
+    pub(crate) fn collapse_all(&mut self) {
+        let top = self
+            .selected_row()
+            .map(|row| ancestors(row.id()).next().unwrap_or(row.id()).to_string());
+        self.expanded.clear();
+        self.rebuild();
+        if let Some(top) = top {
+            self.select_id(&top);
+        }
+    }
+
+    /// Expand the ancestors of a node and select it.
+    pub(crate) fn reveal(&mut self, id: &str) {
+        for ancestor in ancestors(id) {
+            self.expanded.insert(ancestor.to_string());
+        }
+        self.rebuild();
+        self.select_id(id);
+    }
+}
+
+/// Enclosing nodes of `id`, outermost first.
+fn ancestors(id: &str) -> impl Iterator<Item = &str> {
+    id.match_indices('/').filter(|(index, _)| *index > 0).map(|(index, _)| &id[..index])
+}
+
+fn parent(id: &str) -> Option<&str> {
+    id.rfind('/').filter(|index| *index > 0).map(|index| &id[..index])
+}
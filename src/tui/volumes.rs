@@ -0,0 +1,307 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{collections::HashMap, sync::Arc};
+
+use log::warn;
+use ratui_lib::{
+    KeyCode,
+    ratatui::{
+        Frame,
+        layout::{Constraint, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph, Row, Table},
+    },
+};
+
+use super::{Selection, TaskSet, confirmed, display_name, render_dialog};
+use crate::docker::{Connection, Container, models::Volume};
+use crate::error::WhaleError;
+use crate::utils::format_bytes;
+
+/// A volume with its usage and the containers that mount it.
+#[derive(Debug, Clone)]
+pub(crate) struct VolumeRow {
+    pub volume: Volume,
+    /// Unknown when `/system/df` failed or the driver can't tell.
+    pub size: Option<u64>,
+    pub ref_count: Option<u64>,
+    /// Names of the containers mounting it.
+    pub containers: Vec<String>,
+}
+
+impl VolumeRow {
+    /// Not mounted by any container, running or not.
+    pub(crate) fn is_orphaned(&self) -> bool {
+        self.containers.is_empty() && self.ref_count.is_none_or(|count| count == 0)
+    }
+}
+
+/// Names of the containers mounting each volume, by volume name.
+pub(crate) fn volume_mounts(containers: &[Container]) -> HashMap<String, Vec<String>> {
+    let mut mounts: HashMap<String, Vec<String>> = HashMap::new();
+    for container in containers {
+        for volume in container.mounts.iter().filter(|m| m.kind == "volume").filter_map(|m| m.name.clone()) {
+            mounts.entry(volume).or_default().push(display_name(container));
+        }
+    }
+    mounts
+}
+
+/// Join the volume list with the sizes from `/system/df` and the mounts
+/// from [`volume_mounts`], by volume name.
+pub(crate) fn volume_rows(volumes: Vec<Volume>, usage: &[Volume], mounts: &HashMap<String, Vec<String>>) -> Vec<VolumeRow> {
+    let usage: HashMap<&str, _> = usage
+        .iter()
+        .filter_map(|volume| Some((volume.name.as_str(), volume.usage_data?)))
+        .collect();
+    let mut rows: Vec<VolumeRow> = volumes
+        .into_iter()
+        .map(|volume| {
+            let usage = usage.get(volume.name.as_str());
+            VolumeRow {
+                size: usage.and_then(|usage| usage.size()),
+                ref_count: usage.and_then(|usage| usage.ref_count()),
+                containers: mounts.get(&volume.name).cloned().unwrap_or_default(),
+                volume,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| a.volume.name.cmp(&b.volume.name));
+    rows
+}
+
+/// Read the volumes and their sizes, and join them with `mounts`.
+async fn load(client: Arc<dyn Connection>, mounts: HashMap<String, Vec<String>>) -> Result<Vec<VolumeRow>, WhaleError> {
+    let volumes = client.list_volumes().await?;
+    let usage = match client.disk_usage().await {
+        Ok(usage) => usage.volumes,
+        Err(e) => {
+            warn!("Failed to read volume sizes: {e}");
+            Vec::new()
+        }
+    };
+    Ok(volume_rows(volumes, &usage, &mounts))
+}
+
+enum Message {
+    Volumes(Result<Vec<VolumeRow>, WhaleError>),
+    /// Outcome of a removal, and whether it failed.
+    Done(String, bool),
+}
+
+/// The volumes, with orphaned ones highlighted.
+pub(crate) struct VolumesView {
+    client: Arc<dyn Connection>,
+    /// Names of the containers mounting each volume, from the container
+    /// list the view was opened with.
+    mounts: HashMap<String, Vec<String>>,
+    rows: Vec<VolumeRow>,
+    error: Option<String>,
+    loaded: bool,
+    tasks: TaskSet<Message>,
+    /// Biggest first instead of by name.
+    by_size: bool,
+    selection: Selection<VolumeRow, String>,
+    /// Name of the volume waiting for the user to confirm its removal.
+    confirm: Option<String>,
+    /// Outcome of the last removal.
+    message: Option<(String, bool)>,
+}
+
+impl VolumesView {
+    /// List the volumes in the background.
+    pub(crate) fn open(client: Arc<dyn Connection>, containers: &[Container]) -> Self {
+        let mut view = Self {
+            client,
+            mounts: volume_mounts(containers),
+            rows: Vec::new(),
+            error: None,
+            loaded: false,
+            tasks: TaskSet::default(),
+            by_size: false,
+            selection: Selection::new(|row: &VolumeRow| &row.volume.name),
+            confirm: None,
+            message: None,
+        };
+        view.reload();
+        view
+    }
+
+    fn reload(&mut self) {
+        let client = Arc::clone(&self.client);
+        let mounts = self.mounts.clone();
+        self.tasks.spawn(async move { Message::Volumes(load(client, mounts).await) });
+    }
+
+    /// Take finished requests.
+    pub(crate) fn update(&mut self) {
+        while let Some(message) = self.tasks.try_recv() {
+            match message {
+                Message::Volumes(result) => {
+                    self.loaded = true;
+                    match result {
+                        Ok(rows) => {
+                            self.rows = rows;
+                            self.error = None;
+                            self.sort();
+                        }
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+                Message::Done(message, error) => {
+                    self.message = Some((message, error));
+                    self.reload();
+                }
+            }
+        }
+    }
+
+    /// Whether a removal is waiting for confirmation.
+    pub(crate) fn is_confirming(&self) -> bool {
+        self.confirm.is_some()
+    }
+
+    fn sort(&mut self) {
+        if self.by_size {
+            // Unknown sizes last, then by name
+            self.rows.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.volume.name.cmp(&b.volume.name)));
+        } else {
+            self.rows.sort_by(|a, b| a.volume.name.cmp(&b.volume.name));
+        }
+        self.selection.retain(&self.rows);
+    }
+
+    /// Ask to remove the selected volume; mounted ones are refused here
+    /// rather than by the daemon.
+    fn request_remove(&mut self) {
+        let Some(row) = self.selection.get(&self.rows) else {
+            return;
+        };
+        if row.is_orphaned() {
+            self.confirm = Some(row.volume.name.clone());
+        } else if row.containers.is_empty() {
+            self.message = Some((format!("{} is still referenced by a container", row.volume.name), true));
+        } else {
+            self.message = Some((format!("{} is mounted by {}", row.volume.name, row.containers.join(", ")), true));
+        }
+    }
+
+    fn remove(&mut self, name: String) {
+        let client = Arc::clone(&self.client);
+        self.tasks.spawn(async move {
+            match client.remove_volume(&name).await {
+                Ok(()) => Message::Done(format!("{name} removed"), false),
+                Err(e) => Message::Done(format!("Failed to remove {name}: {e}"), true),
+            }
+        });
+    }
+
+    /// Handle a key; returns whether the view should close.
+    pub(crate) fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.confirm.is_some() {
+            if let Some(name) = confirmed(&mut self.confirm, code) {
+                self.remove(name);
+            }
+            return false;
+        }
+        self.message = None;
+        if self.selection.handle_key(code, &self.rows) {
+            return false;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return true,
+            KeyCode::Char('s') => {
+                self.by_size = !self.by_size;
+                self.sort();
+            }
+            KeyCode::Char('r') => self.reload(),
+            KeyCode::Char('D') => self.request_remove(),
+            _ => {}
+        }
+        false
+    }
+
+    pub(crate) fn render(&self, f: &mut Frame, area: Rect) {
+        // Borders and the header row
+        let height = usize::from(area.height.saturating_sub(3)).max(1);
+        let orphaned = self.rows.iter().filter(|r| r.is_orphaned()).count();
+        let total: u64 = self.rows.iter().filter_map(|r| r.size).sum();
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Volumes ")
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} volumes · {} · {orphaned} orphaned ", self.rows.len(), format_bytes(total)),
+                    Style::default().fg(Color::Gray),
+                ))
+                .right_aligned(),
+            );
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {error} "),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if let Some((message, error)) = &self.message {
+            block = block.title_bottom(
+                Line::from(Span::styled(
+                    format!(" {message} "),
+                    Style::default().fg(if *error { Color::Red } else { Color::Green }),
+                ))
+                .right_aligned(),
+            );
+        }
+        if !self.loaded {
+            let loading = Paragraph::new(Span::styled("Loading…", Style::default().fg(Color::DarkGray)));
+            f.render_widget(loading.block(block), area);
+            return;
+        }
+
+        let selected = self.selection.index(&self.rows);
+        let top = self.selection.scroll(&self.rows, height);
+        let header = Row::new([
+            "NAME".to_string(),
+            "DRIVER".to_string(),
+            format!("SIZE{}", if self.by_size { " ▼" } else { "" }),
+            "REFS".to_string(),
+            "CONTAINERS".to_string(),
+            "MOUNTPOINT".to_string(),
+        ])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        let rows = self.rows.iter().enumerate().skip(top).take(height).map(|(index, row)| {
+            let cells = [
+                row.volume.name.clone(),
+                row.volume.driver.clone(),
+                row.size.map_or_else(|| "-".to_string(), format_bytes),
+                row.ref_count.map_or_else(|| "-".to_string(), |count| count.to_string()),
+                if row.is_orphaned() { "orphaned".to_string() } else { row.containers.join(", ") },
+                row.volume.mountpoint.clone(),
+            ];
+            let table_row = if row.is_orphaned() {
+                Row::new(cells).style(Style::default().fg(Color::Yellow))
+            } else {
+                Row::new(cells)
+            };
+            if index == selected {
+                table_row.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                table_row
+            }
+        });
+        let widths = [
+            Constraint::Min(16),
+            Constraint::Length(8),
+            Constraint::Length(11),
+            Constraint::Length(5),
+            Constraint::Min(12),
+            Constraint::Min(20),
+        ];
+        f.render_widget(Table::new(rows, widths).header(header).block(block), area);
+
+        if let Some(name) = &self.confirm {
+            render_dialog(f, &format!("Remove volume {name}?"));
+        }
+    }
+}
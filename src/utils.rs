@@ -3,7 +3,8 @@ pub fn format_duration(timestamp: i64) -> String {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
-        .as_secs() as i64;
+        .as_secs();
+    let now = i64::try_from(now).unwrap_or(i64::MAX);
     
     let duration = now - timestamp;
     if duration < 60 {
@@ -15,4 +16,103 @@ pub fn format_duration(timestamp: i64) -> String {
     } else {
         format!("{} days ago", duration / 86400)
     }
-} 
\ No newline at end of file
+} 
+/// Format a refresh interval compactly, e.g. `250ms`, `1s`, `1.5s` or `2m`
+pub fn format_interval(interval: std::time::Duration) -> String {
+    let millis = interval.as_millis();
+    if millis < 1000 {
+        format!("{millis}ms")
+    } else if millis % 60_000 == 0 {
+        format!("{}m", millis / 60_000)
+    } else if millis % 1000 == 0 {
+        format!("{}s", millis / 1000)
+    } else {
+        format!("{:.1}s", interval.as_secs_f64())
+    }
+}
+
+/// Parse a `--since` value: a Unix timestamp, or how long ago such as `30s`,
+/// `10m`, `2h` or `1d`
+pub fn parse_since(value: &str) -> Result<i64, String> {
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    let ago = parse_ago(value).ok_or_else(|| {
+        format!("invalid time '{value}': expected a Unix timestamp or a duration like 30s, 10m, 2h or 1d")
+    })?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    Ok(i64::try_from(now.saturating_sub(ago)).unwrap_or(i64::MAX))
+}
+
+fn parse_ago(value: &str) -> Option<u64> {
+    let (number, unit) = value.split_at(value.find(|c: char| !c.is_ascii_digit())?);
+    let number: u64 = number.parse().ok()?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    number.checked_mul(seconds)
+}
+
+/// Parse a `--tail` value: a line count, or `all` for `None`
+pub fn parse_tail(value: &str) -> Result<Option<usize>, String> {
+    if value == "all" {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("invalid tail '{value}': expected a number of lines or 'all'"))
+}
+
+/// Format a byte count with binary units, e.g. `512 B`, `1.5 KiB` or `2.0 GiB`
+// One decimal is shown, far coarser than what the cast loses
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Cut `text` to at most `width` characters, ending in `…` when shortened
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(width.saturating_sub(1)).collect();
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// Standard base64 with padding, as OSC 52 clipboard sequences expect
+pub fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, b)| bits | u32::from(*b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(bits >> (18 - 6 * i)) as usize & 63]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}